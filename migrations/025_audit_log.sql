@@ -0,0 +1,25 @@
+-- Persistent audit trail of administrative and destructive actions (retro
+-- creation, deletion and archiving, sign-in and sign-out, settings changes).
+--
+-- Rows are append-only. The target is stored as text (e.g. the retro slug)
+-- and further context goes into `details`, so entries outlive the objects
+-- they describe.
+
+CREATE TYPE audit_action AS ENUM (
+    'RETRO_CREATED', 'RETRO_DELETED', 'RETRO_ARCHIVED',
+    'USER_SIGNED_IN', 'USER_SIGNED_OUT'
+);
+
+CREATE TABLE audit_log (
+    id         BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    actor_id   INTEGER REFERENCES users(id) ON DELETE RESTRICT,
+    action     audit_action NOT NULL,
+    target     TEXT,
+    details    JSONB NOT NULL DEFAULT '{}'::jsonb,
+    ip_address TEXT,
+    user_agent TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX audit_log_created_at_idx ON audit_log(created_at);
+CREATE INDEX audit_log_actor_id_idx ON audit_log(actor_id);
@@ -0,0 +1,11 @@
+-- Track when a session was last used, so admins can see active sessions and
+-- force a logout. `public_id` identifies a session in the UI without exposing
+-- the session token itself (which is the cookie value).
+ALTER TABLE sessions
+    ADD COLUMN last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN public_id TEXT NOT NULL DEFAULT uuidv7()::text;
+
+CREATE UNIQUE INDEX sessions_public_id_idx ON sessions(public_id);
+
+ALTER TYPE audit_action ADD VALUE 'SESSION_REVOKED';
+ALTER TYPE audit_action ADD VALUE 'USER_SESSIONS_REVOKED';
@@ -0,0 +1,5 @@
+-- Remember which client a session was created from, so users can recognize
+-- (and revoke) their own sessions on the profile page.
+ALTER TABLE sessions
+    ADD COLUMN user_agent TEXT,
+    ADD COLUMN ip_address TEXT;
@@ -0,0 +1,24 @@
+-- Let users choose how they appear on cards: a nickname that replaces the
+-- GitHub name, custom initials instead of the computed ones, and whether the
+-- GitHub avatar is shown.
+ALTER TABLE users
+    ADD COLUMN nickname TEXT,
+    ADD COLUMN initials TEXT,
+    ADD COLUMN show_avatar BOOLEAN NOT NULL DEFAULT TRUE;
+
+ALTER TABLE users
+    ADD CONSTRAINT users_nickname_length_check
+    CHECK (length(nickname) BETWEEN 1 AND 100);
+
+ALTER TABLE users
+    ADD CONSTRAINT users_initials_length_check
+    CHECK (length(initials) BETWEEN 1 AND 4);
+
+-- The nickname takes precedence over the GitHub name everywhere a user's name
+-- is displayed.
+ALTER TABLE users DROP COLUMN display_name;
+ALTER TABLE users
+    ADD COLUMN display_name TEXT
+    GENERATED ALWAYS AS (COALESCE(nickname, full_name, username)) VIRTUAL;
+
+ALTER TYPE audit_action ADD VALUE 'PROFILE_UPDATED';
@@ -0,0 +1,46 @@
+-- Who is looking at which retro right now.
+--
+-- Every app instance records the SSE connections it serves, one row per retro,
+-- user and instance (a user with several tabs on the same instance shares a
+-- row).  Instances refresh heartbeat_at periodically and delete rows whose
+-- instance stopped heartbeating (crashed or killed without cleanup).  The
+-- table is UNLOGGED: presence is worthless after a database restart anyway.
+
+CREATE UNLOGGED TABLE presence (
+    retro_id     INTEGER NOT NULL REFERENCES retrospectives(id) ON DELETE CASCADE,
+    user_id      INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    instance_id  TEXT NOT NULL,
+    connections  INTEGER NOT NULL CHECK (connections > 0),
+    joined_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (retro_id, user_id, instance_id)
+);
+
+CREATE INDEX presence_instance_id_idx ON presence(instance_id);
+
+-- Joins and leaves NOTIFY the 'rostfacto_presence' channel so every instance
+-- can push the new participant list to its subscribers.  An additional
+-- connection of someone already present notifies, too, so the new connection
+-- receives the list; closing one of several connections and heartbeats do not
+-- change who is present and stay silent.
+CREATE OR REPLACE FUNCTION notify_presence_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM pg_notify('rostfacto_presence', OLD.retro_id::text);
+    ELSIF TG_OP = 'INSERT' OR NEW.connections > OLD.connections THEN
+        PERFORM pg_notify('rostfacto_presence', NEW.retro_id::text);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER presence_change_notify
+    AFTER INSERT OR DELETE OR UPDATE OF connections ON presence
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_presence_change();
+
+-- How many people took part in an archived retro: those present when it was
+-- archived plus the authors of the archived cards.  NULL for archives made
+-- before presence was tracked.
+ALTER TABLE archives ADD COLUMN participants_count INTEGER;
@@ -0,0 +1,16 @@
+-- Retention for the event log.
+--
+-- A background job deletes events older than the configured retention period
+-- and, per retro, events older than its latest archive (they describe a board
+-- that has been cleared).  Since event ids are global, gaps in a retro's ids
+-- are normal and cannot tell a reconnecting client that it missed deleted
+-- events; instead the job records the highest id it deleted per retro.  A
+-- client whose Last-Event-ID is below that mark is told to reload.
+
+CREATE TABLE event_retention (
+    retro_id       INTEGER PRIMARY KEY REFERENCES retrospectives(id) ON DELETE CASCADE,
+    pruned_through BIGINT NOT NULL,
+    pruned_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX events_created_at_idx ON events(created_at);
@@ -0,0 +1,71 @@
+-- Action items join the event log, so clients (and API consumers using the
+-- full-payload stream mode) learn about them like about cards.
+
+ALTER TYPE event_type ADD VALUE 'ACTION_ITEM_CREATED';
+ALTER TYPE event_type ADD VALUE 'ACTION_ITEM_UPDATED';
+ALTER TYPE event_type ADD VALUE 'ACTION_ITEM_COMPLETED';
+ALTER TYPE event_type ADD VALUE 'ACTION_ITEM_DELETED';
+
+ALTER TABLE events ADD COLUMN action_item_id INTEGER; -- NULL unless about an action item
+
+-- Like emit_item_event(): one event per insert, text change, completion and
+-- deletion.  Archive updates are excluded by the WHEN clause (RETRO_ARCHIVED
+-- covers them), and deletions cascading from a deleted retro are skipped.
+CREATE OR REPLACE FUNCTION emit_action_item_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_event_type event_type;
+    v_row        action_items;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        IF NOT EXISTS (SELECT 1 FROM retrospectives WHERE id = OLD.retro_id) THEN
+            RETURN NULL; -- parent retro is being deleted
+        END IF;
+        v_event_type := 'ACTION_ITEM_DELETED';
+        v_row := OLD;
+    ELSIF TG_OP = 'INSERT' THEN
+        v_event_type := 'ACTION_ITEM_CREATED';
+        v_row := NEW;
+    ELSIF OLD.completed_at IS NULL AND NEW.completed_at IS NOT NULL THEN
+        v_event_type := 'ACTION_ITEM_COMPLETED';
+        v_row := NEW;
+    ELSIF OLD.text IS DISTINCT FROM NEW.text THEN
+        v_event_type := 'ACTION_ITEM_UPDATED';
+        v_row := NEW;
+    ELSE
+        RETURN NULL;
+    END IF;
+
+    INSERT INTO events (retro_id, event_type, action_item_id, payload)
+    VALUES (
+        v_row.retro_id,
+        v_event_type,
+        v_row.id,
+        jsonb_build_object(
+            'action_item_id', v_row.id,
+            'retro_id', v_row.retro_id,
+            'text', v_row.text,
+            'completed_at', v_row.completed_at
+        )
+    );
+
+    PERFORM pg_notify('rostfacto_events', v_row.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER action_items_insert_event
+    AFTER INSERT ON action_items
+    FOR EACH ROW
+    EXECUTE FUNCTION emit_action_item_event();
+
+CREATE TRIGGER action_items_update_event
+    AFTER UPDATE ON action_items
+    FOR EACH ROW
+    WHEN (OLD.archive_id IS NOT DISTINCT FROM NEW.archive_id)
+    EXECUTE FUNCTION emit_action_item_event();
+
+CREATE TRIGGER action_items_delete_event
+    AFTER DELETE ON action_items
+    FOR EACH ROW
+    EXECUTE FUNCTION emit_action_item_event();
@@ -0,0 +1,86 @@
+-- Undo: events remember who caused them, so each user can take back their
+-- own recent destructive actions, and which event an undo reverses.
+--
+-- The app sets two transaction-local settings before mutating:
+--   rostfacto.actor_id        the user making the change
+--   rostfacto.undoes_event_id the event being undone (only while undoing)
+-- A BEFORE INSERT trigger copies them to every event the transaction writes,
+-- so the existing event triggers need not know about them.
+
+ALTER TYPE event_type ADD VALUE 'ACTION_UNDONE';
+
+ALTER TABLE events
+    ADD COLUMN actor_id        INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN undoes_event_id BIGINT; -- no FK: retention may delete the undone event
+
+CREATE INDEX events_actor_idx ON events (actor_id, retro_id, id) WHERE actor_id IS NOT NULL;
+CREATE INDEX events_undoes_event_id_idx ON events (undoes_event_id) WHERE undoes_event_id IS NOT NULL;
+
+CREATE OR REPLACE FUNCTION set_event_actor()
+RETURNS TRIGGER AS $$
+BEGIN
+    -- A setting that was only ever set locally reads as '' after its
+    -- transaction ended.
+    NEW.actor_id := COALESCE(
+        NEW.actor_id,
+        NULLIF(current_setting('rostfacto.actor_id', true), '')::integer
+    );
+    NEW.undoes_event_id := COALESCE(
+        NEW.undoes_event_id,
+        NULLIF(current_setting('rostfacto.undoes_event_id', true), '')::bigint
+    );
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER events_set_actor
+    BEFORE INSERT ON events
+    FOR EACH ROW
+    EXECUTE FUNCTION set_event_actor();
+
+-- Action item events carry what an undo needs to restore: the creation time
+-- (for deletions) and the previous text (for edits).
+CREATE OR REPLACE FUNCTION emit_action_item_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_event_type event_type;
+    v_row        action_items;
+    v_payload    JSONB;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        IF NOT EXISTS (SELECT 1 FROM retrospectives WHERE id = OLD.retro_id) THEN
+            RETURN NULL; -- parent retro is being deleted
+        END IF;
+        v_event_type := 'ACTION_ITEM_DELETED';
+        v_row := OLD;
+    ELSIF TG_OP = 'INSERT' THEN
+        v_event_type := 'ACTION_ITEM_CREATED';
+        v_row := NEW;
+    ELSIF OLD.completed_at IS NULL AND NEW.completed_at IS NOT NULL THEN
+        v_event_type := 'ACTION_ITEM_COMPLETED';
+        v_row := NEW;
+    ELSIF OLD.text IS DISTINCT FROM NEW.text THEN
+        v_event_type := 'ACTION_ITEM_UPDATED';
+        v_row := NEW;
+    ELSE
+        RETURN NULL;
+    END IF;
+
+    v_payload := jsonb_build_object(
+        'action_item_id', v_row.id,
+        'retro_id', v_row.retro_id,
+        'text', v_row.text,
+        'created_at', v_row.created_at,
+        'completed_at', v_row.completed_at
+    );
+    IF v_event_type = 'ACTION_ITEM_UPDATED' THEN
+        v_payload := v_payload || jsonb_build_object('old_text', OLD.text);
+    END IF;
+
+    INSERT INTO events (retro_id, event_type, action_item_id, payload)
+    VALUES (v_row.retro_id, v_event_type, v_row.id, v_payload);
+
+    PERFORM pg_notify('rostfacto_events', v_row.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
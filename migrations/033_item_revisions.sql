@@ -0,0 +1,35 @@
+-- Card edit history: one row per text change, with who made it.  Revisions
+-- belong to the card, so they stay with it when it is archived.
+
+CREATE TABLE item_revisions (
+    id         BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    item_id    INTEGER NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    editor_id  INTEGER REFERENCES users(id) ON DELETE SET NULL, -- NULL when unknown
+    old_text   TEXT NOT NULL,
+    new_text   TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX item_revisions_item_id_idx ON item_revisions (item_id, id);
+
+-- The editor is the actor the app sets for the transaction (see
+-- 032_undo.sql), so every way of changing the text is recorded.
+CREATE OR REPLACE FUNCTION record_item_revision()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO item_revisions (item_id, editor_id, old_text, new_text)
+    VALUES (
+        NEW.id,
+        NULLIF(current_setting('rostfacto.actor_id', true), '')::integer,
+        OLD.text,
+        NEW.text
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER items_record_revision
+    AFTER UPDATE OF text ON items
+    FOR EACH ROW
+    WHEN (OLD.text IS DISTINCT FROM NEW.text)
+    EXECUTE FUNCTION record_item_revision();
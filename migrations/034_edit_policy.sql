@@ -0,0 +1,9 @@
+-- Who may change the text of a card: only its author, its author and the
+-- facilitators of the retro (its creator and admins), or everyone with access
+-- to the retro. Existing retros stay open, as before.
+CREATE TYPE edit_policy AS ENUM ('AUTHOR', 'AUTHOR_AND_FACILITATOR', 'OPEN');
+
+ALTER TABLE retrospectives
+    ADD COLUMN edit_policy edit_policy NOT NULL DEFAULT 'OPEN';
+
+ALTER TYPE audit_action ADD VALUE 'RETRO_EDIT_POLICY_CHANGED';
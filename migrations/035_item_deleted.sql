@@ -0,0 +1,40 @@
+-- Cards can be deleted from the board.  Their likes and revisions go with them
+-- (ON DELETE CASCADE); the like trigger already skips likes whose card is gone,
+-- so a deletion writes a single ITEM_DELETED event instead of one ITEM_UNLIKED
+-- per like.
+
+ALTER TYPE event_type ADD VALUE 'ITEM_DELETED';
+
+-- Deletions cascading from a deleted retro are skipped, like for action items.
+CREATE OR REPLACE FUNCTION emit_item_deleted_event()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM retrospectives WHERE id = OLD.retro_id) THEN
+        RETURN NULL; -- parent retro is being deleted
+    END IF;
+
+    INSERT INTO events (retro_id, event_type, item_id, payload)
+    VALUES (
+        OLD.retro_id,
+        'ITEM_DELETED',
+        OLD.id,
+        jsonb_build_object(
+            'item_id', OLD.id,
+            'retro_id', OLD.retro_id,
+            'category', OLD.category,
+            'text', OLD.text,
+            'status', OLD.status
+        )
+    );
+
+    PERFORM pg_notify('rostfacto_events', OLD.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Archived cards are part of an archive and never deleted on their own.
+CREATE TRIGGER items_delete_event
+    AFTER DELETE ON items
+    FOR EACH ROW
+    WHEN (OLD.archive_id IS NULL)
+    EXECUTE FUNCTION emit_item_deleted_event();
@@ -0,0 +1,78 @@
+-- Cards can be moved to another column.  The item event trigger reports a
+-- category change as ITEM_MOVED; it ranks right after ITEM_STATUS_CHANGED in
+-- the precedence described in 022_item_timers.sql (a move never changes the
+-- status or the timer in the same UPDATE).
+
+ALTER TYPE event_type ADD VALUE 'ITEM_MOVED';
+
+CREATE OR REPLACE FUNCTION emit_item_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_event_type event_type;
+    v_payload    JSONB;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        v_event_type := 'ITEM_CREATED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'retro_id', NEW.retro_id,
+            'category', NEW.category,
+            'text', NEW.text,
+            'status', NEW.status,
+            'likes_count', 0,
+            -- The client re-fetches /items/{id} for full card re-renders, so
+            -- only the author name is included here; author initials are
+            -- derived per-retro by the app (disambiguation), not in SQL.
+            'author_name', (SELECT display_name FROM users WHERE id = NEW.created_by)
+        );
+    ELSIF OLD.status IS DISTINCT FROM NEW.status THEN
+        v_event_type := 'ITEM_STATUS_CHANGED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'old_status', OLD.status,
+            'new_status', NEW.status
+        );
+    ELSIF OLD.category IS DISTINCT FROM NEW.category THEN
+        v_event_type := 'ITEM_MOVED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'old_category', OLD.category,
+            'new_category', NEW.category
+        );
+    ELSIF OLD.timer_elapsed_at IS NULL AND NEW.timer_elapsed_at IS NOT NULL THEN
+        v_event_type := 'TIMER_ELAPSED';
+        v_payload := jsonb_build_object('item_id', NEW.id);
+    ELSIF OLD.timer_started_at IS NULL AND NEW.timer_started_at IS NOT NULL THEN
+        v_event_type := 'TIMER_STARTED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'duration_seconds', NEW.timer_duration_seconds,
+            'started_at', NEW.timer_started_at,
+            -- The timer_ends_at virtual generated column reads as NULL from
+            -- trigger NEW, so compute the deadline here instead.
+            'ends_at', NEW.timer_started_at + (NEW.timer_duration_seconds * INTERVAL '1 second')
+        );
+    ELSIF OLD.timer_started_at IS DISTINCT FROM NEW.timer_started_at
+       OR OLD.timer_duration_seconds IS DISTINCT FROM NEW.timer_duration_seconds
+       OR OLD.timer_elapsed_at IS DISTINCT FROM NEW.timer_elapsed_at THEN
+        v_event_type := 'TIMER_EXTENDED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'duration_seconds', NEW.timer_duration_seconds,
+            'started_at', NEW.timer_started_at,
+            'ends_at', NEW.timer_started_at + (NEW.timer_duration_seconds * INTERVAL '1 second')
+        );
+    ELSIF OLD.text IS DISTINCT FROM NEW.text THEN
+        v_event_type := 'ITEM_UPDATED';
+        v_payload := jsonb_build_object('item_id', NEW.id, 'text', NEW.text);
+    ELSE
+        RETURN NULL; -- no interesting change (e.g. only updated_at)
+    END IF;
+
+    INSERT INTO events (retro_id, event_type, item_id, payload)
+    VALUES (NEW.retro_id, v_event_type, NEW.id, v_payload);
+
+    PERFORM pg_notify('rostfacto_events', NEW.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,190 @@
+-- Cards have a manual order within their column.  items.position is a
+-- fractional index: a key over the digits 0-9A-Za-z compared bytewise
+-- (COLLATE "C"), so a card can always be placed between two others by writing
+-- a key that sorts between theirs, without renumbering the column.  Keys never
+-- end in '0', which keeps room in front of every key.
+--
+-- Keys of one retro are only computed while holding lock_item_positions(), so
+-- concurrent inserts, moves and reorders never hand out the same key twice.
+-- A reorder is reported as ITEM_REORDERED, right after ITEM_MOVED in the
+-- precedence described in 022_item_timers.sql (a move also sets a position,
+-- which ITEM_MOVED carries).
+
+ALTER TYPE event_type ADD VALUE 'ITEM_REORDERED';
+
+-- A key that sorts strictly between lo and hi; NULL stands for the start or
+-- the end of the column.
+CREATE FUNCTION item_position_between(lo TEXT, hi TEXT)
+RETURNS TEXT AS $$
+DECLARE
+    digits CONSTANT TEXT := '0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz';
+    a      TEXT := COALESCE(lo, '');
+    b      TEXT := hi;
+    key    TEXT := '';
+    da     INTEGER;
+    db     INTEGER;
+BEGIN
+    IF b IS NOT NULL AND a COLLATE "C" >= b COLLATE "C" THEN
+        RAISE EXCEPTION 'item position % is not before %', lo, hi;
+    END IF;
+
+    LOOP
+        -- Shared prefix; a missing digit of lo counts as '0'.
+        IF b IS NOT NULL THEN
+            WHILE b <> '' AND left(b, 1) = COALESCE(NULLIF(left(a, 1), ''), '0') LOOP
+                key := key || left(b, 1);
+                a := substr(a, 2);
+                b := substr(b, 2);
+            END LOOP;
+        END IF;
+
+        da := COALESCE(strpos(digits, NULLIF(left(a, 1), '')) - 1, 0);
+        db := CASE WHEN b IS NULL THEN length(digits) ELSE strpos(digits, left(b, 1)) - 1 END;
+
+        IF db - da > 1 THEN
+            RETURN key || substr(digits, (da + db + 1) / 2 + 1, 1);
+        ELSIF b IS NOT NULL AND length(b) > 1 THEN
+            RETURN key || left(b, 1);
+        END IF;
+
+        -- Adjacent digits: keep lo's digit and find room after the rest of lo.
+        key := key || substr(digits, da + 1, 1);
+        a := substr(a, 2);
+        b := NULL;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+CREATE FUNCTION lock_item_positions(p_retro_id INTEGER)
+RETURNS VOID AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock(1886352238, p_retro_id); -- "posn"
+END;
+$$ LANGUAGE plpgsql;
+
+-- The key after the last card of a column (locks the retro's positions).
+CREATE FUNCTION item_position_at_end(p_retro_id INTEGER, p_category category)
+RETURNS TEXT AS $$
+BEGIN
+    PERFORM lock_item_positions(p_retro_id);
+    RETURN item_position_between(
+        (SELECT max(position) FROM items
+         WHERE retro_id = p_retro_id AND category = p_category AND archive_id IS NULL),
+        NULL
+    );
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE items ADD COLUMN position TEXT COLLATE "C";
+
+-- Existing cards keep their creation order.
+UPDATE items SET position = ranked.position
+FROM (
+    SELECT id,
+           lpad((row_number() OVER (PARTITION BY retro_id, category, archive_id
+                                    ORDER BY created_at, id))::text, 10, '0') || 'V' AS position
+    FROM items
+) ranked
+WHERE items.id = ranked.id;
+
+ALTER TABLE items ALTER COLUMN position SET NOT NULL;
+
+CREATE INDEX items_position_idx ON items (retro_id, category, position)
+WHERE archive_id IS NULL;
+
+-- New cards go to the end of their column unless the insert sets a position.
+CREATE FUNCTION assign_item_position()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.position IS NULL THEN
+        NEW.position := item_position_at_end(NEW.retro_id, NEW.category);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER items_assign_position
+    BEFORE INSERT ON items
+    FOR EACH ROW
+    EXECUTE FUNCTION assign_item_position();
+
+CREATE OR REPLACE FUNCTION emit_item_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_event_type event_type;
+    v_payload    JSONB;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        v_event_type := 'ITEM_CREATED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'retro_id', NEW.retro_id,
+            'category', NEW.category,
+            'text', NEW.text,
+            'status', NEW.status,
+            'likes_count', 0,
+            'position', NEW.position,
+            -- The client re-fetches /items/{id} for full card re-renders, so
+            -- only the author name is included here; author initials are
+            -- derived per-retro by the app (disambiguation), not in SQL.
+            'author_name', (SELECT display_name FROM users WHERE id = NEW.created_by)
+        );
+    ELSIF OLD.status IS DISTINCT FROM NEW.status THEN
+        v_event_type := 'ITEM_STATUS_CHANGED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'old_status', OLD.status,
+            'new_status', NEW.status
+        );
+    ELSIF OLD.category IS DISTINCT FROM NEW.category THEN
+        v_event_type := 'ITEM_MOVED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'old_category', OLD.category,
+            'new_category', NEW.category,
+            'position', NEW.position
+        );
+    ELSIF OLD.position IS DISTINCT FROM NEW.position THEN
+        v_event_type := 'ITEM_REORDERED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'category', NEW.category,
+            'position', NEW.position
+        );
+    ELSIF OLD.timer_elapsed_at IS NULL AND NEW.timer_elapsed_at IS NOT NULL THEN
+        v_event_type := 'TIMER_ELAPSED';
+        v_payload := jsonb_build_object('item_id', NEW.id);
+    ELSIF OLD.timer_started_at IS NULL AND NEW.timer_started_at IS NOT NULL THEN
+        v_event_type := 'TIMER_STARTED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'duration_seconds', NEW.timer_duration_seconds,
+            'started_at', NEW.timer_started_at,
+            -- The timer_ends_at virtual generated column reads as NULL from
+            -- trigger NEW, so compute the deadline here instead.
+            'ends_at', NEW.timer_started_at + (NEW.timer_duration_seconds * INTERVAL '1 second')
+        );
+    ELSIF OLD.timer_started_at IS DISTINCT FROM NEW.timer_started_at
+       OR OLD.timer_duration_seconds IS DISTINCT FROM NEW.timer_duration_seconds
+       OR OLD.timer_elapsed_at IS DISTINCT FROM NEW.timer_elapsed_at THEN
+        v_event_type := 'TIMER_EXTENDED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'duration_seconds', NEW.timer_duration_seconds,
+            'started_at', NEW.timer_started_at,
+            'ends_at', NEW.timer_started_at + (NEW.timer_duration_seconds * INTERVAL '1 second')
+        );
+    ELSIF OLD.text IS DISTINCT FROM NEW.text THEN
+        v_event_type := 'ITEM_UPDATED';
+        v_payload := jsonb_build_object('item_id', NEW.id, 'text', NEW.text);
+    ELSE
+        RETURN NULL; -- no interesting change (e.g. only updated_at)
+    END IF;
+
+    INSERT INTO events (retro_id, event_type, item_id, payload)
+    VALUES (NEW.retro_id, v_event_type, NEW.id, v_payload);
+
+    PERFORM pg_notify('rostfacto_events', NEW.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
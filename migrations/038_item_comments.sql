@@ -0,0 +1,89 @@
+-- Comments on cards: short notes taken while a highlighted card is
+-- discussed.  Comments belong to the card, so they stay with it when it is
+-- archived and go away when it is deleted.
+
+ALTER TYPE event_type ADD VALUE 'ITEM_COMMENT_ADDED';
+ALTER TYPE event_type ADD VALUE 'ITEM_COMMENT_DELETED';
+
+CREATE TABLE item_comments (
+    id         BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    item_id    INTEGER NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    created_by INTEGER NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    text       TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Mirrored by MAX_COMMENT_TEXT_LENGTH in src/handlers.rs.
+ALTER TABLE item_comments
+    ADD CONSTRAINT item_comments_text_length_check
+    CHECK (length(text) <= 500);
+
+CREATE INDEX item_comments_item_id_idx ON item_comments (item_id, id);
+
+-- One event per added or deleted comment, about the card it belongs to.
+-- Deletions cascading from a deleted card (or retro) are skipped; the card's
+-- ITEM_DELETED event covers them.
+CREATE OR REPLACE FUNCTION emit_item_comment_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_retro_id INTEGER;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        SELECT retro_id INTO v_retro_id FROM items WHERE id = NEW.item_id;
+        INSERT INTO events (retro_id, event_type, item_id, payload)
+        VALUES (
+            v_retro_id,
+            'ITEM_COMMENT_ADDED',
+            NEW.item_id,
+            jsonb_build_object(
+                'item_id', NEW.item_id,
+                'comment_id', NEW.id,
+                'text', NEW.text,
+                'author_name', (SELECT display_name FROM users WHERE id = NEW.created_by),
+                'created_at', NEW.created_at
+            )
+        );
+    ELSE
+        SELECT retro_id INTO v_retro_id FROM items WHERE id = OLD.item_id;
+        IF v_retro_id IS NULL THEN
+            RETURN NULL; -- parent card is being deleted
+        END IF;
+        INSERT INTO events (retro_id, event_type, item_id, payload)
+        VALUES (
+            v_retro_id,
+            'ITEM_COMMENT_DELETED',
+            OLD.item_id,
+            jsonb_build_object('item_id', OLD.item_id, 'comment_id', OLD.id)
+        );
+    END IF;
+
+    PERFORM pg_notify('rostfacto_events', v_retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER item_comments_event
+    AFTER INSERT OR DELETE ON item_comments
+    FOR EACH ROW
+    EXECUTE FUNCTION emit_item_comment_event();
+
+-- The comments of a card, oldest first, as the app renders them.
+CREATE FUNCTION item_comments_json(p_item_id INTEGER)
+RETURNS JSONB AS $$
+    SELECT COALESCE(
+        jsonb_agg(
+            jsonb_build_object(
+                'id', c.id,
+                'author_id', c.created_by,
+                'author_name', u.display_name,
+                'text', c.text,
+                'created_at', c.created_at
+            )
+            ORDER BY c.id
+        ),
+        '[]'::jsonb
+    )
+    FROM item_comments c
+    JOIN users u ON u.id = c.created_by
+    WHERE c.item_id = p_item_id
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,92 @@
+-- Emoji reactions on cards, next to the likes.  Every user can give each
+-- reaction once per card; toggling it off deletes the row.
+
+CREATE TYPE reaction AS ENUM ('PLUS_ONE', 'PARTY', 'LAUGH', 'CONFUSED');
+
+ALTER TYPE event_type ADD VALUE 'ITEM_REACTED';
+ALTER TYPE event_type ADD VALUE 'ITEM_UNREACTED';
+
+CREATE TABLE reactions (
+    item_id    INTEGER NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    user_id    INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reaction   reaction NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (item_id, user_id, reaction)
+);
+
+-- The reactions of a card, in the order of the enum, with who gave them.
+CREATE FUNCTION item_reactions_json(p_item_id INTEGER)
+RETURNS JSONB AS $$
+    SELECT COALESCE(
+        jsonb_agg(
+            jsonb_build_object('reaction', reaction, 'count', count, 'user_ids', user_ids)
+            ORDER BY reaction
+        ),
+        '[]'::jsonb
+    )
+    FROM (
+        SELECT reaction, COUNT(*) AS count, array_agg(user_id ORDER BY created_at) AS user_ids
+        FROM reactions
+        WHERE item_id = p_item_id
+        GROUP BY reaction
+    ) counts
+$$ LANGUAGE sql STABLE;
+
+-- Like emit_like_event(): ITEM_REACTED / ITEM_UNREACTED with the recomputed
+-- count of the toggled reaction and the counts of all reactions of the card.
+-- Events are skipped when the card is already gone (cascade delete).
+CREATE OR REPLACE FUNCTION emit_reaction_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_row      reactions;
+    v_retro_id INTEGER;
+    v_counts   JSONB;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        v_row := OLD;
+    ELSE
+        v_row := NEW;
+    END IF;
+
+    SELECT retro_id INTO v_retro_id FROM items WHERE id = v_row.item_id;
+    IF v_retro_id IS NULL THEN
+        RETURN NULL; -- parent retro/item is being cascade-deleted
+    END IF;
+
+    SELECT COALESCE(jsonb_object_agg(reaction, count), '{}'::jsonb) INTO v_counts
+    FROM (
+        SELECT reaction, COUNT(*) AS count
+        FROM reactions
+        WHERE item_id = v_row.item_id
+        GROUP BY reaction
+    ) counts;
+
+    INSERT INTO events (retro_id, event_type, item_id, payload)
+    VALUES (
+        v_retro_id,
+        CASE WHEN TG_OP = 'INSERT' THEN 'ITEM_REACTED'::event_type
+             ELSE 'ITEM_UNREACTED'::event_type END,
+        v_row.item_id,
+        jsonb_build_object(
+            'item_id', v_row.item_id,
+            'user_id', v_row.user_id,
+            'reaction', v_row.reaction,
+            'count', COALESCE((v_counts ->> v_row.reaction::text)::bigint, 0),
+            'counts', v_counts
+        )
+    );
+
+    PERFORM pg_notify('rostfacto_events', v_retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reactions_insert_event
+    AFTER INSERT ON reactions
+    FOR EACH ROW
+    EXECUTE FUNCTION emit_reaction_event();
+
+CREATE TRIGGER reactions_delete_event
+    AFTER DELETE ON reactions
+    FOR EACH ROW
+    EXECUTE FUNCTION emit_reaction_event();
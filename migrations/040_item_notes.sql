@@ -0,0 +1,44 @@
+-- Discussion notes: one shared text per card, written by the team while the
+-- card is highlighted and kept with it in the archive.  Every save replaces
+-- the text (last writer wins) and bumps notes_revision, which lets clients
+-- drop updates that arrive out of order.
+
+ALTER TYPE event_type ADD VALUE 'ITEM_NOTES_UPDATED';
+
+ALTER TABLE items ADD COLUMN notes TEXT NOT NULL DEFAULT '';
+ALTER TABLE items ADD COLUMN notes_revision INTEGER NOT NULL DEFAULT 0;
+
+-- Mirrored by MAX_NOTES_LENGTH in src/handlers.rs.
+ALTER TABLE items
+    ADD CONSTRAINT items_notes_length_check
+    CHECK (length(notes) <= 5000);
+
+-- emit_item_event() ignores updates that only change the notes, so they get
+-- their own trigger.  The editor is the actor the app sets for the
+-- transaction (see 032_undo.sql).
+CREATE OR REPLACE FUNCTION emit_item_notes_event()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO events (retro_id, event_type, item_id, payload)
+    VALUES (
+        NEW.retro_id,
+        'ITEM_NOTES_UPDATED',
+        NEW.id,
+        jsonb_build_object(
+            'item_id', NEW.id,
+            'notes', NEW.notes,
+            'notes_revision', NEW.notes_revision,
+            'editor_id', NULLIF(current_setting('rostfacto.actor_id', true), '')::integer
+        )
+    );
+
+    PERFORM pg_notify('rostfacto_events', NEW.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER items_notes_event
+    AFTER UPDATE OF notes ON items
+    FOR EACH ROW
+    WHEN (OLD.notes IS DISTINCT FROM NEW.notes AND OLD.archive_id IS NOT DISTINCT FROM NEW.archive_id)
+    EXECUTE FUNCTION emit_item_notes_event();
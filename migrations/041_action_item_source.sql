@@ -0,0 +1,55 @@
+-- Action items can be created from a card.  They keep a link to the card they
+-- came from, shown on the board and in the archive; deleting the card only
+-- drops the link.  Action item events carry the link as source_item_id, so
+-- undoing a deletion restores it.
+
+ALTER TABLE action_items
+    ADD COLUMN source_item_id INTEGER REFERENCES items(id) ON DELETE SET NULL;
+
+CREATE INDEX action_items_source_item_id_idx ON action_items(source_item_id);
+
+CREATE OR REPLACE FUNCTION emit_action_item_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_event_type event_type;
+    v_row        action_items;
+    v_payload    JSONB;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        IF NOT EXISTS (SELECT 1 FROM retrospectives WHERE id = OLD.retro_id) THEN
+            RETURN NULL; -- parent retro is being deleted
+        END IF;
+        v_event_type := 'ACTION_ITEM_DELETED';
+        v_row := OLD;
+    ELSIF TG_OP = 'INSERT' THEN
+        v_event_type := 'ACTION_ITEM_CREATED';
+        v_row := NEW;
+    ELSIF OLD.completed_at IS NULL AND NEW.completed_at IS NOT NULL THEN
+        v_event_type := 'ACTION_ITEM_COMPLETED';
+        v_row := NEW;
+    ELSIF OLD.text IS DISTINCT FROM NEW.text THEN
+        v_event_type := 'ACTION_ITEM_UPDATED';
+        v_row := NEW;
+    ELSE
+        RETURN NULL;
+    END IF;
+
+    v_payload := jsonb_build_object(
+        'action_item_id', v_row.id,
+        'retro_id', v_row.retro_id,
+        'text', v_row.text,
+        'created_at', v_row.created_at,
+        'completed_at', v_row.completed_at,
+        'source_item_id', v_row.source_item_id
+    );
+    IF v_event_type = 'ACTION_ITEM_UPDATED' THEN
+        v_payload := v_payload || jsonb_build_object('old_text', OLD.text);
+    END IF;
+
+    INSERT INTO events (retro_id, event_type, action_item_id, payload)
+    VALUES (v_row.retro_id, v_event_type, v_row.id, v_payload);
+
+    PERFORM pg_notify('rostfacto_events', v_row.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
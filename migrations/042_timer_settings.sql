@@ -0,0 +1,122 @@
+-- Per-retro highlight timer settings, and pausing a running timer.
+--
+-- A retro chooses the length of a discussion, the step of "extend", an
+-- optional cap on the total length (NULL: none) and whether highlighting a
+-- card starts its timer.  Existing retros keep the previous behaviour.
+--
+-- A paused timer records when it was paused; the remaining time is frozen at
+-- timer_ends_at - timer_paused_at.  Resuming moves timer_started_at forward by
+-- the pause, so timer_duration_seconds stays the total length (and the cap
+-- applies to it) and timer_ends_at is the new deadline.  The sweep skips
+-- paused timers.
+
+ALTER TYPE event_type ADD VALUE 'TIMER_PAUSED';
+ALTER TYPE event_type ADD VALUE 'TIMER_RESUMED';
+
+ALTER TABLE retrospectives
+    ADD COLUMN timer_default_seconds INTEGER NOT NULL DEFAULT 300,
+    ADD COLUMN timer_extend_seconds INTEGER NOT NULL DEFAULT 120,
+    ADD COLUMN timer_max_seconds INTEGER,
+    ADD COLUMN timer_auto_start BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD CONSTRAINT retrospectives_timer_default_seconds_check
+        CHECK (timer_default_seconds BETWEEN 1 AND 3600),
+    ADD CONSTRAINT retrospectives_timer_extend_seconds_check
+        CHECK (timer_extend_seconds BETWEEN 1 AND 3600),
+    ADD CONSTRAINT retrospectives_timer_max_seconds_check
+        CHECK (timer_max_seconds BETWEEN timer_default_seconds AND 14400);
+
+ALTER TABLE items
+    ADD COLUMN timer_paused_at TIMESTAMPTZ;
+
+ALTER TYPE audit_action ADD VALUE 'RETRO_TIMER_SETTINGS_CHANGED';
+
+-- Pausing and resuming rank after TIMER_STARTED in the precedence described
+-- in 022_item_timers.sql.  Restarting a paused timer clears the pause and is
+-- reported as TIMER_RESUMED, with the new deadline.  Timer payloads carry
+-- paused_at, so clients freeze the countdown of a paused timer at
+-- ends_at - paused_at.
+CREATE OR REPLACE FUNCTION emit_item_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_event_type event_type;
+    v_payload    JSONB;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        v_event_type := 'ITEM_CREATED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'retro_id', NEW.retro_id,
+            'category', NEW.category,
+            'text', NEW.text,
+            'status', NEW.status,
+            'likes_count', 0,
+            'position', NEW.position,
+            -- The client re-fetches /items/{id} for full card re-renders, so
+            -- only the author name is included here; author initials are
+            -- derived per-retro by the app (disambiguation), not in SQL.
+            'author_name', (SELECT display_name FROM users WHERE id = NEW.created_by)
+        );
+    ELSIF OLD.status IS DISTINCT FROM NEW.status THEN
+        v_event_type := 'ITEM_STATUS_CHANGED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'old_status', OLD.status,
+            'new_status', NEW.status
+        );
+    ELSIF OLD.category IS DISTINCT FROM NEW.category THEN
+        v_event_type := 'ITEM_MOVED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'old_category', OLD.category,
+            'new_category', NEW.category,
+            'position', NEW.position
+        );
+    ELSIF OLD.position IS DISTINCT FROM NEW.position THEN
+        v_event_type := 'ITEM_REORDERED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'category', NEW.category,
+            'position', NEW.position
+        );
+    ELSIF OLD.timer_elapsed_at IS NULL AND NEW.timer_elapsed_at IS NOT NULL THEN
+        v_event_type := 'TIMER_ELAPSED';
+        v_payload := jsonb_build_object('item_id', NEW.id);
+    ELSE
+        IF OLD.timer_started_at IS NULL AND NEW.timer_started_at IS NOT NULL THEN
+            v_event_type := 'TIMER_STARTED';
+        ELSIF OLD.timer_paused_at IS NULL AND NEW.timer_paused_at IS NOT NULL THEN
+            v_event_type := 'TIMER_PAUSED';
+        ELSIF OLD.timer_paused_at IS NOT NULL AND NEW.timer_paused_at IS NULL THEN
+            v_event_type := 'TIMER_RESUMED';
+        ELSIF OLD.timer_started_at IS DISTINCT FROM NEW.timer_started_at
+           OR OLD.timer_duration_seconds IS DISTINCT FROM NEW.timer_duration_seconds
+           OR OLD.timer_elapsed_at IS DISTINCT FROM NEW.timer_elapsed_at THEN
+            v_event_type := 'TIMER_EXTENDED';
+        ELSIF OLD.text IS DISTINCT FROM NEW.text THEN
+            v_event_type := 'ITEM_UPDATED';
+        ELSE
+            RETURN NULL; -- no interesting change (e.g. only updated_at)
+        END IF;
+
+        IF v_event_type = 'ITEM_UPDATED' THEN
+            v_payload := jsonb_build_object('item_id', NEW.id, 'text', NEW.text);
+        ELSE
+            v_payload := jsonb_build_object(
+                'item_id', NEW.id,
+                'duration_seconds', NEW.timer_duration_seconds,
+                'started_at', NEW.timer_started_at,
+                -- The timer_ends_at virtual generated column reads as NULL
+                -- from trigger NEW, so compute the deadline here instead.
+                'ends_at', NEW.timer_started_at + (NEW.timer_duration_seconds * INTERVAL '1 second'),
+                'paused_at', NEW.timer_paused_at
+            );
+        END IF;
+    END IF;
+
+    INSERT INTO events (retro_id, event_type, item_id, payload)
+    VALUES (NEW.retro_id, v_event_type, NEW.id, v_payload);
+
+    PERFORM pg_notify('rostfacto_events', NEW.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,83 @@
+-- Retro-wide timers: a countdown for the whole session and one for the
+-- current agenda phase (e.g. 10 min writing, 5 min voting).  They work like
+-- the highlight timers of the cards (022): the deadline is derived from the
+-- stored state, the timer sweep marks them elapsed, and a trigger turns every
+-- change into an event.  A retro has at most one timer of each kind; stopping
+-- a timer deletes its row.
+
+CREATE TYPE retro_timer_kind AS ENUM ('SESSION', 'PHASE');
+
+ALTER TYPE event_type ADD VALUE 'RETRO_TIMER_STARTED';
+ALTER TYPE event_type ADD VALUE 'RETRO_TIMER_EXTENDED';
+ALTER TYPE event_type ADD VALUE 'RETRO_TIMER_ELAPSED';
+ALTER TYPE event_type ADD VALUE 'RETRO_TIMER_STOPPED';
+
+CREATE TABLE retro_timers (
+    retro_id         INTEGER NOT NULL REFERENCES retrospectives(id) ON DELETE CASCADE,
+    kind             retro_timer_kind NOT NULL,
+    -- Name of the phase (e.g. "Writing"); NULL for the session timer.
+    label            TEXT CHECK (char_length(label) BETWEEN 1 AND 50),
+    started_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    duration_seconds INTEGER NOT NULL CHECK (duration_seconds > 0),
+    -- Same epoch arithmetic as items.timer_ends_at (see 022).
+    ends_at          TIMESTAMPTZ GENERATED ALWAYS AS
+        (to_timestamp(EXTRACT(EPOCH FROM (started_at - '1970-01-01 00:00:00+00'::timestamptz)) + duration_seconds)) VIRTUAL,
+    elapsed_at       TIMESTAMPTZ,
+    PRIMARY KEY (retro_id, kind)
+);
+
+-- RETRO_TIMER_STARTED for a new timer (or a restart, which replaces the start
+-- time), RETRO_TIMER_ELAPSED when the sweep marks it elapsed,
+-- RETRO_TIMER_EXTENDED for any other change and RETRO_TIMER_STOPPED when it is
+-- deleted.  Timers deleted with their retro are skipped.
+CREATE FUNCTION emit_retro_timer_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_event_type event_type;
+    v_row        retro_timers;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        IF NOT EXISTS (SELECT 1 FROM retrospectives WHERE id = OLD.retro_id) THEN
+            RETURN NULL; -- parent retro is being deleted
+        END IF;
+        v_event_type := 'RETRO_TIMER_STOPPED';
+        v_row := OLD;
+    ELSIF TG_OP = 'INSERT' OR OLD.started_at IS DISTINCT FROM NEW.started_at THEN
+        v_event_type := 'RETRO_TIMER_STARTED';
+        v_row := NEW;
+    ELSIF OLD.elapsed_at IS NULL AND NEW.elapsed_at IS NOT NULL THEN
+        v_event_type := 'RETRO_TIMER_ELAPSED';
+        v_row := NEW;
+    ELSIF OLD.duration_seconds IS DISTINCT FROM NEW.duration_seconds
+       OR OLD.label IS DISTINCT FROM NEW.label
+       OR OLD.elapsed_at IS DISTINCT FROM NEW.elapsed_at THEN
+        v_event_type := 'RETRO_TIMER_EXTENDED';
+        v_row := NEW;
+    ELSE
+        RETURN NULL;
+    END IF;
+
+    INSERT INTO events (retro_id, event_type, payload)
+    VALUES (
+        v_row.retro_id,
+        v_event_type,
+        jsonb_build_object(
+            'retro_id', v_row.retro_id,
+            'kind', v_row.kind,
+            'label', v_row.label,
+            'duration_seconds', v_row.duration_seconds,
+            'started_at', v_row.started_at,
+            -- The virtual ends_at column reads as NULL from the trigger row.
+            'ends_at', v_row.started_at + (v_row.duration_seconds * INTERVAL '1 second')
+        )
+    );
+
+    PERFORM pg_notify('rostfacto_events', v_row.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER retro_timers_event
+    AFTER INSERT OR UPDATE OR DELETE ON retro_timers
+    FOR EACH ROW
+    EXECUTE FUNCTION emit_retro_timer_event();
@@ -0,0 +1,30 @@
+-- Wake the timer scheduler when a deadline may have moved.
+--
+-- The scheduler (src/timers.rs) sleeps until the next deadline of a running
+-- timer instead of polling.  Starting, extending and resuming a timer (also
+-- the highlight an undo restores, which sets the timer columns) NOTIFY it to
+-- recompute the next deadline; the payload is unused.  Pausing and stopping a
+-- timer only move deadlines away, so at worst the scheduler wakes once for
+-- nothing.
+
+CREATE FUNCTION notify_timer_scheduler()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('rostfacto_timers', '');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER items_timer_scheduler
+    AFTER UPDATE OF timer_started_at, timer_duration_seconds, timer_paused_at ON items
+    FOR EACH ROW
+    WHEN (NEW.timer_started_at IS NOT NULL
+          AND NEW.timer_paused_at IS NULL
+          AND NEW.timer_elapsed_at IS NULL)
+    EXECUTE FUNCTION notify_timer_scheduler();
+
+CREATE TRIGGER retro_timers_scheduler
+    AFTER INSERT OR UPDATE OF started_at, duration_seconds ON retro_timers
+    FOR EACH ROW
+    WHEN (NEW.elapsed_at IS NULL)
+    EXECUTE FUNCTION notify_timer_scheduler();
@@ -0,0 +1,129 @@
+-- "1 minute left" warning for highlight timers.
+--
+-- The timer scheduler marks a running timer as warned (timer_warned_at) once
+-- its deadline is a minute away, which the item trigger reports as
+-- TIMER_WARNING, so every client alerts at the same moment.  Timers of a
+-- minute or less are not warned about.  When the deadline moves more than a
+-- minute away again (a restart or an extension) or the timer ends, the mark
+-- is cleared, so the next minute is announced again; a timer resumed with
+-- less than a minute left keeps the warning it already had.
+
+ALTER TYPE event_type ADD VALUE 'TIMER_WARNING';
+
+ALTER TABLE items
+    ADD COLUMN timer_warned_at TIMESTAMPTZ;
+
+CREATE FUNCTION reset_timer_warning()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.timer_started_at IS NULL
+       OR NEW.timer_started_at + (NEW.timer_duration_seconds * INTERVAL '1 second')
+          > NOW() + INTERVAL '60 seconds' THEN
+        NEW.timer_warned_at := NULL;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER items_reset_timer_warning
+    BEFORE UPDATE OF timer_started_at, timer_duration_seconds ON items
+    FOR EACH ROW
+    EXECUTE FUNCTION reset_timer_warning();
+
+-- TIMER_WARNING ranks right after TIMER_ELAPSED in the precedence described
+-- in 022_item_timers.sql.  Both carry the deadline, so clients can tell a
+-- live alert from a replayed one.
+CREATE OR REPLACE FUNCTION emit_item_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_event_type event_type;
+    v_payload    JSONB;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        v_event_type := 'ITEM_CREATED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'retro_id', NEW.retro_id,
+            'category', NEW.category,
+            'text', NEW.text,
+            'status', NEW.status,
+            'likes_count', 0,
+            'position', NEW.position,
+            -- The client re-fetches /items/{id} for full card re-renders, so
+            -- only the author name is included here; author initials are
+            -- derived per-retro by the app (disambiguation), not in SQL.
+            'author_name', (SELECT display_name FROM users WHERE id = NEW.created_by)
+        );
+    ELSIF OLD.status IS DISTINCT FROM NEW.status THEN
+        v_event_type := 'ITEM_STATUS_CHANGED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'old_status', OLD.status,
+            'new_status', NEW.status
+        );
+    ELSIF OLD.category IS DISTINCT FROM NEW.category THEN
+        v_event_type := 'ITEM_MOVED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'old_category', OLD.category,
+            'new_category', NEW.category,
+            'position', NEW.position
+        );
+    ELSIF OLD.position IS DISTINCT FROM NEW.position THEN
+        v_event_type := 'ITEM_REORDERED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'category', NEW.category,
+            'position', NEW.position
+        );
+    ELSIF OLD.timer_elapsed_at IS NULL AND NEW.timer_elapsed_at IS NOT NULL THEN
+        v_event_type := 'TIMER_ELAPSED';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'ends_at', NEW.timer_started_at + (NEW.timer_duration_seconds * INTERVAL '1 second')
+        );
+    ELSIF OLD.timer_warned_at IS NULL AND NEW.timer_warned_at IS NOT NULL THEN
+        v_event_type := 'TIMER_WARNING';
+        v_payload := jsonb_build_object(
+            'item_id', NEW.id,
+            'ends_at', NEW.timer_started_at + (NEW.timer_duration_seconds * INTERVAL '1 second')
+        );
+    ELSE
+        IF OLD.timer_started_at IS NULL AND NEW.timer_started_at IS NOT NULL THEN
+            v_event_type := 'TIMER_STARTED';
+        ELSIF OLD.timer_paused_at IS NULL AND NEW.timer_paused_at IS NOT NULL THEN
+            v_event_type := 'TIMER_PAUSED';
+        ELSIF OLD.timer_paused_at IS NOT NULL AND NEW.timer_paused_at IS NULL THEN
+            v_event_type := 'TIMER_RESUMED';
+        ELSIF OLD.timer_started_at IS DISTINCT FROM NEW.timer_started_at
+           OR OLD.timer_duration_seconds IS DISTINCT FROM NEW.timer_duration_seconds
+           OR OLD.timer_elapsed_at IS DISTINCT FROM NEW.timer_elapsed_at THEN
+            v_event_type := 'TIMER_EXTENDED';
+        ELSIF OLD.text IS DISTINCT FROM NEW.text THEN
+            v_event_type := 'ITEM_UPDATED';
+        ELSE
+            RETURN NULL; -- no interesting change (e.g. only updated_at)
+        END IF;
+
+        IF v_event_type = 'ITEM_UPDATED' THEN
+            v_payload := jsonb_build_object('item_id', NEW.id, 'text', NEW.text);
+        ELSE
+            v_payload := jsonb_build_object(
+                'item_id', NEW.id,
+                'duration_seconds', NEW.timer_duration_seconds,
+                'started_at', NEW.timer_started_at,
+                -- The timer_ends_at virtual generated column reads as NULL
+                -- from trigger NEW, so compute the deadline here instead.
+                'ends_at', NEW.timer_started_at + (NEW.timer_duration_seconds * INTERVAL '1 second'),
+                'paused_at', NEW.timer_paused_at
+            );
+        END IF;
+    END IF;
+
+    INSERT INTO events (retro_id, event_type, item_id, payload)
+    VALUES (NEW.retro_id, v_event_type, NEW.id, v_payload);
+
+    PERFORM pg_notify('rostfacto_events', NEW.retro_id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
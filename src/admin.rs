@@ -0,0 +1,256 @@
+use crate::audit::{self, AuditAction, RequestMeta};
+use crate::auth::{read_cookie, AuthUser, SESSION_COOKIE};
+use crate::handlers::{
+    database_error_response, forbidden, log_database_error, not_found_page, require_fresh_login,
+    HandlerError,
+};
+use crate::templates::AdminConsoleTemplate;
+use crate::AppState;
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    http::{request::Parts, StatusCode},
+    response::{Html, IntoResponse, Response},
+};
+
+/// A user as listed in the admin console, with their active sessions.
+pub struct AdminUser {
+    pub id: i32,
+    pub username: String,
+    pub display_name: String,
+    pub retros_created: i64,
+    pub sessions: Vec<AdminSession>,
+}
+
+impl AdminUser {
+    /// Most recent activity across the user's active sessions.
+    pub fn last_seen_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.sessions.iter().map(|s| s.last_seen_at).max()
+    }
+}
+
+/// An unexpired session. Identified by its `public_id`; the session token
+/// itself never leaves the cookie.
+pub struct AdminSession {
+    pub public_id: String,
+    pub user_id: i32,
+    pub is_admin: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// True for the session the current request was made with.
+    pub is_current: bool,
+}
+
+/// Retro ownership statistics for one team.
+pub struct TeamRetroStats {
+    pub team_slug: String,
+    pub retros: i64,
+    pub archives: i64,
+    /// Usernames of the users who created the team's retros.
+    pub owners: Vec<String>,
+    pub latest_retro_at: chrono::DateTime<chrono::Utc>,
+}
+
+fn require_admin(state: &AppState, user: &AuthUser) -> Result<(), HandlerError> {
+    if user.is_admin {
+        Ok(())
+    } else {
+        Err(forbidden(state, "Only admins can use the admin console").into())
+    }
+}
+
+pub async fn admin_console(
+    State(state): State<AppState>,
+    user: AuthUser,
+    parts: Parts,
+) -> Result<Html<String>, HandlerError> {
+    require_admin(&state, &user)?;
+    let current_session = read_cookie(&parts, SESSION_COOKIE).unwrap_or_default();
+
+    let mut users: Vec<AdminUser> = sqlx::query!(
+        r#"SELECT u.id, u.username, u.display_name as "display_name!",
+                  (SELECT COUNT(*) FROM retrospectives r WHERE r.created_by = u.id) as "retros_created!"
+           FROM users u
+           ORDER BY lower(u.username)"#
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|error| {
+        log_database_error("admin_list_users", &error);
+        database_error_response()
+    })?
+    .into_iter()
+    .map(|row| AdminUser {
+        id: row.id,
+        username: row.username,
+        display_name: row.display_name,
+        retros_created: row.retros_created,
+        sessions: Vec::new(),
+    })
+    .collect();
+
+    let sessions = sqlx::query_as!(
+        AdminSession,
+        r#"SELECT public_id, user_id, is_admin, created_at, last_seen_at, expires_at,
+                  id = $1 as "is_current!"
+           FROM sessions
+           WHERE expires_at > NOW()
+           ORDER BY last_seen_at DESC"#,
+        current_session
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|error| {
+        log_database_error("admin_list_sessions", &error);
+        database_error_response()
+    })?;
+    for session in sessions {
+        if let Some(owner) = users.iter_mut().find(|u| u.id == session.user_id) {
+            owner.sessions.push(session);
+        }
+    }
+
+    let team_stats = sqlx::query_as!(
+        TeamRetroStats,
+        r#"SELECT r.team_slug as "team_slug!", COUNT(*) as "retros!",
+                  COALESCE(SUM((SELECT COUNT(*) FROM archives a WHERE a.retro_id = r.id)), 0)::bigint as "archives!",
+                  array_agg(DISTINCT u.username ORDER BY u.username) as "owners!",
+                  MAX(r.created_at) as "latest_retro_at!"
+           FROM retrospectives r
+           JOIN users u ON u.id = r.created_by
+           GROUP BY r.team_slug
+           ORDER BY r.team_slug"#
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|error| {
+        log_database_error("admin_team_stats", &error);
+        database_error_response()
+    })?;
+
+    let template = AdminConsoleTemplate {
+        active_sessions: users.iter().map(|u| u.sessions.len()).sum(),
+        users,
+        team_stats,
+        hub: state.events.metrics(),
+        user: Some(user),
+        demo_mode: state.config.demo_mode(),
+    };
+    Ok(Html(template.render().unwrap()))
+}
+
+fn back_to_console() -> Response {
+    (StatusCode::SEE_OTHER, [("Location", "/admin")]).into_response()
+}
+
+/// Forced logout of a single session.
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    user: AuthUser,
+    meta: RequestMeta,
+    Path(public_id): Path<String>,
+    parts: Parts,
+) -> Result<Response, HandlerError> {
+    require_admin(&state, &user)?;
+    // A forced logout is destructive, like deleting a retro.
+    require_fresh_login(&state, &user, &parts, "revoke a session").await?;
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("revoke_session_begin_transaction", &error);
+        database_error_response()
+    })?;
+    let owner = sqlx::query_scalar!(
+        r#"WITH revoked AS (
+               DELETE FROM sessions WHERE public_id = $1 RETURNING user_id
+           )
+           SELECT u.username FROM users u JOIN revoked ON revoked.user_id = u.id"#,
+        public_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("revoke_session", &error);
+        database_error_response()
+    })?
+    .ok_or_else(|| not_found_page(&state))?;
+    audit::record(
+        &mut *tx,
+        user.user_id,
+        AuditAction::SessionRevoked,
+        &owner,
+        serde_json::json!({ "session": public_id }),
+        &meta,
+    )
+    .await
+    .map_err(|error| {
+        log_database_error("revoke_session_audit", &error);
+        database_error_response()
+    })?;
+    tx.commit().await.map_err(|error| {
+        log_database_error("revoke_session_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::info!(user_id = user.user_id, "session revoked by admin");
+    Ok(back_to_console())
+}
+
+/// Forced logout of a user: deletes all of their sessions.
+pub async fn revoke_user_sessions(
+    State(state): State<AppState>,
+    user: AuthUser,
+    meta: RequestMeta,
+    Path(user_id): Path<i32>,
+    parts: Parts,
+) -> Result<Response, HandlerError> {
+    require_admin(&state, &user)?;
+    // A forced logout is destructive, like deleting a retro.
+    require_fresh_login(&state, &user, &parts, "sign out a user").await?;
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("revoke_user_sessions_begin_transaction", &error);
+        database_error_response()
+    })?;
+    let username = sqlx::query_scalar!("SELECT username FROM users WHERE id = $1", user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("revoke_user_sessions_load_user", &error);
+            database_error_response()
+        })?
+        .ok_or_else(|| not_found_page(&state))?;
+    let revoked = sqlx::query!("DELETE FROM sessions WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("revoke_user_sessions", &error);
+            database_error_response()
+        })?
+        .rows_affected();
+    audit::record(
+        &mut *tx,
+        user.user_id,
+        AuditAction::UserSessionsRevoked,
+        &username,
+        serde_json::json!({ "sessions": revoked }),
+        &meta,
+    )
+    .await
+    .map_err(|error| {
+        log_database_error("revoke_user_sessions_audit", &error);
+        database_error_response()
+    })?;
+    tx.commit().await.map_err(|error| {
+        log_database_error("revoke_user_sessions_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::info!(
+        user_id = user.user_id,
+        target_user_id = user_id,
+        sessions = revoked,
+        "user sessions revoked by admin"
+    );
+    Ok(back_to_console())
+}
@@ -0,0 +1,434 @@
+use crate::auth::AuthUser;
+use crate::handlers::{
+    bad_request, database_error_response, forbidden, log_database_error, HandlerError,
+};
+use crate::templates::AuditLogTemplate;
+use crate::AppState;
+use askama::Template;
+use axum::{
+    extract::{FromRequestParts, Query, RawQuery, State},
+    http::{header, request::Parts, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+};
+use chrono::{NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, FromRow, PgExecutor};
+use std::convert::Infallible;
+use std::fmt::Display;
+
+/// The audit page shows the most recent entries only; the CSV export is
+/// unbounded so compliance reviews get the complete filtered log.
+const AUDIT_PAGE_LIMIT: i64 = 500;
+
+/// Upper bound for the stored user agent, so a client cannot bloat the log
+/// with an arbitrarily long header.
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+/// Actions recorded in the `audit_log` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "audit_action", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditAction {
+    RetroCreated,
+    RetroDeleted,
+    RetroArchived,
+    RetroEditPolicyChanged,
+    RetroTimerSettingsChanged,
+    UserSignedIn,
+    UserSignedOut,
+    SessionRevoked,
+    UserSessionsRevoked,
+    ProfileUpdated,
+}
+
+impl AuditAction {
+    /// All actions, in the order the filter on the audit page lists them.
+    pub const ALL: [AuditAction; 10] = [
+        AuditAction::RetroCreated,
+        AuditAction::RetroDeleted,
+        AuditAction::RetroArchived,
+        AuditAction::RetroEditPolicyChanged,
+        AuditAction::RetroTimerSettingsChanged,
+        AuditAction::UserSignedIn,
+        AuditAction::UserSignedOut,
+        AuditAction::SessionRevoked,
+        AuditAction::UserSessionsRevoked,
+        AuditAction::ProfileUpdated,
+    ];
+}
+
+impl Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AuditAction::RetroCreated => "RETRO_CREATED",
+            AuditAction::RetroDeleted => "RETRO_DELETED",
+            AuditAction::RetroArchived => "RETRO_ARCHIVED",
+            AuditAction::RetroEditPolicyChanged => "RETRO_EDIT_POLICY_CHANGED",
+            AuditAction::RetroTimerSettingsChanged => "RETRO_TIMER_SETTINGS_CHANGED",
+            AuditAction::UserSignedIn => "USER_SIGNED_IN",
+            AuditAction::UserSignedOut => "USER_SIGNED_OUT",
+            AuditAction::SessionRevoked => "SESSION_REVOKED",
+            AuditAction::UserSessionsRevoked => "USER_SESSIONS_REVOKED",
+            AuditAction::ProfileUpdated => "PROFILE_UPDATED",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Where a request came from, as far as the app can tell. Behind a reverse
+/// proxy the client address is taken from `X-Forwarded-For` (first hop) or
+/// `X-Real-IP`; the values are recorded as reported and not verified.
+#[derive(Debug, Clone, Default)]
+pub struct RequestMeta {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl RequestMeta {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let header_value = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let ip_address = header_value("x-forwarded-for")
+            .and_then(|value| value.split(',').next())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .or_else(|| header_value("x-real-ip"))
+            .map(str::to_string);
+        let user_agent = header_value(header::USER_AGENT.as_str())
+            .map(|value| value.chars().take(MAX_USER_AGENT_LENGTH).collect());
+        RequestMeta {
+            ip_address,
+            user_agent,
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for RequestMeta
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(RequestMeta::from_headers(&parts.headers))
+    }
+}
+
+/// Append one entry to the audit log. Callers that mutate data pass their
+/// transaction, so the action and its audit entry commit (or fail) together.
+pub(crate) async fn record<'e>(
+    executor: impl PgExecutor<'e>,
+    actor_id: i32,
+    action: AuditAction,
+    target: &str,
+    details: Value,
+    meta: &RequestMeta,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO audit_log (actor_id, action, target, details, ip_address, user_agent)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+        actor_id,
+        action as AuditAction,
+        target,
+        details,
+        meta.ip_address,
+        meta.user_agent
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// One audit log row as shown on the admin page and in the CSV export.
+#[derive(Debug, FromRow)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub actor: Option<String>,
+    pub action: AuditAction,
+    pub target: Option<String>,
+    pub details: Json<Value>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// Filters of the audit page. HTML forms submit empty fields as empty
+/// strings, which mean "no filter".
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditFilter {
+    pub action: Option<String>,
+    pub actor: Option<String>,
+    pub target: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// `AuditFilter` with its fields validated and converted for the query.
+struct ParsedAuditFilter {
+    action: Option<AuditAction>,
+    actor: Option<String>,
+    target: Option<String>,
+    from: Option<chrono::DateTime<Utc>>,
+    until: Option<chrono::DateTime<Utc>>,
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+impl AuditFilter {
+    fn parse(&self) -> Result<ParsedAuditFilter, &'static str> {
+        let action = match non_empty(&self.action) {
+            Some(name) => Some(
+                AuditAction::ALL
+                    .into_iter()
+                    .find(|action| action.to_string() == name)
+                    .ok_or("Unknown audit action")?,
+            ),
+            None => None,
+        };
+        let parse_date = |value: &Option<String>| -> Result<Option<NaiveDate>, &'static str> {
+            non_empty(value)
+                .map(|value| {
+                    NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                        .map_err(|_| "Dates must be formatted as YYYY-MM-DD")
+                })
+                .transpose()
+        };
+        let start_of_day =
+            |date: NaiveDate| Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap());
+        // The `to` date is inclusive: everything before the following midnight.
+        let from = parse_date(&self.from)?.map(start_of_day);
+        let until = parse_date(&self.to)?
+            .and_then(|date| date.succ_opt())
+            .map(start_of_day);
+        Ok(ParsedAuditFilter {
+            action,
+            actor: non_empty(&self.actor),
+            target: non_empty(&self.target),
+            from,
+            until,
+        })
+    }
+}
+
+async fn load_audit_entries(
+    state: &AppState,
+    filter: &ParsedAuditFilter,
+    limit: Option<i64>,
+) -> Result<Vec<AuditEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        AuditEntry,
+        r#"SELECT a.id as "id!", a.created_at as "created_at!", u.username as "actor?",
+                  a.action as "action: AuditAction", a.target as "target?",
+                  a.details as "details: Json<Value>",
+                  a.ip_address as "ip_address?", a.user_agent as "user_agent?"
+           FROM audit_log a
+           LEFT JOIN users u ON u.id = a.actor_id
+           WHERE ($1::audit_action IS NULL OR a.action = $1)
+             AND ($2::text IS NULL OR u.username = $2)
+             AND ($3::text IS NULL OR strpos(lower(a.target), lower($3)) > 0)
+             AND ($4::timestamptz IS NULL OR a.created_at >= $4)
+             AND ($5::timestamptz IS NULL OR a.created_at < $5)
+           ORDER BY a.id DESC
+           LIMIT $6"#,
+        filter.action as Option<AuditAction>,
+        filter.actor,
+        filter.target,
+        filter.from,
+        filter.until,
+        limit
+    )
+    .fetch_all(&state.pool)
+    .await
+}
+
+pub async fn audit_log(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(filter): Query<AuditFilter>,
+    RawQuery(query): RawQuery,
+) -> Result<Html<String>, HandlerError> {
+    if !user.is_admin {
+        return Err(forbidden(&state, "Only admins can view the audit log").into());
+    }
+    let parsed = filter
+        .parse()
+        .map_err(|message| bad_request(&state, message))?;
+    let entries = load_audit_entries(&state, &parsed, Some(AUDIT_PAGE_LIMIT))
+        .await
+        .map_err(|error| {
+            log_database_error("load_audit_log", &error);
+            database_error_response()
+        })?;
+
+    let csv_url = match query.filter(|query| !query.is_empty()) {
+        Some(query) => format!("/admin/audit.csv?{query}"),
+        None => "/admin/audit.csv".to_string(),
+    };
+    let template = AuditLogTemplate {
+        truncated: entries.len() as i64 == AUDIT_PAGE_LIMIT,
+        entries,
+        actions: AuditAction::ALL.to_vec(),
+        filter_action: non_empty(&filter.action).unwrap_or_default(),
+        filter_actor: non_empty(&filter.actor).unwrap_or_default(),
+        filter_target: non_empty(&filter.target).unwrap_or_default(),
+        filter_from: non_empty(&filter.from).unwrap_or_default(),
+        filter_to: non_empty(&filter.to).unwrap_or_default(),
+        csv_url,
+        user: Some(user),
+        demo_mode: state.config.demo_mode(),
+    };
+    Ok(Html(template.render().unwrap()))
+}
+
+pub async fn audit_log_csv(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(filter): Query<AuditFilter>,
+) -> Result<Response, HandlerError> {
+    if !user.is_admin {
+        return Err(forbidden(&state, "Only admins can export the audit log").into());
+    }
+    let parsed = filter
+        .parse()
+        .map_err(|message| bad_request(&state, message))?;
+    let entries = load_audit_entries(&state, &parsed, None)
+        .await
+        .map_err(|error| {
+            log_database_error("export_audit_log", &error);
+            database_error_response()
+        })?;
+
+    tracing::info!(
+        user_id = user.user_id,
+        entries = entries.len(),
+        "audit log exported"
+    );
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"audit-log.csv\"",
+            ),
+        ],
+        audit_csv(&entries),
+    )
+        .into_response())
+}
+
+fn audit_csv(entries: &[AuditEntry]) -> String {
+    let mut csv = String::from("timestamp,actor,action,target,ip_address,user_agent,details\r\n");
+    for entry in entries {
+        let fields = [
+            entry.created_at.to_rfc3339(),
+            entry.actor.clone().unwrap_or_default(),
+            entry.action.to_string(),
+            entry.target.clone().unwrap_or_default(),
+            entry.ip_address.clone().unwrap_or_default(),
+            entry.user_agent.clone().unwrap_or_default(),
+            entry.details.0.to_string(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// Quote a CSV field (RFC 4180) when needed. Values that a spreadsheet would
+/// interpret as a formula are prefixed with `'`, so an attacker-controlled
+/// user agent or retro title cannot inject formulas into the export.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn csv_field_neutralizes_formulas() {
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("+1"), "'+1");
+        assert_eq!(csv_field("@sum"), "'@sum");
+        assert_eq!(csv_field("-retro"), "'-retro");
+    }
+
+    #[test]
+    fn request_meta_prefers_the_first_forwarded_address() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.7, 10.0.0.1".parse().unwrap());
+        headers.insert("x-real-ip", "10.0.0.1".parse().unwrap());
+        headers.insert(header::USER_AGENT, "Firefox".parse().unwrap());
+        let meta = RequestMeta::from_headers(&headers);
+        assert_eq!(meta.ip_address.as_deref(), Some("203.0.113.7"));
+        assert_eq!(meta.user_agent.as_deref(), Some("Firefox"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", "198.51.100.2".parse().unwrap());
+        let meta = RequestMeta::from_headers(&headers);
+        assert_eq!(meta.ip_address.as_deref(), Some("198.51.100.2"));
+        assert_eq!(meta.user_agent, None);
+    }
+
+    #[test]
+    fn audit_filter_treats_empty_fields_as_absent_and_includes_the_to_date() {
+        let filter = AuditFilter {
+            action: Some("RETRO_DELETED".to_string()),
+            actor: Some(String::new()),
+            target: Some("  ".to_string()),
+            from: Some("2026-01-01".to_string()),
+            to: Some("2026-01-31".to_string()),
+        };
+        let parsed = filter.parse().expect("filter should parse");
+        assert_eq!(parsed.action, Some(AuditAction::RetroDeleted));
+        assert_eq!(parsed.actor, None);
+        assert_eq!(parsed.target, None);
+        assert_eq!(
+            parsed.from.unwrap().to_rfc3339(),
+            "2026-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parsed.until.unwrap().to_rfc3339(),
+            "2026-02-01T00:00:00+00:00"
+        );
+
+        let invalid = AuditFilter {
+            action: Some("DROP_TABLES".to_string()),
+            ..AuditFilter::default()
+        };
+        assert!(invalid.parse().is_err());
+    }
+}
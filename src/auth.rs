@@ -1,3 +1,4 @@
+use crate::audit::{self, AuditAction, RequestMeta};
 use crate::github::{get_user, is_team_member, list_org_teams, GitHubUser};
 use axum::{
     extract::{FromRef, FromRequestParts, Query, State},
@@ -18,6 +19,9 @@ const SESSION_MAX_AGE_SECONDS: i64 = 60 * 60 * 24 * 7; // 7 days idle
 /// creates a fresh session and revokes all previous ones.
 const SESSION_ABSOLUTE_MAX_AGE_SECONDS: i64 = 60 * 60 * 24 * 30; // 30 days
 const OAUTH_STATE_MAX_AGE_SECONDS: i64 = 60 * 10; // 10 minutes
+/// `sessions.last_seen_at` is refreshed at most this often, so tracking
+/// activity costs one UPDATE per minute and session, not one per request.
+const SESSION_ACTIVITY_RESOLUTION_SECONDS: i64 = 60;
 /// Destructive admin actions (e.g. deleting a retro) require a login that is
 /// at most this old, so a long-lived stolen session cannot delete data.
 pub(crate) const ADMIN_REAUTH_MAX_AGE_SECONDS: i64 = 60 * 60 * 24; // 24 hours
@@ -81,6 +85,7 @@ pub(crate) struct SessionRow {
     team_listing_errors: sqlx::types::Json<Vec<String>>,
     created_at: chrono::DateTime<chrono::Utc>,
     expires_at: chrono::DateTime<chrono::Utc>,
+    last_seen_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Deserialize)]
@@ -145,7 +150,8 @@ pub(crate) async fn load_session(
             s.teams as "teams: _",
             s.team_listing_errors as "team_listing_errors: _",
             s.created_at,
-            s.expires_at
+            s.expires_at,
+            s.last_seen_at
         FROM users u
         JOIN sessions s ON s.user_id = u.id
         WHERE s.id = $1 AND s.expires_at > NOW()
@@ -157,23 +163,33 @@ pub(crate) async fn load_session(
 
     // Sliding expiry: when less than half of the idle window remains, extend
     // the deadline to now + idle, capped at the absolute maximum so abandoned
-    // sessions eventually die. The rare UPDATE keeps per-request overhead low.
+    // sessions eventually die. The same UPDATE records the last activity; both
+    // are rare, which keeps per-request overhead low.
     let mut session = session;
     if let Some(session) = &mut session {
         let idle = chrono::Duration::try_seconds(SESSION_MAX_AGE_SECONDS).unwrap();
         let absolute = chrono::Duration::try_seconds(SESSION_ABSOLUTE_MAX_AGE_SECONDS).unwrap();
+        let resolution =
+            chrono::Duration::try_seconds(SESSION_ACTIVITY_RESOLUTION_SECONDS).unwrap();
         let now = Utc::now();
-        if session.expires_at - now < idle / 2 {
-            let new_expiry = (session.created_at + absolute).min(now + idle);
+        let extend = session.expires_at - now < idle / 2;
+        if extend || now - session.last_seen_at >= resolution {
+            let new_expiry = if extend {
+                (session.created_at + absolute).min(now + idle)
+            } else {
+                session.expires_at
+            };
             sqlx::query!(
-                "UPDATE sessions SET expires_at = $2 WHERE id = $1",
+                "UPDATE sessions SET expires_at = $2, last_seen_at = $3 WHERE id = $1",
                 session_id,
-                new_expiry
+                new_expiry,
+                now
             )
             .execute(pool)
             .await?;
             // Keep the returned row in sync with the database.
             session.expires_at = new_expiry;
+            session.last_seen_at = now;
         }
     }
 
@@ -207,6 +223,7 @@ pub(crate) async fn create_session(
     is_admin: bool,
     teams: &[CachedTeam],
     team_listing_errors: &[String],
+    meta: &RequestMeta,
 ) -> Result<String, sqlx::Error> {
     // A fresh session starts at the full idle window; the absolute cap only
     // matters once the session is extended by `load_session`.
@@ -217,12 +234,14 @@ pub(crate) async fn create_session(
     let teams_json = serde_json::to_value(teams).unwrap();
     let errors_json = serde_json::to_value(team_listing_errors).unwrap();
     let session_id = sqlx::query_scalar!(
-        "INSERT INTO sessions (user_id, expires_at, is_admin, teams, team_listing_errors) VALUES ($1, $2, $3, $4, $5) RETURNING id",
+        "INSERT INTO sessions (user_id, expires_at, is_admin, teams, team_listing_errors, user_agent, ip_address) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id",
         user_id,
         expires_at,
         is_admin,
         teams_json,
-        errors_json
+        errors_json,
+        meta.user_agent,
+        meta.ip_address
     )
     .fetch_one(pool)
     .await?;
@@ -257,7 +276,7 @@ fn session_cookie(session_id: &str, secure: bool) -> String {
     set_cookie(SESSION_COOKIE, session_id, SESSION_MAX_AGE_SECONDS, secure)
 }
 
-fn clear_session_cookie(secure: bool) -> String {
+pub(crate) fn clear_session_cookie(secure: bool) -> String {
     clear_cookie(SESSION_COOKIE, secure)
 }
 
@@ -462,6 +481,7 @@ pub struct CallbackQuery {
 pub async fn callback(
     State(state): State<crate::AppState>,
     Query(params): Query<CallbackQuery>,
+    meta: RequestMeta,
     parts: Parts,
 ) -> impl IntoResponse {
     if state.config.demo_mode() {
@@ -634,22 +654,44 @@ pub async fn callback(
         "user authenticated"
     );
 
-    let session_id =
-        match create_session(&state.pool, user.id, is_admin, &teams, &team_listing_errors).await {
-            Ok(id) => id,
-            Err(error) => {
-                tracing::error!(operation = "create_session", "database operation failed");
-                tracing::debug!(error = %error, "session creation failure details");
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Failed to create session",
-                )
-                    .into_response();
-            }
-        };
+    let session_id = match create_session(
+        &state.pool,
+        user.id,
+        is_admin,
+        &teams,
+        &team_listing_errors,
+        &meta,
+    )
+    .await
+    {
+        Ok(id) => id,
+        Err(error) => {
+            tracing::error!(operation = "create_session", "database operation failed");
+            tracing::debug!(error = %error, "session creation failure details");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to create session",
+            )
+                .into_response();
+        }
+    };
 
     tracing::debug!("session created for user '{}'", github_user.login);
 
+    if let Err(error) = audit::record(
+        &state.pool,
+        user.id,
+        AuditAction::UserSignedIn,
+        &user.username,
+        serde_json::json!({ "is_admin": is_admin }),
+        &meta,
+    )
+    .await
+    {
+        tracing::error!(operation = "audit_sign_in", "database operation failed");
+        tracing::debug!(error = %error, "sign-in audit failure details");
+    }
+
     let mut response = (StatusCode::SEE_OTHER, [("Location", "/")]).into_response();
     let headers = response.headers_mut();
     headers.append(
@@ -676,7 +718,8 @@ pub async fn cleanup_expired_sessions(pool: &PgPool) -> Result<u64, sqlx::Error>
 
 pub async fn logout(
     State(state): State<crate::AppState>,
-    _user: AuthUser,
+    user: AuthUser,
+    meta: RequestMeta,
     parts: Parts,
 ) -> impl IntoResponse {
     if let Some(session_id) = read_cookie(&parts, SESSION_COOKIE) {
@@ -692,6 +735,66 @@ pub async fn logout(
         }
     }
 
+    signed_out(&state, &user, &meta, serde_json::json!({})).await
+}
+
+/// "Sign out everywhere": revokes all sessions of the user, including the
+/// one the request was made with. Unlike a plain logout, a failed revocation
+/// is reported, because the user relies on the other sessions being gone.
+pub async fn logout_everywhere(
+    State(state): State<crate::AppState>,
+    user: AuthUser,
+    meta: RequestMeta,
+) -> impl IntoResponse {
+    let revoked = match sqlx::query!("DELETE FROM sessions WHERE user_id = $1", user.user_id)
+        .execute(&state.pool)
+        .await
+    {
+        Ok(result) => result.rows_affected(),
+        Err(error) => {
+            tracing::error!(
+                error_type = "session_delete",
+                "failed to delete sessions during logout everywhere"
+            );
+            tracing::debug!(error = %error, "session deletion failure details");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to revoke sessions",
+            )
+                .into_response();
+        }
+    };
+
+    signed_out(
+        &state,
+        &user,
+        &meta,
+        serde_json::json!({ "everywhere": true, "sessions": revoked }),
+    )
+    .await
+}
+
+/// Audit a sign-out and send the browser home without its session cookie.
+async fn signed_out(
+    state: &crate::AppState,
+    user: &AuthUser,
+    meta: &RequestMeta,
+    details: serde_json::Value,
+) -> Response {
+    if let Err(error) = audit::record(
+        &state.pool,
+        user.user_id,
+        AuditAction::UserSignedOut,
+        &user.username,
+        details,
+        meta,
+    )
+    .await
+    {
+        tracing::error!(operation = "audit_sign_out", "database operation failed");
+        tracing::debug!(error = %error, "sign-out audit failure details");
+    }
+
     tracing::info!("user signed out");
 
     (
@@ -737,9 +840,16 @@ mod tests {
             },
         ];
 
-        let session_id = create_session(&pool, user_id, true, &teams, &["org-x".to_string()])
-            .await
-            .expect("Failed to create session");
+        let session_id = create_session(
+            &pool,
+            user_id,
+            true,
+            &teams,
+            &["org-x".to_string()],
+            &RequestMeta::default(),
+        )
+        .await
+        .expect("Failed to create session");
 
         let session = load_session(&pool, &session_id)
             .await
@@ -795,7 +905,7 @@ mod tests {
             .await
             .expect("Failed to ensure demo user");
 
-        let expired_id = create_session(&pool, user_id, false, &[], &[])
+        let expired_id = create_session(&pool, user_id, false, &[], &[], &RequestMeta::default())
             .await
             .expect("Failed to create expired session");
         sqlx::query!(
@@ -806,7 +916,7 @@ mod tests {
         .await
         .expect("Failed to expire session");
 
-        let valid_id = create_session(&pool, user_id, false, &[], &[])
+        let valid_id = create_session(&pool, user_id, false, &[], &[], &RequestMeta::default())
             .await
             .expect("Failed to create valid session");
 
@@ -856,7 +966,7 @@ mod tests {
         let user_id = ensure_demo_user(&pool)
             .await
             .expect("Failed to ensure demo user");
-        let session_id = create_session(&pool, user_id, false, &[], &[])
+        let session_id = create_session(&pool, user_id, false, &[], &[], &RequestMeta::default())
             .await
             .expect("Failed to create session");
 
@@ -912,4 +1022,74 @@ mod tests {
             .await
             .expect("Failed to delete test session");
     }
+
+    #[tokio::test]
+    async fn load_session_records_activity_at_most_once_per_resolution() {
+        let database_url =
+            std::env::var("DATABASE_URL").expect("DATABASE_URL environment variable must be set");
+        let pool = PgPool::connect(&database_url)
+            .await
+            .expect("Failed to connect to database");
+
+        let user_id = ensure_demo_user(&pool)
+            .await
+            .expect("Failed to ensure demo user");
+        let session_id = create_session(&pool, user_id, false, &[], &[], &RequestMeta::default())
+            .await
+            .expect("Failed to create session");
+
+        // Recent activity is not written again on every request.
+        let recent = Utc::now() - chrono::Duration::seconds(5);
+        sqlx::query!(
+            "UPDATE sessions SET last_seen_at = $2 WHERE id = $1",
+            session_id,
+            recent
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to set last activity");
+        let session = load_session(&pool, &session_id)
+            .await
+            .expect("Failed to load session")
+            .expect("Session should exist");
+        assert!(
+            (session.last_seen_at - recent).num_milliseconds().abs() < 1,
+            "activity within the resolution should not be rewritten"
+        );
+
+        // Stale activity is refreshed, without touching the expiry.
+        sqlx::query!(
+            "UPDATE sessions SET last_seen_at = NOW() - interval '1 hour' WHERE id = $1",
+            session_id
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to age last activity");
+        let expires_before = session.expires_at;
+        let session = load_session(&pool, &session_id)
+            .await
+            .expect("Failed to load session")
+            .expect("Session should exist");
+        assert!(
+            Utc::now() - session.last_seen_at < chrono::Duration::minutes(1),
+            "stale activity should be refreshed"
+        );
+        assert_eq!(session.expires_at, expires_before);
+        let stored = sqlx::query_scalar!(
+            "SELECT last_seen_at FROM sessions WHERE id = $1",
+            session_id
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to read last activity");
+        assert!(
+            (stored - session.last_seen_at).num_milliseconds().abs() < 1,
+            "the refreshed activity should be persisted"
+        );
+
+        sqlx::query!("DELETE FROM sessions WHERE id = $1", session_id)
+            .execute(&pool)
+            .await
+            .expect("Failed to delete test session");
+    }
 }
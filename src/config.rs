@@ -18,6 +18,26 @@ pub struct Config {
     /// explicitly with `DEMO_MODE=1`; a deployment without GitHub auth
     /// configuration fails closed instead of silently running unsecured.
     pub demo_mode: bool,
+    /// Events older than this many days are deleted; `None` keeps events
+    /// regardless of their age.
+    pub event_retention_days: Option<u16>,
+    /// Whether events older than a retro's latest archive are deleted.
+    pub event_retention_prune_archived: bool,
+}
+
+/// Events are kept this long unless `EVENT_RETENTION_DAYS` says otherwise.
+const DEFAULT_EVENT_RETENTION_DAYS: u16 = 30;
+
+/// `EVENT_RETENTION_DAYS`: unset means the default, `0` keeps events forever.
+fn parse_retention_days(value: Option<&str>) -> Option<u16> {
+    let Some(value) = value else {
+        return Some(DEFAULT_EVENT_RETENTION_DAYS);
+    };
+    match value.trim().parse::<u16>() {
+        Ok(0) => None,
+        Ok(days) => Some(days),
+        Err(_) => panic!("EVENT_RETENTION_DAYS must be a number of days (0 keeps events forever)"),
+    }
 }
 
 impl Config {
@@ -62,6 +82,11 @@ impl Config {
             })
             .unwrap_or_default();
         let github_app_owner = env::var("GITHUB_APP_OWNER").ok();
+        let event_retention_days =
+            parse_retention_days(env::var("EVENT_RETENTION_DAYS").ok().as_deref());
+        let event_retention_prune_archived = env::var("EVENT_RETENTION_PRUNE_ARCHIVED")
+            .map(|value| !(value == "0" || value.eq_ignore_ascii_case("false")))
+            .unwrap_or(true);
         let public_url = match env::var("PUBLIC_URL") {
             Ok(url) => url,
             Err(_) if demo_mode => {
@@ -103,6 +128,8 @@ impl Config {
             github_user_orgs,
             github_app_owner,
             demo_mode,
+            event_retention_days,
+            event_retention_prune_archived,
         }
     }
 
@@ -129,9 +156,46 @@ impl Config {
             .unwrap_or_else(|| "https://github.com/settings/applications".to_string())
     }
 
+    /// Origins GitHub serves user avatars (`avatar_url`) from. GitHub
+    /// Enterprise serves them from its own host or, with subdomain isolation,
+    /// from the `avatars.` subdomain.
+    pub fn avatar_origins(&self) -> Vec<String> {
+        let Some(url) = self.github_enterprise_url.as_deref() else {
+            return vec!["https://avatars.githubusercontent.com".to_string()];
+        };
+        let origin = url.trim_end_matches('/');
+        let mut origins = vec![origin.to_string()];
+        if let Some((scheme, host)) = origin.split_once("://") {
+            origins.push(format!("{scheme}://avatars.{host}"));
+        }
+        origins
+    }
+
     /// Whether session cookies should carry the `Secure` flag: true when the
     /// public URL is served over HTTPS.
     pub fn cookies_secure(&self) -> bool {
         self.public_url.starts_with("https://")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_retention_defaults_to_thirty_days() {
+        assert_eq!(parse_retention_days(None), Some(30));
+        assert_eq!(parse_retention_days(Some(" 7 ")), Some(7));
+    }
+
+    #[test]
+    fn zero_event_retention_days_keep_events_forever() {
+        assert_eq!(parse_retention_days(Some("0")), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "EVENT_RETENTION_DAYS")]
+    fn invalid_event_retention_days_prevent_startup() {
+        parse_retention_days(Some("a month"));
+    }
+}
@@ -18,6 +18,9 @@ use url::Url;
 /// legacy clients, a `Referer`) header, it must match the request's `Host` or
 /// the configured public URL.
 ///
+/// WebSocket handshakes are GET requests, but the socket accepts mutations, so
+/// they are checked, too (cross-site WebSocket hijacking).
+///
 /// Requests without an `Origin`/`Referer` header are allowed: Firefox, for
 /// one, omits both on same-origin form POSTs, while browsers always send
 /// `Origin` on cross-origin requests — so a missing header here cannot be a
@@ -28,7 +31,9 @@ pub async fn check(
     request: Request<axum::body::Body>,
     next: Next,
 ) -> Response {
-    if is_unsafe_method(request.method()) && !origin_is_acceptable(&request, &state.config) {
+    if (is_unsafe_method(request.method()) || is_websocket_handshake(&request))
+        && !origin_is_acceptable(&request, &state.config)
+    {
         tracing::warn!(
             origin = request
                 .headers()
@@ -63,6 +68,14 @@ fn is_unsafe_method(method: &Method) -> bool {
     )
 }
 
+fn is_websocket_handshake(request: &Request<axum::body::Body>) -> bool {
+    request
+        .headers()
+        .get(header::UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("websocket"))
+}
+
 fn origin_is_acceptable(
     request: &Request<axum::body::Body>,
     config: &crate::config::Config,
@@ -164,6 +177,8 @@ mod tests {
             github_user_orgs: Vec::new(),
             github_app_owner: None,
             demo_mode: false,
+            event_retention_days: None,
+            event_retention_prune_archived: false,
         }
     }
 
@@ -241,6 +256,21 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn websocket_handshakes_are_detected() {
+        let request = Request::builder()
+            .uri("/retro/demo/ws")
+            .header(header::UPGRADE, "WebSocket")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        assert!(is_websocket_handshake(&request));
+        assert!(!is_websocket_handshake(&req_with(
+            "example.com",
+            None,
+            None
+        )));
+    }
+
     #[test]
     fn unsafe_methods_are_detected() {
         assert!(is_unsafe_method(&Method::POST));
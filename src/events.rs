@@ -1,13 +1,18 @@
 use crate::auth::AuthUser;
 use crate::handlers::{
-    database_error_response, log_database_error, not_found_response, require_retro_access,
+    database_error_response, load_action_item, load_item_with_initials, log_database_error,
+    not_found_response, require_retro_access, HandlerError,
 };
+use crate::models::Category;
+use crate::presence;
+use crate::retention;
 use crate::AppState;
 use axum::{
     body::Body,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
+    Form,
 };
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
@@ -18,10 +23,24 @@ use sqlx::{
 };
 use std::collections::HashMap;
 use std::fmt::Display;
-use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 
-/// Event types written to the `events` table by DB triggers.
+/// Postgres channel the event triggers NOTIFY with the retro id.
+pub(crate) const EVENTS_CHANNEL: &str = "rostfacto_events";
+/// Postgres channel typing announcements are NOTIFYed on (as JSON), so they
+/// reach the subscribers of every app instance.
+const TYPING_CHANNEL: &str = "rostfacto_typing";
+/// A user's typing announcements for one column are forwarded at most this
+/// often; clients announce more eagerly.
+const TYPING_THROTTLE: Duration = Duration::from_secs(2);
+/// Someone counts as typing for this long after their last announcement.
+const TYPING_EXPIRY: Duration = Duration::from_secs(6);
+
+/// Event types written to the `events` table by DB triggers (and by undo,
+/// see `undo.rs`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "event_type", rename_all = "SCREAMING_SNAKE_CASE")]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -31,11 +50,31 @@ pub enum EventType {
     ItemStatusChanged,
     ItemLiked,
     ItemUnliked,
+    ItemReacted,
+    ItemUnreacted,
+    ItemDeleted,
+    ItemMoved,
+    ItemReordered,
+    ItemCommentAdded,
+    ItemCommentDeleted,
+    ItemNotesUpdated,
     TimerStarted,
     TimerExtended,
     TimerCancelled,
     TimerElapsed,
+    TimerWarning,
+    TimerPaused,
+    TimerResumed,
+    RetroTimerStarted,
+    RetroTimerExtended,
+    RetroTimerElapsed,
+    RetroTimerStopped,
     RetroArchived,
+    ActionItemCreated,
+    ActionItemUpdated,
+    ActionItemCompleted,
+    ActionItemDeleted,
+    ActionUndone,
 }
 
 impl Display for EventType {
@@ -46,11 +85,31 @@ impl Display for EventType {
             EventType::ItemStatusChanged => "ITEM_STATUS_CHANGED",
             EventType::ItemLiked => "ITEM_LIKED",
             EventType::ItemUnliked => "ITEM_UNLIKED",
+            EventType::ItemReacted => "ITEM_REACTED",
+            EventType::ItemUnreacted => "ITEM_UNREACTED",
+            EventType::ItemDeleted => "ITEM_DELETED",
+            EventType::ItemMoved => "ITEM_MOVED",
+            EventType::ItemReordered => "ITEM_REORDERED",
+            EventType::ItemCommentAdded => "ITEM_COMMENT_ADDED",
+            EventType::ItemCommentDeleted => "ITEM_COMMENT_DELETED",
+            EventType::ItemNotesUpdated => "ITEM_NOTES_UPDATED",
             EventType::TimerStarted => "TIMER_STARTED",
             EventType::TimerExtended => "TIMER_EXTENDED",
             EventType::TimerCancelled => "TIMER_CANCELLED",
             EventType::TimerElapsed => "TIMER_ELAPSED",
+            EventType::TimerWarning => "TIMER_WARNING",
+            EventType::TimerPaused => "TIMER_PAUSED",
+            EventType::TimerResumed => "TIMER_RESUMED",
+            EventType::RetroTimerStarted => "RETRO_TIMER_STARTED",
+            EventType::RetroTimerExtended => "RETRO_TIMER_EXTENDED",
+            EventType::RetroTimerElapsed => "RETRO_TIMER_ELAPSED",
+            EventType::RetroTimerStopped => "RETRO_TIMER_STOPPED",
             EventType::RetroArchived => "RETRO_ARCHIVED",
+            EventType::ActionItemCreated => "ACTION_ITEM_CREATED",
+            EventType::ActionItemUpdated => "ACTION_ITEM_UPDATED",
+            EventType::ActionItemCompleted => "ACTION_ITEM_COMPLETED",
+            EventType::ActionItemDeleted => "ACTION_ITEM_DELETED",
+            EventType::ActionUndone => "ACTION_UNDONE",
         };
         write!(f, "{}", name)
     }
@@ -63,6 +122,7 @@ pub struct Event {
     pub retro_id: i32,
     pub event_type: EventType,
     pub item_id: Option<i32>,
+    pub action_item_id: Option<i32>,
     pub payload: Value,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
@@ -73,6 +133,7 @@ struct EventRow {
     retro_id: i32,
     event_type: EventType,
     item_id: Option<i32>,
+    action_item_id: Option<i32>,
     payload: sqlx::types::Json<Value>,
     created_at: chrono::DateTime<chrono::Utc>,
 }
@@ -84,6 +145,7 @@ impl From<EventRow> for Event {
             retro_id: row.retro_id,
             event_type: row.event_type,
             item_id: row.item_id,
+            action_item_id: row.action_item_id,
             payload: row.payload.0,
             created_at: row.created_at,
         }
@@ -100,7 +162,8 @@ async fn load_events(
     let rows = sqlx::query_as!(
         EventRow,
         r#"SELECT id as "id!", retro_id as "retro_id!", event_type as "event_type: _",
-                  item_id as "item_id: _", payload as "payload: Json<Value>",
+                  item_id as "item_id: _", action_item_id as "action_item_id: _",
+                  payload as "payload: Json<Value>",
                   created_at as "created_at!"
            FROM events
            WHERE retro_id = $1 AND id > $2 AND id <= $3
@@ -114,19 +177,172 @@ async fn load_events(
     Ok(rows.into_iter().map(Event::from).collect())
 }
 
-/// In-process fan-out of events to SSE subscribers, keyed by retro.
+/// Up to `limit` events of all retros after `after_id`, oldest first.
+async fn load_new_events(
+    pool: &PgPool,
+    after_id: i64,
+    limit: i64,
+) -> Result<Vec<Event>, sqlx::Error> {
+    let rows = sqlx::query_as!(
+        EventRow,
+        r#"SELECT id as "id!", retro_id as "retro_id!", event_type as "event_type: _",
+                  item_id as "item_id: _", action_item_id as "action_item_id: _",
+                  payload as "payload: Json<Value>",
+                  created_at as "created_at!"
+           FROM events
+           WHERE id > $1
+           ORDER BY id
+           LIMIT $2"#,
+        after_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(Event::from).collect())
+}
+
+/// Ephemeral signals: streamed to connected clients like events, but never
+/// written to the `events` table, never replayed and without an event id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalType {
+    /// The full list of people currently looking at the retro.
+    Participants,
+    /// The full list of people currently drafting a card, with their column.
+    Typing,
+}
+
+impl Display for SignalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SignalType::Participants => "PARTICIPANTS",
+            SignalType::Typing => "TYPING",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single ephemeral signal for one retro.
+#[derive(Debug, Clone)]
+pub struct Signal {
+    pub retro_id: i32,
+    pub signal_type: SignalType,
+    pub payload: Value,
+}
+
+/// Everything the hub fans out to the subscribers of a retro.
+#[derive(Debug, Clone)]
+pub enum Broadcast {
+    Event(Event),
+    Signal(Signal),
+}
+
+impl Broadcast {
+    fn retro_id(&self) -> i32 {
+        match self {
+            Broadcast::Event(event) => event.retro_id,
+            Broadcast::Signal(signal) => signal.retro_id,
+        }
+    }
+}
+
+/// In-process fan-out of events and signals to SSE subscribers, keyed by
+/// retro.
 ///
 /// The notifier task is the only writer; each SSE handler subscribes for its
 /// retro and filters out events it already replayed from the DB (see
-/// `retro_events`).
+/// `retro_events`). Subscribers are spread over `HUB_SHARDS` independently
+/// locked shards, so retros do not contend for one lock, and no lock is ever
+/// held across an await point.
+///
+/// Each subscriber has a queue of `SUBSCRIBER_QUEUE_CAPACITY` messages. A
+/// client that falls that far behind is dropped instead of buffering without
+/// bound: its stream ends after the queued messages, and the browser
+/// reconnects and catches up via `Last-Event-ID` replay.
 #[derive(Clone, Default)]
 pub struct EventHub {
     inner: Arc<EventHubInner>,
 }
 
+/// How long the notifier collects event notifications before loading the
+/// new events in one query. Bulk actions and busy instances notify many
+/// times in quick succession.
+const EVENT_COALESCE_WINDOW: Duration = Duration::from_millis(20);
+/// Events loaded per query; more are loaded in further queries.
+const EVENT_BATCH_SIZE: i64 = 500;
+
+/// Number of independently locked subscriber maps.
+const HUB_SHARDS: usize = 16;
+/// Messages buffered per subscriber before it counts as too slow.
+pub const SUBSCRIBER_QUEUE_CAPACITY: usize = 256;
+
+type Shard = HashMap<i32, Vec<mpsc::Sender<Broadcast>>>;
+
 #[derive(Default)]
 struct EventHubInner {
-    subscribers: Mutex<HashMap<i32, Vec<mpsc::UnboundedSender<Event>>>>,
+    shards: [Mutex<Shard>; HUB_SHARDS],
+    counters: HubCounters,
+    /// Who is drafting a card where, per retro; fed by the announcements of
+    /// all instances and pruned by `typing_expiry_loop`.
+    typists: Mutex<HashMap<i32, Vec<Typist>>>,
+    /// When each (retro, user, column) was last announced through this
+    /// instance, for throttling.
+    typing_announced: Mutex<HashMap<(i32, i32, Category), Instant>>,
+}
+
+#[derive(Default)]
+struct HubCounters {
+    published: AtomicU64,
+    delivered: AtomicU64,
+    dropped_subscribers: AtomicU64,
+    peak_queue_depth: AtomicUsize,
+    event_queries: AtomicU64,
+}
+
+/// A snapshot of the hub's state and counters since this instance started.
+pub struct HubMetrics {
+    /// Retros with at least one subscriber.
+    pub retros: usize,
+    pub subscribers: usize,
+    /// Messages waiting in subscriber queues right now.
+    pub queued: usize,
+    /// The fullest subscriber queue right now.
+    pub max_queue_depth: usize,
+    /// The fullest any subscriber queue has been.
+    pub peak_queue_depth: usize,
+    pub queue_capacity: usize,
+    pub published: u64,
+    pub delivered: u64,
+    /// Subscribers dropped because their queue was full.
+    pub dropped_subscribers: u64,
+    /// Queries the notifier ran to load new events.
+    pub event_queries: u64,
+}
+
+/// Locks recover from poisoning: a panic elsewhere must not take down event
+/// delivery, and every critical section leaves the maps consistent.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Someone drafting a card in a column of a retro.
+#[derive(Debug, Clone, Serialize)]
+struct Typist {
+    user_id: i32,
+    name: String,
+    category: Category,
+    #[serde(skip)]
+    expires_at: Instant,
+}
+
+/// What instances tell each other via `TYPING_CHANNEL`.
+#[derive(Debug, Serialize, Deserialize)]
+struct TypingAnnouncement {
+    retro_id: i32,
+    user_id: i32,
+    name: String,
+    category: Category,
+    /// The user submitted or discarded the draft.
+    stopped: bool,
 }
 
 impl EventHub {
@@ -134,37 +350,183 @@ impl EventHub {
         Self::default()
     }
 
+    fn shard(&self, retro_id: i32) -> &Mutex<Shard> {
+        &self.inner.shards[retro_id.unsigned_abs() as usize % HUB_SHARDS]
+    }
+
     /// Subscribe to all events for one retro. The receiver yields every event
     /// published after the subscription; the caller is responsible for
-    /// ignoring anything it already replayed.
-    pub async fn subscribe(&self, retro_id: i32) -> mpsc::UnboundedReceiver<Event> {
-        let (sender, receiver) = mpsc::unbounded_channel();
-        self.inner
-            .subscribers
-            .lock()
-            .await
+    /// ignoring anything it already replayed. The receiver ends early when
+    /// the subscriber falls too far behind.
+    pub fn subscribe(&self, retro_id: i32) -> mpsc::Receiver<Broadcast> {
+        let (sender, receiver) = mpsc::channel(SUBSCRIBER_QUEUE_CAPACITY);
+        lock(self.shard(retro_id))
             .entry(retro_id)
             .or_default()
             .push(sender);
         receiver
     }
 
-    /// Deliver one event to all subscribers of its retro, pruning senders
-    /// whose clients disconnected.
-    async fn publish(&self, event: &Event) {
-        let mut subscribers = self.inner.subscribers.lock().await;
-        let Some(list) = subscribers.get_mut(&event.retro_id) else {
+    /// Whether anyone on this instance is subscribed to the retro.
+    fn has_subscribers(&self, retro_id: i32) -> bool {
+        lock(self.shard(retro_id))
+            .get(&retro_id)
+            .is_some_and(|senders| senders.iter().any(|sender| !sender.is_closed()))
+    }
+
+    pub fn metrics(&self) -> HubMetrics {
+        let mut metrics = HubMetrics {
+            retros: 0,
+            subscribers: 0,
+            queued: 0,
+            max_queue_depth: 0,
+            peak_queue_depth: self.inner.counters.peak_queue_depth.load(Ordering::Relaxed),
+            queue_capacity: SUBSCRIBER_QUEUE_CAPACITY,
+            published: self.inner.counters.published.load(Ordering::Relaxed),
+            delivered: self.inner.counters.delivered.load(Ordering::Relaxed),
+            dropped_subscribers: self
+                .inner
+                .counters
+                .dropped_subscribers
+                .load(Ordering::Relaxed),
+            event_queries: self.inner.counters.event_queries.load(Ordering::Relaxed),
+        };
+        for shard in &self.inner.shards {
+            for senders in lock(shard).values() {
+                metrics.retros += 1;
+                for sender in senders.iter().filter(|sender| !sender.is_closed()) {
+                    let depth = sender.max_capacity() - sender.capacity();
+                    metrics.subscribers += 1;
+                    metrics.queued += depth;
+                    metrics.max_queue_depth = metrics.max_queue_depth.max(depth);
+                }
+            }
+        }
+        metrics
+    }
+
+    /// Throttle typing announcements per user and column. Stopping is always
+    /// announced so indicators disappear right away.
+    fn should_announce_typing(
+        &self,
+        retro_id: i32,
+        user_id: i32,
+        category: Category,
+        stopped: bool,
+        now: Instant,
+    ) -> bool {
+        let mut announced = lock(&self.inner.typing_announced);
+        announced.retain(|_, at| now.duration_since(*at) < TYPING_EXPIRY);
+        let key = (retro_id, user_id, category);
+        if stopped {
+            announced.remove(&key);
+            return true;
+        }
+        if announced
+            .get(&key)
+            .is_some_and(|at| now.duration_since(*at) < TYPING_THROTTLE)
+        {
+            return false;
+        }
+        announced.insert(key, now);
+        true
+    }
+
+    /// Apply an announcement (from any instance) and send the new list of
+    /// typists to the retro's subscribers.
+    fn apply_typing(&self, announcement: TypingAnnouncement) {
+        let retro_id = announcement.retro_id;
+        let typists = {
+            let mut all = lock(&self.inner.typists);
+            let typists = all.entry(retro_id).or_default();
+            typists.retain(|typist| {
+                typist.user_id != announcement.user_id || typist.category != announcement.category
+            });
+            if !announcement.stopped {
+                typists.push(Typist {
+                    user_id: announcement.user_id,
+                    name: announcement.name,
+                    category: announcement.category,
+                    expires_at: Instant::now() + TYPING_EXPIRY,
+                });
+            }
+            let snapshot = typists.clone();
+            if typists.is_empty() {
+                all.remove(&retro_id);
+            }
+            snapshot
+        };
+        self.publish_typists(retro_id, &typists);
+    }
+
+    /// Drop typists whose last announcement is too old, and tell the
+    /// subscribers of the affected retros.
+    fn expire_typists(&self, now: Instant) {
+        let mut changed = Vec::new();
+        {
+            let mut all = lock(&self.inner.typists);
+            for (retro_id, typists) in all.iter_mut() {
+                let before = typists.len();
+                typists.retain(|typist| typist.expires_at > now);
+                if typists.len() != before {
+                    changed.push((*retro_id, typists.clone()));
+                }
+            }
+            all.retain(|_, typists| !typists.is_empty());
+        }
+        for (retro_id, typists) in changed {
+            self.publish_typists(retro_id, &typists);
+        }
+    }
+
+    fn publish_typists(&self, retro_id: i32, typists: &[Typist]) {
+        self.publish(Broadcast::Signal(Signal {
+            retro_id,
+            signal_type: SignalType::Typing,
+            payload: serde_json::json!({ "typing": typists }),
+        }));
+    }
+
+    /// Deliver one event or signal to all subscribers of its retro without
+    /// waiting for any of them. Subscribers whose client disconnected or
+    /// whose queue is full are removed.
+    fn publish(&self, message: Broadcast) {
+        let retro_id = message.retro_id();
+        let counters = &self.inner.counters;
+        let mut shard = lock(self.shard(retro_id));
+        let Some(senders) = shard.get_mut(&retro_id) else {
             return;
         };
-        list.retain(|sender| sender.send(event.clone()).is_ok());
-        if list.is_empty() {
-            subscribers.remove(&event.retro_id);
+        counters.published.fetch_add(1, Ordering::Relaxed);
+        senders.retain(|sender| match sender.try_send(message.clone()) {
+            Ok(()) => {
+                counters.delivered.fetch_add(1, Ordering::Relaxed);
+                let depth = sender.max_capacity() - sender.capacity();
+                counters
+                    .peak_queue_depth
+                    .fetch_max(depth, Ordering::Relaxed);
+                true
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                counters.dropped_subscribers.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(
+                    retro_id,
+                    capacity = SUBSCRIBER_QUEUE_CAPACITY,
+                    "dropping slow SSE subscriber; it will reconnect and replay"
+                );
+                false
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+        if senders.is_empty() {
+            shard.remove(&retro_id);
         }
     }
 }
 
-/// Background task: LISTENs on the Postgres channel and forwards new `events`
-/// rows to the in-process hub. Spawned once per app process.
+/// Background task: LISTENs on the Postgres channels and forwards new `events`
+/// rows and presence changes to the in-process hub. Spawned once per app
+/// process.
 pub async fn notifier_loop(pool: PgPool, hub: EventHub) {
     let mut last_id = match sqlx::query_scalar!("SELECT COALESCE(MAX(id), 0) FROM events")
         .fetch_one(&pool)
@@ -190,23 +552,101 @@ pub async fn notifier_loop(pool: PgPool, hub: EventHub) {
 
 async fn notify_once(pool: &PgPool, hub: &EventHub, last_id: &mut i64) -> Result<(), sqlx::Error> {
     let mut listener = sqlx::postgres::PgListener::connect_with(pool).await?;
-    listener.listen("rostfacto_events").await?;
+    listener
+        .listen_all([EVENTS_CHANNEL, presence::PRESENCE_CHANNEL, TYPING_CHANNEL])
+        .await?;
+    // Catch up on events committed while this task was not listening.
+    publish_new_events(pool, hub, last_id).await?;
+
+    // Event notifications only mark events as pending; they are loaded
+    // together once the coalescing window after the first one has passed.
+    let mut flush_at = None;
     loop {
-        let notification = listener.recv().await?;
+        let notification = match flush_at {
+            Some(deadline) => match tokio::time::timeout_at(deadline, listener.recv()).await {
+                Ok(notification) => notification?,
+                Err(_) => {
+                    flush_at = None;
+                    publish_new_events(pool, hub, last_id).await?;
+                    continue;
+                }
+            },
+            None => listener.recv().await?,
+        };
+        if notification.channel() == TYPING_CHANNEL {
+            match serde_json::from_str(notification.payload()) {
+                Ok(announcement) => hub.apply_typing(announcement),
+                Err(error) => tracing::warn!(error = %error, "malformed typing announcement"),
+            }
+            continue;
+        }
         let Ok(retro_id) = notification.payload().parse::<i32>() else {
             continue;
         };
-        // Reading from the durable events table (rather than trusting the
-        // notification alone) also catches up on events that were committed
-        // while this task was disconnected.
-        let events = load_events(pool, retro_id, *last_id, i64::MAX).await?;
+        if notification.channel() == presence::PRESENCE_CHANNEL {
+            publish_participants(pool, hub, retro_id).await?;
+            continue;
+        }
+        flush_at.get_or_insert_with(|| tokio::time::Instant::now() + EVENT_COALESCE_WINDOW);
+    }
+}
+
+/// Load all events after `last_id`, of all retros, and fan them out.
+///
+/// Reading from the durable events table (rather than trusting the
+/// notifications alone) also catches up on events that were committed while
+/// the notifier was disconnected.
+async fn publish_new_events(
+    pool: &PgPool,
+    hub: &EventHub,
+    last_id: &mut i64,
+) -> Result<(), sqlx::Error> {
+    loop {
+        let events = load_new_events(pool, *last_id, EVENT_BATCH_SIZE).await?;
+        hub.inner
+            .counters
+            .event_queries
+            .fetch_add(1, Ordering::Relaxed);
+        let complete = events.len() < EVENT_BATCH_SIZE as usize;
         for event in events {
-            hub.publish(&event).await;
             *last_id = (*last_id).max(event.id);
+            hub.publish(Broadcast::Event(event));
+        }
+        if complete {
+            return Ok(());
         }
     }
 }
 
+/// Send the current participant list to this instance's subscribers of the
+/// retro, if there are any.
+async fn publish_participants(
+    pool: &PgPool,
+    hub: &EventHub,
+    retro_id: i32,
+) -> Result<(), sqlx::Error> {
+    if !hub.has_subscribers(retro_id) {
+        return Ok(());
+    }
+    let participants = presence::load_participants(pool, retro_id).await?;
+    hub.publish(Broadcast::Signal(Signal {
+        retro_id,
+        signal_type: SignalType::Participants,
+        payload: serde_json::json!({ "participants": participants }),
+    }));
+    Ok(())
+}
+
+/// Background task: ends typing indicators of people who stopped announcing
+/// (closed the tab, went idle). Spawned once per app process.
+pub async fn typing_expiry_loop(hub: EventHub) {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        interval.tick().await;
+        hub.expire_typists(Instant::now());
+    }
+}
+
 fn sse_frame(event: &Event) -> Result<Bytes, std::convert::Infallible> {
     let data = serde_json::to_string(&event.payload).expect("event payload should serialize");
     Ok(Bytes::from(format!(
@@ -215,16 +655,166 @@ fn sse_frame(event: &Event) -> Result<Bytes, std::convert::Infallible> {
     )))
 }
 
+/// Signals carry no `id:` so they never move the client's `Last-Event-ID`.
+fn signal_frame(signal: &Signal) -> Result<Bytes, std::convert::Infallible> {
+    let data = serde_json::to_string(&signal.payload).expect("signal payload should serialize");
+    Ok(Bytes::from(format!(
+        "event: {}\ndata: {}\n\n",
+        signal.signal_type, data
+    )))
+}
+
+/// Tells a client that events it missed were deleted by retention, so it
+/// cannot catch up by replay and has to reload the board.
+fn reset_frame() -> Result<Bytes, std::convert::Infallible> {
+    Ok(Bytes::from_static(
+        b"event: RESET\ndata: {\"reason\":\"events_pruned\"}\n\n",
+    ))
+}
+
+/// How a client that reconnects catches up on what it missed.
+pub(crate) enum CatchUp {
+    /// The missed events, oldest first.
+    Replay(Vec<Event>),
+    /// Some of the missed events were deleted by retention (see `retention`);
+    /// the client has to reload the board.
+    Reset,
+}
+
+/// A client's live connection to a retro, shared by the SSE and WebSocket
+/// endpoints.
+pub(crate) struct Subscription {
+    pub receiver: mpsc::Receiver<Broadcast>,
+    /// Newest event at subscribe time. Live events up to it are covered by
+    /// the catch-up and must be skipped.
+    pub max_id: i64,
+    pub catch_up: CatchUp,
+    /// Keeps the user present in the retro until dropped with the connection.
+    pub presence: presence::PresenceGuard,
+}
+
+/// Subscribe a user to a retro's events and signals, catching up on the
+/// events after `last_event_id` (nothing for a fresh connection).
+pub(crate) async fn subscribe_with_catch_up(
+    state: &AppState,
+    retro_id: i32,
+    user_id: i32,
+    last_event_id: Option<i64>,
+) -> Result<Subscription, sqlx::Error> {
+    // Subscribe before computing the replay bound so that no event can fall
+    // between the replay query and the subscription.
+    let receiver = state.events.subscribe(retro_id);
+    let max_id = sqlx::query_scalar!(
+        "SELECT COALESCE(MAX(id), 0) FROM events WHERE retro_id = $1",
+        retro_id
+    )
+    .fetch_one(&state.pool)
+    .await
+    .inspect_err(|error| log_database_error("subscribe_max_event_id", error))?
+    .unwrap_or(0);
+
+    let catch_up = match last_event_id {
+        Some(since) => {
+            let replay = load_events(&state.pool, retro_id, since, max_id)
+                .await
+                .inspect_err(|error| log_database_error("subscribe_replay_events", error))?;
+            // Checked after loading the replay: events deleted before that
+            // query ran have raised the mark by now, as both happen in the
+            // same statement.
+            let pruned_through = retention::pruned_through(&state.pool, retro_id)
+                .await
+                .inspect_err(|error| log_database_error("subscribe_pruned_through", error))?;
+            if pruned_through.is_some_and(|id| id > since) {
+                CatchUp::Reset
+            } else {
+                CatchUp::Replay(replay)
+            }
+        }
+        None => CatchUp::Replay(Vec::new()),
+    };
+
+    // Joining after subscribing means this client receives the participant
+    // list that includes itself.
+    let presence = state
+        .presence
+        .join(retro_id, user_id)
+        .await
+        .inspect_err(|error| log_database_error("subscribe_join_presence", error))?;
+
+    Ok(Subscription {
+        receiver,
+        max_id,
+        catch_up,
+        presence,
+    })
+}
+
+/// How much an event's `data` carries.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadMode {
+    /// What the trigger recorded: just enough for the board to refetch.
+    #[default]
+    Compact,
+    /// Additionally the complete card (`item`) or action item
+    /// (`action_item`), so clients need no follow-up request.
+    Full,
+}
+
+#[derive(Deserialize)]
+pub struct EventsParams {
+    #[serde(default)]
+    payload: PayloadMode,
+}
+
+/// Prepare an event for a subscriber that asked for `mode`.
+///
+/// Full payloads reflect the state at delivery time, not at the time of the
+/// event: a replayed `ITEM_CREATED` already shows later likes, and the card
+/// or action item is `null` once it was deleted.
+pub(crate) async fn with_payload(pool: &PgPool, mode: PayloadMode, mut event: Event) -> Event {
+    if mode == PayloadMode::Compact {
+        return event;
+    }
+    if let Some(item_id) = event.item_id {
+        let item = match pool.acquire().await {
+            Ok(mut conn) => load_item_with_initials(&mut conn, item_id).await,
+            Err(error) => Err(error),
+        };
+        event.payload["item"] = embedded("events_full_payload_item", item);
+    }
+    if let Some(action_item_id) = event.action_item_id {
+        let action_item = load_action_item(pool, action_item_id).await;
+        event.payload["action_item"] = embedded("events_full_payload_action_item", action_item);
+    }
+    event
+}
+
+fn embedded<T: Serialize>(operation: &'static str, loaded: Result<T, sqlx::Error>) -> Value {
+    match loaded {
+        Ok(value) => serde_json::to_value(value).expect("models should serialize"),
+        Err(sqlx::Error::RowNotFound) => Value::Null,
+        Err(error) => {
+            log_database_error(operation, &error);
+            Value::Null
+        }
+    }
+}
+
 /// `GET /retro/{slug}/events` — SSE stream of events for one retro.
 ///
 /// Replays events newer than the client's `Last-Event-ID` (bounded by the
-/// newest event at connect time) and then streams live events, with periodic
-/// keep-alive comments.
+/// newest event at connect time) and then streams live events and signals,
+/// with periodic keep-alive comments. If some of the missed events were
+/// already deleted, a `RESET` frame replaces the replay. The user counts as
+/// present in the retro for as long as the stream is open. With
+/// `?payload=full`, events carry the complete card or action item.
 pub async fn retro_events(
     State(state): State<AppState>,
     user: AuthUser,
     headers: HeaderMap,
     Path(slug): Path<String>,
+    Query(params): Query<EventsParams>,
 ) -> Response {
     let retro = match require_retro_access(&state, &user, &slug).await {
         Ok(Some(retro)) => retro,
@@ -237,45 +827,39 @@ pub async fn retro_events(
         .and_then(|value| value.to_str().ok())
         .and_then(|value| value.parse::<i64>().ok());
 
-    // Subscribe before computing the replay bound so that no event can fall
-    // between the replay query and the subscription.
-    let mut receiver = state.events.subscribe(retro.id).await;
-    let max_id = match sqlx::query_scalar!(
-        "SELECT COALESCE(MAX(id), 0) FROM events WHERE retro_id = $1",
-        retro.id
-    )
-    .fetch_one(&state.pool)
-    .await
-    {
-        Ok(id) => id.unwrap_or(0),
-        Err(error) => {
-            log_database_error("sse_max_event_id", &error);
-            return database_error_response();
-        }
-    };
-
-    let replay = match last_event_id {
-        Some(since) => match load_events(&state.pool, retro.id, since, max_id).await {
-            Ok(events) => events,
-            Err(error) => {
-                log_database_error("sse_replay_events", &error);
-                return database_error_response();
-            }
-        },
-        None => Vec::new(),
+    let Subscription {
+        mut receiver,
+        max_id,
+        catch_up,
+        presence,
+    } = match subscribe_with_catch_up(&state, retro.id, user.user_id, last_event_id).await {
+        Ok(subscription) => subscription,
+        Err(_) => return database_error_response(),
     };
 
+    let pool = state.pool.clone();
+    let mode = params.payload;
     let stream = async_stream::stream! {
-        for event in replay {
-            yield sse_frame(&event);
+        // Dropped together with the stream when the client disconnects.
+        let _presence = presence;
+        match catch_up {
+            CatchUp::Reset => yield reset_frame(),
+            CatchUp::Replay(replay) => {
+                for event in replay {
+                    yield sse_frame(&with_payload(&pool, mode, event).await);
+                }
+            }
         }
         let mut keepalive = tokio::time::interval(std::time::Duration::from_secs(15));
         loop {
             tokio::select! {
                 received = receiver.recv() => {
                     match received {
-                        Some(event) if event.id > max_id => yield sse_frame(&event),
-                        Some(_) => {} // already covered by the replay
+                        Some(Broadcast::Event(event)) if event.id > max_id => {
+                            yield sse_frame(&with_payload(&pool, mode, event).await);
+                        }
+                        Some(Broadcast::Event(_)) => {} // already covered by the replay
+                        Some(Broadcast::Signal(signal)) => yield signal_frame(&signal),
                         None => break,
                     }
                 }
@@ -296,3 +880,242 @@ pub async fn retro_events(
         .body(Body::from_stream(stream))
         .expect("SSE response should build")
 }
+
+#[derive(Deserialize)]
+pub struct TypingForm {
+    category: Category,
+    /// Present when the user submitted or discarded the draft.
+    stopped: Option<String>,
+}
+
+/// `POST /retro/{slug}/typing` — announce that the user is drafting a card in
+/// a column (or stopped doing so). Announcements are ephemeral: they are
+/// relayed to all instances via NOTIFY and never touch the `events` table.
+pub async fn announce_typing(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(slug): Path<String>,
+    Form(form): Form<TypingForm>,
+) -> Result<StatusCode, HandlerError> {
+    let retro = match require_retro_access(&state, &user, &slug).await? {
+        Some(retro) => retro,
+        None => return Err(not_found_response(&state, &slug).into()),
+    };
+    let stopped = form.stopped.is_some();
+    if !state.events.should_announce_typing(
+        retro.id,
+        user.user_id,
+        form.category,
+        stopped,
+        Instant::now(),
+    ) {
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    let name = sqlx::query_scalar!(
+        r#"SELECT display_name as "name!" FROM users WHERE id = $1"#,
+        user.user_id
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|error| {
+        log_database_error("typing_load_name", &error);
+        database_error_response()
+    })?;
+    let announcement = TypingAnnouncement {
+        retro_id: retro.id,
+        user_id: user.user_id,
+        name,
+        category: form.category,
+        stopped,
+    };
+    let payload =
+        serde_json::to_string(&announcement).expect("typing announcement should serialize");
+    sqlx::query!("SELECT pg_notify($1, $2)", TYPING_CHANNEL, payload)
+        .execute(&state.pool)
+        .await
+        .map_err(|error| {
+            log_database_error("typing_notify", &error);
+            database_error_response()
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn typing_announcements_are_throttled_per_user_and_column() {
+        let hub = EventHub::new();
+        let now = Instant::now();
+        assert!(hub.should_announce_typing(1, 7, Category::Good, false, now));
+        assert!(!hub.should_announce_typing(1, 7, Category::Good, false, now));
+        assert!(hub.should_announce_typing(1, 7, Category::Bad, false, now));
+        assert!(hub.should_announce_typing(1, 8, Category::Good, false, now));
+
+        let later = now + TYPING_THROTTLE;
+        assert!(hub.should_announce_typing(1, 7, Category::Good, false, later));
+    }
+
+    #[tokio::test]
+    async fn stopping_is_always_announced() {
+        let hub = EventHub::new();
+        let now = Instant::now();
+        assert!(hub.should_announce_typing(1, 7, Category::Good, false, now));
+        assert!(hub.should_announce_typing(1, 7, Category::Good, true, now));
+        assert!(hub.should_announce_typing(1, 7, Category::Good, false, now));
+    }
+
+    #[tokio::test]
+    async fn typists_expire_without_announcements() {
+        let hub = EventHub::new();
+        let mut receiver = hub.subscribe(1);
+        hub.apply_typing(TypingAnnouncement {
+            retro_id: 1,
+            user_id: 7,
+            name: "Ada".to_string(),
+            category: Category::Watch,
+            stopped: false,
+        });
+        let Some(Broadcast::Signal(signal)) = receiver.recv().await else {
+            panic!("expected a typing signal");
+        };
+        assert_eq!(signal.signal_type, SignalType::Typing);
+        assert_eq!(signal.payload["typing"][0]["name"], "Ada");
+        assert_eq!(signal.payload["typing"][0]["category"], "Watch");
+
+        hub.expire_typists(Instant::now());
+        assert!(receiver.try_recv().is_err(), "nobody expired yet");
+
+        hub.expire_typists(Instant::now() + TYPING_EXPIRY);
+        let Some(Broadcast::Signal(signal)) = receiver.recv().await else {
+            panic!("expected a typing signal");
+        };
+        assert_eq!(signal.payload["typing"], serde_json::json!([]));
+    }
+
+    fn participants_signal(retro_id: i32) -> Broadcast {
+        Broadcast::Signal(Signal {
+            retro_id,
+            signal_type: SignalType::Participants,
+            payload: serde_json::json!({ "participants": [] }),
+        })
+    }
+
+    #[tokio::test]
+    async fn slow_subscribers_are_dropped_without_affecting_others() {
+        let hub = EventHub::new();
+        let mut slow = hub.subscribe(1);
+        let mut fast = hub.subscribe(1);
+        for _ in 0..=SUBSCRIBER_QUEUE_CAPACITY {
+            hub.publish(participants_signal(1));
+            assert!(fast.recv().await.is_some());
+        }
+
+        let mut queued = 0;
+        while slow.recv().await.is_some() {
+            queued += 1;
+        }
+        assert_eq!(
+            queued, SUBSCRIBER_QUEUE_CAPACITY,
+            "the queue drains, then ends"
+        );
+
+        hub.publish(participants_signal(1));
+        assert!(fast.recv().await.is_some());
+        assert!(hub.has_subscribers(1));
+    }
+
+    #[tokio::test]
+    async fn metrics_report_queue_depth_and_drops() {
+        let hub = EventHub::new();
+        let _idle = hub.subscribe(1);
+        let _other_retro = hub.subscribe(-17);
+        for _ in 0..3 {
+            hub.publish(participants_signal(1));
+        }
+        hub.publish(participants_signal(2)); // nobody listening
+
+        let metrics = hub.metrics();
+        assert_eq!(metrics.retros, 2);
+        assert_eq!(metrics.subscribers, 2);
+        assert_eq!(metrics.queued, 3);
+        assert_eq!(metrics.max_queue_depth, 3);
+        assert_eq!(metrics.peak_queue_depth, 3);
+        assert_eq!(metrics.published, 3);
+        assert_eq!(metrics.delivered, 3);
+        assert_eq!(metrics.dropped_subscribers, 0);
+
+        for _ in 0..SUBSCRIBER_QUEUE_CAPACITY {
+            hub.publish(participants_signal(1));
+        }
+        let metrics = hub.metrics();
+        assert_eq!(metrics.subscribers, 1);
+        assert_eq!(metrics.dropped_subscribers, 1);
+        assert_eq!(metrics.peak_queue_depth, SUBSCRIBER_QUEUE_CAPACITY);
+    }
+
+    #[tokio::test]
+    async fn notifier_loads_bulk_events_in_few_queries() {
+        let database_url =
+            std::env::var("DATABASE_URL").expect("DATABASE_URL environment variable must be set");
+        let pool = PgPool::connect(&database_url)
+            .await
+            .expect("Failed to connect to database");
+        let user_id = crate::auth::ensure_demo_user(&pool)
+            .await
+            .expect("Failed to ensure demo user");
+        let slug = format!("notifier-batch-{}", std::process::id());
+        let retro_id = sqlx::query_scalar!(
+            "INSERT INTO retrospectives (title, slug, team_slug, created_by)
+             VALUES ('Notifier', $1, 'demo', $2) RETURNING id",
+            slug,
+            user_id
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to create retro");
+
+        let hub = EventHub::new();
+        let mut receiver = hub.subscribe(retro_id);
+        let notifier = tokio::spawn(notifier_loop(pool.clone(), hub.clone()));
+        // Let the notifier start listening before the burst.
+        while hub.metrics().event_queries == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let queries_before = hub.metrics().event_queries;
+
+        // One bulk insert notifies once per card, all at commit.
+        const CARDS: i32 = 100;
+        sqlx::query!(
+            "INSERT INTO items (retro_id, text, category, status, created_by)
+             SELECT $1, 'Card ' || n, 'GOOD'::category, 'CREATED'::status, $2
+             FROM generate_series(1, $3::int) n",
+            retro_id,
+            user_id,
+            CARDS
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to insert cards");
+
+        for _ in 0..CARDS {
+            let received = tokio::time::timeout(Duration::from_secs(10), receiver.recv())
+                .await
+                .expect("timed out waiting for events");
+            assert!(matches!(received, Some(Broadcast::Event(_))));
+        }
+        let queries = hub.metrics().event_queries - queries_before;
+        assert!(
+            queries <= 3,
+            "{CARDS} notifications should be coalesced, but took {queries} queries"
+        );
+
+        notifier.abort();
+        sqlx::query!("DELETE FROM retrospectives WHERE id = $1", retro_id)
+            .execute(&pool)
+            .await
+            .expect("Failed to delete test retro");
+    }
+}
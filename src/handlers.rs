@@ -1,22 +1,28 @@
+use crate::audit::{self, AuditAction, RequestMeta};
 use crate::auth::{
     read_cookie, AuthUser, MaybeAuthUser, ADMIN_REAUTH_MAX_AGE_SECONDS, SESSION_COOKIE,
 };
 use crate::events::EventType;
 use crate::models::{
-    apply_author_initials, ActionItem, Archive, Category, Item, Retrospective, Status,
+    apply_author_initials, initials_by_user, item_authors, ActionItem, Archive, Category,
+    EditAccess, EditPolicy, Item, ItemComment, ItemRevision, Reaction, ReactionCount, RetroTimer,
+    RetroTimerKind, RetroTimers, Retrospective, Status,
 };
+use crate::presence::load_participants;
 use crate::templates::{
     ActionItemEditTemplate, ActionItemTemplate, ArchiveListEntry, ArchiveModalTemplate,
     ArchiveTemplate, ArchivesTemplate, ErrorTemplate, GitHubTeam, HomeTemplate, ItemCardTemplate,
-    ItemEditTemplate, NewRetroTemplate, RetroTemplate, RetrosTemplate,
+    ItemEditTemplate, ItemHistoryTemplate, NewRetroTemplate, RetroTemplate, RetroTimersTemplate,
+    RetrosTemplate,
 };
+use crate::undo::{attach_undo_header, set_actor};
 use crate::AppState;
 use askama::Template;
 use axum::{
     extract::{Path, Query, State},
     http::{header::HeaderName, request::Parts, HeaderValue, StatusCode},
     response::{Html, IntoResponse, Response},
-    Form,
+    Form, Json,
 };
 use chrono::Utc;
 use serde::Deserialize;
@@ -28,6 +34,18 @@ use std::collections::HashMap;
 const MAX_ITEM_TEXT_LENGTH: usize = 5_000;
 /// Upper bound for the retro title. Mirrored by `retrospectives_title_length_check`.
 const MAX_RETRO_TITLE_LENGTH: usize = 200;
+/// Upper bound for a comment on a card. Mirrored by `item_comments_text_length_check`.
+const MAX_COMMENT_TEXT_LENGTH: usize = 500;
+/// Upper bound for the discussion notes of a card. Mirrored by `items_notes_length_check`.
+const MAX_NOTES_LENGTH: usize = 5_000;
+/// Upper bound for the discussion length and the extension step of a timer.
+/// Mirrored (in seconds) by the `retrospectives_timer_*_check` constraints.
+const MAX_TIMER_MINUTES: i32 = 60;
+/// Upper bound for the maximum total length of a timer, and for the session
+/// timer.
+const MAX_TIMER_TOTAL_MINUTES: i32 = 240;
+/// Upper bound for the name of an agenda phase. Mirrored by `retro_timers_label_check`.
+const MAX_PHASE_LABEL_LENGTH: usize = 50;
 
 /// Error type returned by handlers. `axum::http::Response` is larger than the
 /// 128-byte threshold that trips `clippy::result_large_err`, so it is boxed;
@@ -65,20 +83,100 @@ pub(crate) fn database_error_response() -> Response {
     (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
 }
 
-async fn load_item_with_initials(
+/// Why a card mutation was refused. The mutations are shared by the HTTP
+/// handlers, which render this as an error page, and the WebSocket endpoint,
+/// which reports it to the client as a message.
+pub(crate) enum MutationError {
+    NotFound,
+    Forbidden,
+    Invalid(String),
+    /// Another card of the retro is already highlighted; carries the unchanged
+    /// card and who may edit it.
+    HighlightConflict(Box<Item>, EditAccess),
+    /// Already logged where it happened.
+    Database,
+}
+
+impl MutationError {
+    pub(crate) fn status(&self) -> StatusCode {
+        match self {
+            MutationError::NotFound => StatusCode::NOT_FOUND,
+            MutationError::Forbidden => StatusCode::FORBIDDEN,
+            MutationError::Invalid(_) => StatusCode::BAD_REQUEST,
+            MutationError::HighlightConflict(..) => StatusCode::CONFLICT,
+            MutationError::Database => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub(crate) fn message(&self) -> String {
+        match self {
+            MutationError::NotFound => "Not found".to_string(),
+            MutationError::Forbidden => ACCESS_DENIED_MESSAGE.to_string(),
+            MutationError::Invalid(message) => message.clone(),
+            MutationError::HighlightConflict(..) => HIGHLIGHT_CONFLICT_MESSAGE.to_string(),
+            MutationError::Database => "Database error".to_string(),
+        }
+    }
+
+    fn into_response(self, state: &AppState) -> Response {
+        match self {
+            MutationError::NotFound => not_found_response(state, ""),
+            MutationError::Forbidden => forbidden(state, ACCESS_DENIED_MESSAGE),
+            MutationError::Invalid(message) => bad_request(state, &message),
+            // The card is re-rendered in place, showing the error.
+            MutationError::HighlightConflict(item, edit_access) => Html(
+                ItemCardTemplate {
+                    item: *item,
+                    error_message: Some(HIGHLIGHT_CONFLICT_MESSAGE.to_string()),
+                    edit_access,
+                }
+                .render()
+                .unwrap(),
+            )
+            .into_response(),
+            MutationError::Database => database_error_response(),
+        }
+    }
+}
+
+const ACCESS_DENIED_MESSAGE: &str = "You do not have access to this retrospective";
+const HIGHLIGHT_CONFLICT_MESSAGE: &str = "Only one item can be highlighted at a time";
+
+pub(crate) async fn load_item_with_initials(
     conn: &mut sqlx::PgConnection,
     item_id: i32,
 ) -> Result<Item, sqlx::Error> {
+    load_retro_items_with_initials(conn, item_id)
+        .await?
+        .into_iter()
+        .find(|item| item.id == item_id)
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// All cards of the retro the given card belongs to, with the initials of
+/// their authors disambiguated among them.
+async fn load_retro_items_with_initials(
+    conn: &mut sqlx::PgConnection,
+    item_id: i32,
+) -> Result<Vec<Item>, sqlx::Error> {
     let mut items = sqlx::query_as!(
         Item,
         r#"SELECT i.id as "id!", i.retro_id as "retro_id!", i.text as "text!",
                   i.category as "category: _", i.created_at as "created_at!", i.status as "status: _",
                   i.created_by as "author_id!", u.display_name as "author_name!",
                   ''::text as "author_initials!",
+                  u.initials as "author_custom_initials: _",
+                  CASE WHEN u.show_avatar THEN u.avatar_url END as "author_avatar_url: _",
                   (SELECT COUNT(*) FROM likes WHERE item_id = i.id) as "likes_count!",
+                  item_reactions_json(i.id) as "reactions!: sqlx::types::Json<Vec<ReactionCount>>",
+                  (SELECT COUNT(*) FROM item_revisions WHERE item_id = i.id) as "revisions_count!",
+                  i.position as "position!",
+                  item_comments_json(i.id) as "comments!: sqlx::types::Json<Vec<ItemComment>>",
+                  i.notes as "notes!", i.notes_revision as "notes_revision!",
                   i.archive_id as "archive_id: _", i.archived_at as "archived_at: _",
                   i.timer_started_at as "timer_started_at: _", i.timer_duration_seconds as "timer_duration_seconds: _",
-                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _"
+                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _",
+                  i.timer_paused_at as "timer_paused_at: _"
            FROM items i
            JOIN users u ON u.id = i.created_by
            WHERE i.retro_id = (SELECT retro_id FROM items WHERE id = $1)"#,
@@ -88,17 +186,19 @@ async fn load_item_with_initials(
     .await?;
 
     apply_author_initials(&mut [&mut items]);
-    items
-        .into_iter()
-        .find(|item| item.id == item_id)
-        .ok_or(sqlx::Error::RowNotFound)
+    Ok(items)
 }
 
-async fn load_action_item(pool: &PgPool, action_item_id: i32) -> Result<ActionItem, sqlx::Error> {
+pub(crate) async fn load_action_item(
+    pool: &PgPool,
+    action_item_id: i32,
+) -> Result<ActionItem, sqlx::Error> {
     sqlx::query_as!(
         ActionItem,
         r#"SELECT id as "id!", retro_id as "retro_id!", text as "text!", created_at as "created_at!",
-                  completed_at as "completed_at: _", archive_id as "archive_id: _", archived_at as "archived_at: _"
+                  completed_at as "completed_at: _", archive_id as "archive_id: _", archived_at as "archived_at: _",
+                  source_item_id as "source_item_id: _",
+                  (SELECT text FROM items WHERE items.id = action_items.source_item_id) as "source_item_text: _"
            FROM action_items WHERE id = $1"#,
         action_item_id
     )
@@ -106,7 +206,7 @@ async fn load_action_item(pool: &PgPool, action_item_id: i32) -> Result<ActionIt
     .await
 }
 
-fn forbidden(state: &AppState, message: &str) -> Response {
+pub(crate) fn forbidden(state: &AppState, message: &str) -> Response {
     let template = ErrorTemplate {
         code: "403",
         message: message.to_string(),
@@ -124,7 +224,7 @@ pub(crate) fn not_found_response(state: &AppState, slug: &str) -> Response {
     (StatusCode::NOT_FOUND, Html(template.render().unwrap())).into_response()
 }
 
-fn not_found_page(state: &AppState) -> Response {
+pub(crate) fn not_found_page(state: &AppState) -> Response {
     let template = ErrorTemplate {
         code: "404",
         message: "Page not found".to_string(),
@@ -133,7 +233,7 @@ fn not_found_page(state: &AppState) -> Response {
     (StatusCode::NOT_FOUND, Html(template.render().unwrap())).into_response()
 }
 
-fn bad_request(state: &AppState, message: &str) -> Response {
+pub(crate) fn bad_request(state: &AppState, message: &str) -> Response {
     let template = ErrorTemplate {
         code: "400",
         message: message.to_string(),
@@ -142,10 +242,22 @@ fn bad_request(state: &AppState, message: &str) -> Response {
     (StatusCode::BAD_REQUEST, Html(template.render().unwrap())).into_response()
 }
 
+pub(crate) fn conflict(state: &AppState, message: &str) -> Response {
+    let template = ErrorTemplate {
+        code: "409",
+        message: message.to_string(),
+        demo_mode: state.config.demo_mode(),
+    };
+    (StatusCode::CONFLICT, Html(template.render().unwrap())).into_response()
+}
+
 async fn load_retro(pool: &PgPool, slug: &str) -> Result<Option<Retrospective>, sqlx::Error> {
     sqlx::query_as!(
         Retrospective,
-        "SELECT * FROM retrospectives WHERE slug = $1",
+        r#"SELECT id, title, slug, created_at, updated_at, team_slug, created_by,
+                  edit_policy as "edit_policy: EditPolicy", timer_default_seconds, timer_extend_seconds,
+                  timer_max_seconds, timer_auto_start
+           FROM retrospectives WHERE slug = $1"#,
         slug
     )
     .fetch_optional(pool)
@@ -181,7 +293,7 @@ pub(crate) async fn require_retro_access(
     if user.is_admin || user.is_member_of_team(&retro.team_slug) {
         Ok(Some(retro))
     } else {
-        Err(forbidden(state, "You do not have access to this retrospective").into())
+        Err(forbidden(state, ACCESS_DENIED_MESSAGE).into())
     }
 }
 
@@ -190,9 +302,22 @@ async fn require_retro_access_by_id(
     user: &AuthUser,
     retro_id: i32,
 ) -> Result<Option<Retrospective>, HandlerError> {
+    retro_access_by_id(state, user, retro_id)
+        .await
+        .map_err(|error| error.into_response(state).into())
+}
+
+async fn retro_access_by_id(
+    state: &AppState,
+    user: &AuthUser,
+    retro_id: i32,
+) -> Result<Option<Retrospective>, MutationError> {
     let retro = match sqlx::query_as!(
         Retrospective,
-        "SELECT * FROM retrospectives WHERE id = $1",
+        r#"SELECT id, title, slug, created_at, updated_at, team_slug, created_by,
+                  edit_policy as "edit_policy: EditPolicy", timer_default_seconds, timer_extend_seconds,
+                  timer_max_seconds, timer_auto_start
+           FROM retrospectives WHERE id = $1"#,
         retro_id
     )
     .fetch_optional(&state.pool)
@@ -202,14 +327,54 @@ async fn require_retro_access_by_id(
         Ok(None) => return Ok(None),
         Err(error) => {
             log_database_error("load_retro_by_id", &error);
-            return Err(database_error_response().into());
+            return Err(MutationError::Database);
         }
     };
 
     if user.is_admin || user.is_member_of_team(&retro.team_slug) {
         Ok(Some(retro))
     } else {
-        Err(forbidden(state, "You do not have access to this retrospective").into())
+        Err(MutationError::Forbidden)
+    }
+}
+
+/// The retro of an item the user wants to change, if the user has access.
+async fn item_retro_for_mutation(
+    state: &AppState,
+    user: &AuthUser,
+    item_id: i32,
+) -> Result<Retrospective, MutationError> {
+    let retro_id = sqlx::query_scalar!("SELECT retro_id FROM items WHERE id = $1", item_id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|error| {
+            log_database_error("load_item_retro_id", &error);
+            MutationError::Database
+        })?
+        .ok_or(MutationError::NotFound)?;
+    retro_access_by_id(state, user, retro_id)
+        .await?
+        .ok_or(MutationError::Forbidden)
+}
+
+/// Refuse editing `item` unless the retro's edit policy allows it to `user`.
+fn require_edit_access(
+    state: &AppState,
+    retro: &Retrospective,
+    user: &AuthUser,
+    item: &Item,
+) -> Result<EditAccess, HandlerError> {
+    let edit_access = EditAccess::new(retro, user);
+    if edit_access.allows(item) {
+        Ok(edit_access)
+    } else {
+        tracing::debug!(
+            item_id = item.id,
+            user_id = user.user_id,
+            policy = %retro.edit_policy,
+            "card edit refused"
+        );
+        Err(forbidden(state, retro.edit_policy.denial_message()).into())
     }
 }
 
@@ -228,7 +393,10 @@ pub async fn list_retros(
     let retros = if user.is_admin {
         sqlx::query_as!(
             Retrospective,
-            "SELECT * FROM retrospectives ORDER BY created_at DESC"
+            r#"SELECT id, title, slug, created_at, updated_at, team_slug, created_by,
+                      edit_policy as "edit_policy: EditPolicy", timer_default_seconds, timer_extend_seconds,
+                      timer_max_seconds, timer_auto_start
+               FROM retrospectives ORDER BY created_at DESC"#
         )
         .fetch_all(&state.pool)
         .await
@@ -243,7 +411,10 @@ pub async fn list_retros(
         );
         sqlx::query_as!(
             Retrospective,
-            "SELECT * FROM retrospectives WHERE team_slug = ANY($1) ORDER BY created_at DESC",
+            r#"SELECT id, title, slug, created_at, updated_at, team_slug, created_by,
+                      edit_policy as "edit_policy: EditPolicy", timer_default_seconds, timer_extend_seconds,
+                      timer_max_seconds, timer_auto_start
+               FROM retrospectives WHERE team_slug = ANY($1) ORDER BY created_at DESC"#,
             &team_slugs
         )
         .fetch_all(&state.pool)
@@ -294,6 +465,7 @@ pub async fn new_retro(
 pub async fn create_retro(
     State(state): State<AppState>,
     user: AuthUser,
+    meta: RequestMeta,
     Form(form): Form<NewRetro>,
 ) -> impl IntoResponse {
     if !user.is_admin {
@@ -337,15 +509,28 @@ pub async fn create_retro(
         }
     };
 
+    let mut tx = match state.pool.begin().await {
+        Ok(tx) => tx,
+        Err(error) => {
+            log_database_error("create_retro_begin_transaction", &error);
+            return database_error_response();
+        }
+    };
+
     let retro = match sqlx::query_as!(
         Retrospective,
-        "INSERT INTO retrospectives (title, slug, team_slug, created_by) VALUES ($1, $2, $3, $4) RETURNING *",
+        r#"INSERT INTO retrospectives (title, slug, team_slug, created_by, edit_policy)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING id, title, slug, created_at, updated_at, team_slug, created_by,
+                     edit_policy as "edit_policy: EditPolicy", timer_default_seconds, timer_extend_seconds,
+                     timer_max_seconds, timer_auto_start"#,
         title,
         form.slug,
         team_slug,
-        user.user_id
+        user.user_id,
+        form.edit_policy as EditPolicy
     )
-    .fetch_one(&state.pool)
+    .fetch_one(&mut *tx)
     .await
     {
         Ok(retro) => retro,
@@ -374,6 +559,28 @@ pub async fn create_retro(
         }
     };
 
+    if let Err(error) = audit::record(
+        &mut *tx,
+        user.user_id,
+        AuditAction::RetroCreated,
+        &retro.slug,
+        serde_json::json!({
+            "title": retro.title,
+            "team": retro.team_slug,
+            "edit_policy": retro.edit_policy,
+        }),
+        &meta,
+    )
+    .await
+    {
+        log_database_error("create_retro_audit", &error);
+        return database_error_response();
+    }
+    if let Err(error) = tx.commit().await {
+        log_database_error("create_retro_commit_transaction", &error);
+        return database_error_response();
+    }
+
     tracing::info!(
         retro_id = retro.id,
         user_id = user.user_id,
@@ -387,6 +594,194 @@ pub async fn create_retro(
         .into_response()
 }
 
+/// `POST /retro/{slug}/edit-policy` — facilitators choose who may edit the
+/// cards of the retro.
+pub async fn update_edit_policy(
+    State(state): State<AppState>,
+    user: AuthUser,
+    meta: RequestMeta,
+    Path(slug): Path<String>,
+    Form(form): Form<EditPolicyForm>,
+) -> Result<Response, HandlerError> {
+    let retro = match require_retro_access(&state, &user, &slug).await? {
+        Some(retro) => retro,
+        None => return Err(not_found_response(&state, &slug).into()),
+    };
+    if !retro.is_facilitator(&user) {
+        return Err(forbidden(&state, "Only facilitators can change who can edit cards").into());
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("update_edit_policy_begin_transaction", &error);
+        database_error_response()
+    })?;
+    sqlx::query!(
+        "UPDATE retrospectives SET edit_policy = $1 WHERE id = $2",
+        form.edit_policy as EditPolicy,
+        retro.id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("update_edit_policy", &error);
+        database_error_response()
+    })?;
+    audit::record(
+        &mut *tx,
+        user.user_id,
+        AuditAction::RetroEditPolicyChanged,
+        &retro.slug,
+        serde_json::json!({ "from": retro.edit_policy, "to": form.edit_policy }),
+        &meta,
+    )
+    .await
+    .map_err(|error| {
+        log_database_error("update_edit_policy_audit", &error);
+        database_error_response()
+    })?;
+    tx.commit().await.map_err(|error| {
+        log_database_error("update_edit_policy_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::info!(
+        retro_id = retro.id,
+        user_id = user.user_id,
+        edit_policy = %form.edit_policy,
+        "edit policy changed"
+    );
+
+    Ok((
+        StatusCode::SEE_OTHER,
+        [("Location", format!("/retro/{}", retro.slug))],
+    )
+        .into_response())
+}
+
+/// Change the highlight timer settings of a retro. Facilitators only; they
+/// apply to timers started or extended from now on.
+pub async fn update_timer_settings(
+    State(state): State<AppState>,
+    user: AuthUser,
+    meta: RequestMeta,
+    Path(slug): Path<String>,
+    Form(form): Form<TimerSettingsForm>,
+) -> Result<Response, HandlerError> {
+    let retro = match require_retro_access(&state, &user, &slug).await? {
+        Some(retro) => retro,
+        None => return Err(not_found_response(&state, &slug).into()),
+    };
+    if !retro.is_facilitator(&user) {
+        return Err(forbidden(&state, "Only facilitators can change the timer settings").into());
+    }
+
+    if !(1..=MAX_TIMER_MINUTES).contains(&form.discussion_minutes) {
+        return Err(bad_request(
+            &state,
+            &format!("The discussion length must be between 1 and {MAX_TIMER_MINUTES} minutes"),
+        )
+        .into());
+    }
+    if !(1..=MAX_TIMER_MINUTES).contains(&form.extension_minutes) {
+        return Err(bad_request(
+            &state,
+            &format!("The extension must be between 1 and {MAX_TIMER_MINUTES} minutes"),
+        )
+        .into());
+    }
+    let max_minutes = match form.max_minutes.trim() {
+        "" => None,
+        value => match value.parse::<i32>() {
+            Ok(minutes)
+                if (form.discussion_minutes..=MAX_TIMER_TOTAL_MINUTES).contains(&minutes) =>
+            {
+                Some(minutes)
+            }
+            _ => {
+                return Err(bad_request(
+                    &state,
+                    &format!(
+                        "The maximum length must be between the discussion length and {MAX_TIMER_TOTAL_MINUTES} minutes"
+                    ),
+                )
+                .into())
+            }
+        },
+    };
+    let default_seconds = form.discussion_minutes * 60;
+    let extend_seconds = form.extension_minutes * 60;
+    let max_seconds = max_minutes.map(|minutes| minutes * 60);
+    let auto_start = form.auto_start.is_some();
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("update_timer_settings_begin_transaction", &error);
+        database_error_response()
+    })?;
+    sqlx::query!(
+        "UPDATE retrospectives
+         SET timer_default_seconds = $2, timer_extend_seconds = $3,
+             timer_max_seconds = $4, timer_auto_start = $5
+         WHERE id = $1",
+        retro.id,
+        default_seconds,
+        extend_seconds,
+        max_seconds,
+        auto_start
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("update_timer_settings", &error);
+        database_error_response()
+    })?;
+    audit::record(
+        &mut *tx,
+        user.user_id,
+        AuditAction::RetroTimerSettingsChanged,
+        &retro.slug,
+        serde_json::json!({
+            "from": {
+                "default_seconds": retro.timer_default_seconds,
+                "extend_seconds": retro.timer_extend_seconds,
+                "max_seconds": retro.timer_max_seconds,
+                "auto_start": retro.timer_auto_start,
+            },
+            "to": {
+                "default_seconds": default_seconds,
+                "extend_seconds": extend_seconds,
+                "max_seconds": max_seconds,
+                "auto_start": auto_start,
+            },
+        }),
+        &meta,
+    )
+    .await
+    .map_err(|error| {
+        log_database_error("update_timer_settings_audit", &error);
+        database_error_response()
+    })?;
+    tx.commit().await.map_err(|error| {
+        log_database_error("update_timer_settings_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::info!(
+        retro_id = retro.id,
+        user_id = user.user_id,
+        default_seconds,
+        extend_seconds,
+        ?max_seconds,
+        auto_start,
+        "timer settings changed"
+    );
+
+    Ok((
+        StatusCode::SEE_OTHER,
+        [("Location", format!("/retro/{}", retro.slug))],
+    )
+        .into_response())
+}
+
 pub async fn show_retro(
     State(state): State<AppState>,
     user: AuthUser,
@@ -396,6 +791,7 @@ pub async fn show_retro(
         Some(r) => r,
         None => return Ok(not_found_response(&state, &slug)),
     };
+    let edit_access = EditAccess::new(&retro, &user);
 
     let mut good_items = sqlx::query_as!(
         Item,
@@ -403,16 +799,24 @@ pub async fn show_retro(
                   i.category as "category: _", i.created_at as "created_at!", i.status as "status: _",
                   i.created_by as "author_id!", u.display_name as "author_name!",
                   ''::text as "author_initials!",
+                  u.initials as "author_custom_initials: _",
+                  CASE WHEN u.show_avatar THEN u.avatar_url END as "author_avatar_url: _",
                   (SELECT COUNT(*) FROM likes WHERE item_id = i.id) as "likes_count!",
+                  item_reactions_json(i.id) as "reactions!: sqlx::types::Json<Vec<ReactionCount>>",
+                  (SELECT COUNT(*) FROM item_revisions WHERE item_id = i.id) as "revisions_count!",
+                  i.position as "position!",
+                  item_comments_json(i.id) as "comments!: sqlx::types::Json<Vec<ItemComment>>",
+                  i.notes as "notes!", i.notes_revision as "notes_revision!",
                   i.archive_id as "archive_id: _", i.archived_at as "archived_at: _",
                   i.timer_started_at as "timer_started_at: _", i.timer_duration_seconds as "timer_duration_seconds: _",
-                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _"
+                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _",
+                  i.timer_paused_at as "timer_paused_at: _"
            FROM items i
            JOIN users u ON u.id = i.created_by
            WHERE i.retro_id = $1
            AND i.category = 'GOOD'
            AND i.archive_id IS NULL
-           ORDER BY i.created_at ASC"#,
+           ORDER BY i.position, i.id"#,
         retro.id
     )
     .fetch_all(&state.pool)
@@ -428,16 +832,24 @@ pub async fn show_retro(
                   i.category as "category: _", i.created_at as "created_at!", i.status as "status: _",
                   i.created_by as "author_id!", u.display_name as "author_name!",
                   ''::text as "author_initials!",
+                  u.initials as "author_custom_initials: _",
+                  CASE WHEN u.show_avatar THEN u.avatar_url END as "author_avatar_url: _",
                   (SELECT COUNT(*) FROM likes WHERE item_id = i.id) as "likes_count!",
+                  item_reactions_json(i.id) as "reactions!: sqlx::types::Json<Vec<ReactionCount>>",
+                  (SELECT COUNT(*) FROM item_revisions WHERE item_id = i.id) as "revisions_count!",
+                  i.position as "position!",
+                  item_comments_json(i.id) as "comments!: sqlx::types::Json<Vec<ItemComment>>",
+                  i.notes as "notes!", i.notes_revision as "notes_revision!",
                   i.archive_id as "archive_id: _", i.archived_at as "archived_at: _",
                   i.timer_started_at as "timer_started_at: _", i.timer_duration_seconds as "timer_duration_seconds: _",
-                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _"
+                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _",
+                  i.timer_paused_at as "timer_paused_at: _"
            FROM items i
            JOIN users u ON u.id = i.created_by
            WHERE i.retro_id = $1
            AND i.category = 'BAD'
            AND i.archive_id IS NULL
-           ORDER BY i.created_at ASC"#,
+           ORDER BY i.position, i.id"#,
         retro.id
     )
     .fetch_all(&state.pool)
@@ -453,16 +865,24 @@ pub async fn show_retro(
                   i.category as "category: _", i.created_at as "created_at!", i.status as "status: _",
                   i.created_by as "author_id!", u.display_name as "author_name!",
                   ''::text as "author_initials!",
+                  u.initials as "author_custom_initials: _",
+                  CASE WHEN u.show_avatar THEN u.avatar_url END as "author_avatar_url: _",
                   (SELECT COUNT(*) FROM likes WHERE item_id = i.id) as "likes_count!",
+                  item_reactions_json(i.id) as "reactions!: sqlx::types::Json<Vec<ReactionCount>>",
+                  (SELECT COUNT(*) FROM item_revisions WHERE item_id = i.id) as "revisions_count!",
+                  i.position as "position!",
+                  item_comments_json(i.id) as "comments!: sqlx::types::Json<Vec<ItemComment>>",
+                  i.notes as "notes!", i.notes_revision as "notes_revision!",
                   i.archive_id as "archive_id: _", i.archived_at as "archived_at: _",
                   i.timer_started_at as "timer_started_at: _", i.timer_duration_seconds as "timer_duration_seconds: _",
-                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _"
+                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _",
+                  i.timer_paused_at as "timer_paused_at: _"
            FROM items i
            JOIN users u ON u.id = i.created_by
            WHERE i.retro_id = $1
            AND i.category = 'WATCH'
            AND i.archive_id IS NULL
-           ORDER BY i.created_at ASC"#,
+           ORDER BY i.position, i.id"#,
         retro.id
     )
     .fetch_all(&state.pool)
@@ -475,7 +895,9 @@ pub async fn show_retro(
     let action_items = sqlx::query_as!(
         ActionItem,
         r#"SELECT id as "id!", retro_id as "retro_id!", text as "text!", created_at as "created_at!",
-                  completed_at as "completed_at: _", archive_id as "archive_id: _", archived_at as "archived_at: _"
+                  completed_at as "completed_at: _", archive_id as "archive_id: _", archived_at as "archived_at: _",
+                  source_item_id as "source_item_id: _",
+                  (SELECT text FROM items WHERE items.id = action_items.source_item_id) as "source_item_text: _"
            FROM action_items
            WHERE retro_id = $1 AND archive_id IS NULL
            ORDER BY created_at ASC"#,
@@ -519,14 +941,31 @@ pub async fn show_retro(
         || !watch_items.is_empty()
         || !action_items.is_empty();
 
+    let participants = load_participants(&state.pool, retro.id)
+        .await
+        .map_err(|error| {
+            log_database_error("show_retro_participants", &error);
+            database_error_response()
+        })?;
+
+    let timers = load_retro_timers(&state.pool, retro.id)
+        .await
+        .map_err(|error| {
+            log_database_error("show_retro_timers", &error);
+            database_error_response()
+        })?;
+
     let template = RetroTemplate {
         retro,
         good_items,
         bad_items,
         watch_items,
         action_items,
+        participants,
+        timers,
         show_archive_modal: all_completed,
         is_admin: user.is_admin,
+        edit_access,
         user: Some(user),
         demo_mode: state.config.demo_mode(),
         error_message: None,
@@ -536,34 +975,42 @@ pub async fn show_retro(
     Ok(Html(template.render().unwrap()).into_response())
 }
 
-pub async fn add_item(
-    State(state): State<AppState>,
-    user: AuthUser,
-    Path((category, retro_id)): Path<(Category, i32)>,
-    Form(form): Form<NewItem>,
-) -> Result<Response, HandlerError> {
-    match require_retro_access_by_id(&state, &user, retro_id).await? {
-        Some(_) => {}
-        None => {
-            return Err(not_found_response(&state, "").into());
-        }
-    }
+/// A changed card together with the id of the event the change produced
+/// (`None` when it produced none, e.g. a no-op status change).
+pub(crate) struct ItemMutation {
+    pub item: Item,
+    pub event_id: Option<i64>,
+    pub edit_access: EditAccess,
+    /// The card changed the initials shown for other authors (only a new
+    /// card can).
+    pub initials_changed: bool,
+}
 
-    let text = form.text.trim();
+/// Add a card to a retro.
+pub(crate) async fn create_item(
+    state: &AppState,
+    user: &AuthUser,
+    retro_id: i32,
+    category: Category,
+    text: &str,
+) -> Result<ItemMutation, MutationError> {
+    let Some(retro) = retro_access_by_id(state, user, retro_id).await? else {
+        return Err(MutationError::NotFound);
+    };
+
+    let text = text.trim();
     if text.is_empty() {
-        return Err(bad_request(&state, "Card text is required").into());
+        return Err(MutationError::Invalid("Card text is required".to_string()));
     }
     if text.chars().count() > MAX_ITEM_TEXT_LENGTH {
-        return Err(bad_request(
-            &state,
-            &format!("Card text must be {MAX_ITEM_TEXT_LENGTH} characters or less"),
-        )
-        .into());
+        return Err(MutationError::Invalid(format!(
+            "Card text must be {MAX_ITEM_TEXT_LENGTH} characters or less"
+        )));
     }
 
     let mut tx = state.pool.begin().await.map_err(|error| {
         log_database_error("add_item_begin_transaction", &error);
-        database_error_response()
+        MutationError::Database
     })?;
 
     let item_id = sqlx::query_scalar!(
@@ -579,7 +1026,7 @@ pub async fn add_item(
     .await
     .map_err(|error| {
         log_database_error("add_item", &error);
-        database_error_response()
+        MutationError::Database
     })?;
 
     // The item is brand new, so the only ITEM_CREATED event for it is the one
@@ -593,19 +1040,38 @@ pub async fn add_item(
     .await
     .map_err(|error| {
         log_database_error("add_item_event_id", &error);
-        database_error_response()
+        MutationError::Database
     })?;
 
-    let item = load_item_with_initials(&mut tx, item_id)
+    let items = load_retro_items_with_initials(&mut tx, item_id)
         .await
         .map_err(|error| {
             log_database_error("load_added_item", &error);
-            database_error_response()
+            MutationError::Database
         })?;
+    // A new author whose initials clash with someone else's changes the
+    // initials on that person's cards, too.
+    let initials_before = initials_by_user(items.iter().filter(|other| other.id != item_id).map(
+        |other| {
+            (
+                other.author_id,
+                other.author_name.as_str(),
+                other.author_custom_initials.as_deref(),
+            )
+        },
+    ));
+    let initials_changed = items.iter().any(|other| {
+        other.author_id != user.user_id
+            && initials_before.get(&other.author_id) != Some(&other.author_initials)
+    });
+    let Some(item) = items.into_iter().find(|item| item.id == item_id) else {
+        log_database_error("load_added_item", &sqlx::Error::RowNotFound);
+        return Err(MutationError::Database);
+    };
 
     tx.commit().await.map_err(|error| {
         log_database_error("add_item_commit_transaction", &error);
-        database_error_response()
+        MutationError::Database
     })?;
 
     tracing::debug!(
@@ -614,15 +1080,38 @@ pub async fn add_item(
         category = %item.category.to_string(),
         "item created"
     );
+    Ok(ItemMutation {
+        item,
+        event_id,
+        edit_access: EditAccess::new(&retro, user),
+        initials_changed,
+    })
+}
+
+pub async fn add_item(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((category, retro_id)): Path<(Category, i32)>,
+    Form(form): Form<NewItem>,
+) -> Result<Response, HandlerError> {
+    let ItemMutation {
+        item,
+        event_id,
+        edit_access,
+        initials_changed,
+    } = create_item(&state, &user, retro_id, category, &form.text)
+        .await
+        .map_err(|error| error.into_response(&state))?;
 
-    let needs_initials_refresh = item.author_initials.chars().count() > 2;
     let template = ItemCardTemplate {
         item,
         error_message: None,
+        edit_access,
     };
     let html = Html(template.render().unwrap());
 
-    let mut response = if needs_initials_refresh {
+    // Other cards show new initials, so reload the board.
+    let mut response = if initials_changed {
         (
             [(
                 HeaderName::from_static("hx-refresh"),
@@ -638,31 +1127,28 @@ pub async fn add_item(
     Ok(response)
 }
 
-pub async fn change_item_status(
-    State(state): State<AppState>,
-    user: AuthUser,
-    Path(item_id): Path<i32>,
-    Query(params): Query<HashMap<String, String>>,
-) -> Result<Response, HandlerError> {
-    // Verify the item exists and the user has access to its retro before mutating.
-    let retro_id = match sqlx::query_scalar!("SELECT retro_id FROM items WHERE id = $1", item_id)
-        .fetch_optional(&state.pool)
-        .await
-    {
-        Ok(Some(id)) => id,
-        Ok(None) => return Err(not_found_response(&state, "").into()),
-        Err(error) => {
-            log_database_error("load_item_retro_id", &error);
-            return Err(database_error_response().into());
-        }
-    };
+/// A status change of a card, see `change_status`.
+pub(crate) struct ItemStatusChange {
+    pub item: Item,
+    /// `None` for a no-op change (e.g. completing a card that is not
+    /// highlighted).
+    pub event_id: Option<i64>,
+    /// No active card is left that is not completed.
+    pub all_completed: bool,
+    pub edit_access: EditAccess,
+}
 
-    match require_retro_access_by_id(&state, &user, retro_id).await? {
-        Some(_) => {}
-        None => {
-            return Err(forbidden(&state, "You do not have access to this retrospective").into());
-        }
-    }
+/// Apply a status action (`highlight`, `complete` or `cancel`) to a card.
+pub(crate) async fn change_status(
+    state: &AppState,
+    user: &AuthUser,
+    item_id: i32,
+    action: Option<&str>,
+) -> Result<ItemStatusChange, MutationError> {
+    // Verify the item exists and the user has access to its retro before mutating.
+    let retro = item_retro_for_mutation(state, user, item_id).await?;
+    let retro_id = retro.id;
+    let edit_access = EditAccess::new(&retro, user);
 
     #[derive(sqlx::FromRow)]
     struct StatusChange {
@@ -671,13 +1157,16 @@ pub async fn change_item_status(
         new_status: Status,
     }
 
-    let action = params.get("action").map(|s| s.as_str());
-    // Wrap the UPDATE and the events lookup in one transaction so the header
-    // reflects the event this mutation produced (or nothing for a no-op
+    // Wrap the UPDATE and the events lookup in one transaction so the reported
+    // event id is the one this mutation produced (or nothing for a no-op
     // status change).
     let mut tx = state.pool.begin().await.map_err(|error| {
         log_database_error("change_item_status_begin_transaction", &error);
-        database_error_response()
+        MutationError::Database
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("change_item_status_set_actor", &error);
+        MutationError::Database
     })?;
     let status_change = match sqlx::query_as!(
         StatusChange,
@@ -705,6 +1194,10 @@ pub async fn change_item_status(
         timer_elapsed_at = CASE
             WHEN $2 IN ('cancel', 'complete') AND status = 'HIGHLIGHTED'::status THEN NULL
             ELSE timer_elapsed_at
+        END,
+        timer_paused_at = CASE
+            WHEN $2 IN ('cancel', 'complete') AND status = 'HIGHLIGHTED'::status THEN NULL
+            ELSE timer_paused_at
         END
         WHERE id = $1
         RETURNING id, old.status as "old_status: _", new.status as "new_status: _"
@@ -725,30 +1218,23 @@ pub async fn change_item_status(
                 drop(tx);
                 let mut conn = state.pool.acquire().await.map_err(|error| {
                     log_database_error("reload_item_after_highlight_conflict_acquire", &error);
-                    database_error_response()
+                    MutationError::Database
                 })?;
                 let original =
                     load_item_with_initials(&mut conn, item_id)
                         .await
                         .map_err(|error| {
                             log_database_error("reload_item_after_highlight_conflict", &error);
-                            database_error_response()
+                            MutationError::Database
                         })?;
                 tracing::debug!(item_id, retro_id, "item highlight conflict");
-                return Ok(Html(
-                    ItemCardTemplate {
-                        item: original,
-                        error_message: Some(
-                            "Only one item can be highlighted at a time".to_string(),
-                        ),
-                    }
-                    .render()
-                    .unwrap(),
-                )
-                .into_response());
+                return Err(MutationError::HighlightConflict(
+                    Box::new(original),
+                    edit_access,
+                ));
             }
             log_database_error("change_item_status", &e);
-            return Err(database_error_response().into());
+            return Err(MutationError::Database);
         }
     };
 
@@ -761,23 +1247,23 @@ pub async fn change_item_status(
     .await
     .map_err(|error| {
         log_database_error("change_item_status_event_id", &error);
-        database_error_response()
+        MutationError::Database
     })?;
 
     tx.commit().await.map_err(|error| {
         log_database_error("change_item_status_commit_transaction", &error);
-        database_error_response()
+        MutationError::Database
     })?;
 
     let mut conn = state.pool.acquire().await.map_err(|error| {
         log_database_error("load_updated_item_acquire", &error);
-        database_error_response()
+        MutationError::Database
     })?;
     let item = load_item_with_initials(&mut conn, status_change.id)
         .await
         .map_err(|error| {
             log_database_error("load_updated_item", &error);
-            database_error_response()
+            MutationError::Database
         })?;
 
     tracing::debug!(
@@ -804,7 +1290,7 @@ pub async fn change_item_status(
     .await
     .map_err(|error| {
         log_database_error("change_item_status_completion_check", &error);
-        database_error_response()
+        MutationError::Database
     })?;
 
     tracing::debug!(
@@ -815,10 +1301,47 @@ pub async fn change_item_status(
         "item status change processed"
     );
 
-    let template = if all_completed.unwrap_or(false) {
+    // A no-op status change emits no event; reporting the previous event's id
+    // would wrongly suppress a future SSE update.
+    let event_id = (status_change.old_status != status_change.new_status)
+        .then_some(event_id)
+        .flatten();
+    Ok(ItemStatusChange {
+        item,
+        event_id,
+        all_completed: all_completed.unwrap_or(false),
+        edit_access,
+    })
+}
+
+pub async fn change_item_status(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, HandlerError> {
+    let action = params.get("action").map(|s| s.as_str());
+    let ItemStatusChange {
+        item,
+        event_id,
+        all_completed,
+        edit_access,
+    } = change_status(&state, &user, item_id, action)
+        .await
+        .map_err(|error| error.into_response(&state))?;
+    // Completing and cancelling can be undone; highlighting is harmless.
+    let undo_message = match item.status {
+        _ if event_id.is_none() => None,
+        Status::Completed => Some("Card completed"),
+        Status::Created => Some("Highlight cancelled"),
+        _ => None,
+    };
+
+    let template = if all_completed {
         ArchiveModalTemplate {
             item,
             error_message: None,
+            edit_access,
         }
         .render()
         .unwrap()
@@ -826,18 +1349,17 @@ pub async fn change_item_status(
         ItemCardTemplate {
             item,
             error_message: None,
+            edit_access,
         }
         .render()
         .unwrap()
     };
 
     let mut response = Html(template).into_response();
-    // A no-op status change emits no event; attaching the previous event's id
-    // would wrongly suppress a future SSE update.
-    let event_id = (status_change.old_status != status_change.new_status)
-        .then_some(event_id)
-        .flatten();
     attach_event_id_header(&mut response, event_id);
+    if let Some(message) = undo_message {
+        attach_undo_header(&mut response, message);
+    }
     Ok(response)
 }
 
@@ -860,15 +1382,16 @@ pub async fn show_item(
             }
         })?;
 
-    match require_retro_access_by_id(&state, &user, item.retro_id).await? {
-        Some(_) => {}
+    let edit_access = match require_retro_access_by_id(&state, &user, item.retro_id).await? {
+        Some(retro) => EditAccess::new(&retro, &user),
         None => return Err(not_found_page(&state).into()),
-    }
+    };
 
     Ok(Html(
         ItemCardTemplate {
             item,
             error_message: None,
+            edit_access,
         }
         .render()
         .unwrap(),
@@ -895,13 +1418,62 @@ pub async fn edit_item(
         })?;
 
     match require_retro_access_by_id(&state, &user, item.retro_id).await? {
-        Some(_) => {}
+        Some(retro) => {
+            require_edit_access(&state, &retro, &user, &item)?;
+        }
         None => return Err(not_found_page(&state).into()),
     }
 
     Ok(Html(ItemEditTemplate { item }.render().unwrap()))
 }
 
+/// `GET /items/{id}/history` — the card's text changes, for the history
+/// popover on boards and archives.
+pub async fn show_item_history(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+) -> Result<Html<String>, HandlerError> {
+    let mut conn = state.pool.acquire().await.map_err(|error| {
+        log_database_error("load_item_for_history_acquire", &error);
+        database_error_response()
+    })?;
+    let item = load_item_with_initials(&mut conn, item_id)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => not_found_page(&state),
+            _ => {
+                log_database_error("load_item_for_history", &error);
+                database_error_response()
+            }
+        })?;
+
+    match require_retro_access_by_id(&state, &user, item.retro_id).await? {
+        Some(_) => {}
+        None => return Err(not_found_page(&state).into()),
+    }
+
+    let revisions = sqlx::query_as!(
+        ItemRevision,
+        r#"SELECT r.id, u.display_name as "editor_name?", r.old_text, r.new_text, r.created_at
+           FROM item_revisions r
+           LEFT JOIN users u ON u.id = r.editor_id
+           WHERE r.item_id = $1
+           ORDER BY r.id"#,
+        item_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|error| {
+        log_database_error("load_item_revisions", &error);
+        database_error_response()
+    })?;
+
+    Ok(Html(
+        ItemHistoryTemplate { item, revisions }.render().unwrap(),
+    ))
+}
+
 pub async fn update_item(
     State(state): State<AppState>,
     user: AuthUser,
@@ -922,10 +1494,10 @@ pub async fn update_item(
             }
         })?;
 
-    match require_retro_access_by_id(&state, &user, item.retro_id).await? {
-        Some(_) => {}
+    let edit_access = match require_retro_access_by_id(&state, &user, item.retro_id).await? {
+        Some(retro) => require_edit_access(&state, &retro, &user, &item)?,
         None => return Err(not_found_page(&state).into()),
-    }
+    };
 
     let text = form.text.trim();
     if text.is_empty() {
@@ -944,14 +1516,23 @@ pub async fn update_item(
         log_database_error("update_item_begin_transaction", &error);
         database_error_response()
     })?;
+    // Recorded as the editor in the card's history.
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("update_item_set_actor", &error);
+        database_error_response()
+    })?;
 
-    sqlx::query!("UPDATE items SET text = $1 WHERE id = $2", text, item_id)
+    let updated = sqlx::query!("UPDATE items SET text = $1 WHERE id = $2", text, item_id)
         .execute(&mut *tx)
         .await
         .map_err(|error| {
             log_database_error("update_item", &error);
             database_error_response()
         })?;
+    // Someone else deleted the card in the meantime.
+    if updated.rows_affected() == 0 {
+        return Err(not_found_page(&state).into());
+    }
 
     let event_id = sqlx::query_scalar!(
         "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
@@ -983,6 +1564,7 @@ pub async fn update_item(
         ItemCardTemplate {
             item,
             error_message: None,
+            edit_access,
         }
         .render()
         .unwrap(),
@@ -995,128 +1577,108 @@ pub async fn update_item(
     Ok(response)
 }
 
-pub async fn like_item(
+/// `POST /items/{id}/move` — move a card to another column. Moving a card
+/// changes it, so the retro's edit policy applies.
+pub async fn move_item(
     State(state): State<AppState>,
     user: AuthUser,
     Path(item_id): Path<i32>,
+    Form(form): Form<MoveItemForm>,
 ) -> Result<Response, HandlerError> {
-    let retro_id = match sqlx::query_scalar!("SELECT retro_id FROM items WHERE id = $1", item_id)
-        .fetch_optional(&state.pool)
+    let mut conn = state.pool.acquire().await.map_err(|error| {
+        log_database_error("load_item_for_move_acquire", &error);
+        database_error_response()
+    })?;
+    let item = load_item_with_initials(&mut conn, item_id)
         .await
-    {
-        Ok(Some(id)) => id,
-        Ok(None) => return Err(not_found_response(&state, "").into()),
-        Err(error) => {
-            log_database_error("load_item_retro_id_for_like", &error);
-            return Err(database_error_response().into());
-        }
-    };
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => not_found_page(&state),
+            _ => {
+                log_database_error("load_item_for_move", &error);
+                database_error_response()
+            }
+        })?;
+    drop(conn);
 
-    match require_retro_access_by_id(&state, &user, retro_id).await? {
-        Some(_) => {}
-        None => {
-            return Err(forbidden(&state, "You do not have access to this retrospective").into())
-        }
+    let edit_access = match require_retro_access_by_id(&state, &user, item.retro_id).await? {
+        Some(retro) => require_edit_access(&state, &retro, &user, &item)?,
+        None => return Err(not_found_page(&state).into()),
+    };
+    if item.archive_id.is_some() {
+        return Err(bad_request(&state, "Archived cards cannot be moved").into());
     }
+    let old_category = item.category;
 
     let mut tx = state.pool.begin().await.map_err(|error| {
-        log_database_error("like_item_begin_transaction", &error);
+        log_database_error("move_item_begin_transaction", &error);
         database_error_response()
     })?;
-
-    let already_liked = sqlx::query_scalar!(
-        r#"SELECT EXISTS(SELECT 1 FROM likes WHERE item_id = $1 AND user_id = $2)"#,
-        item_id,
-        user.user_id
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("move_item_set_actor", &error);
+        database_error_response()
+    })?;
+    // Moving a card to the column it is in (by now) emits no event.
+    let moved = sqlx::query!(
+        "UPDATE items SET category = $1, position = item_position_at_end(retro_id, $1)
+         WHERE id = $2 AND archive_id IS NULL AND category <> $1",
+        form.category as Category,
+        item_id
     )
-    .fetch_one(&mut *tx)
+    .execute(&mut *tx)
     .await
     .map_err(|error| {
-        log_database_error("check_existing_like", &error);
+        log_database_error("move_item", &error);
         database_error_response()
     })?
-    .unwrap_or(false);
-
-    // The lookup runs in the same transaction as the mutation, so the returned
-    // id is the event this mutation's trigger wrote (unless a concurrent
-    // client's event for the same item committed in between, which the client
-    // side dedup tolerates).
-    if already_liked {
-        sqlx::query!(
-            r#"DELETE FROM likes WHERE item_id = $1 AND user_id = $2"#,
-            item_id,
-            user.user_id
-        )
-        .execute(&mut *tx)
-        .await
-        .map_err(|error| {
-            log_database_error("delete_like", &error);
-            database_error_response()
-        })?;
-    } else {
-        sqlx::query!(
-            r#"INSERT INTO likes (item_id, user_id) VALUES ($1, $2)"#,
-            item_id,
-            user.user_id
-        )
-        .execute(&mut *tx)
-        .await
-        .map_err(|error| {
-            log_database_error("insert_like", &error);
-            database_error_response()
-        })?;
-    }
+    .rows_affected()
+        > 0;
 
-    let event_id = if already_liked {
+    let event_id = if moved {
         sqlx::query_scalar!(
             "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
             item_id,
-            EventType::ItemUnliked as EventType
+            EventType::ItemMoved as EventType
         )
         .fetch_optional(&mut *tx)
         .await
         .map_err(|error| {
-            log_database_error("delete_like_event_id", &error);
+            log_database_error("move_item_event_id", &error);
             database_error_response()
         })?
     } else {
-        sqlx::query_scalar!(
-            "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
-            item_id,
-            EventType::ItemLiked as EventType
-        )
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|error| {
-            log_database_error("insert_like_event_id", &error);
-            database_error_response()
-        })?
+        None
     };
 
+    // Deleted in the meantime if it is gone now.
     let item = load_item_with_initials(&mut tx, item_id)
         .await
-        .map_err(|error| {
-            log_database_error("load_item_after_like", &error);
-            database_error_response()
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => not_found_page(&state),
+            _ => {
+                log_database_error("load_moved_item", &error);
+                database_error_response()
+            }
         })?;
 
     tx.commit().await.map_err(|error| {
-        log_database_error("like_item_commit_transaction", &error);
+        log_database_error("move_item_commit_transaction", &error);
         database_error_response()
     })?;
 
     tracing::debug!(
         item_id,
-        retro_id,
+        from = %old_category,
+        to = %item.category,
+        moved,
         user_id = user.user_id,
-        liked = !already_liked,
-        "item like toggled"
+        "item moved"
     );
 
     let mut response = Html(
         ItemCardTemplate {
             item,
             error_message: None,
+            edit_access,
         }
         .render()
         .unwrap(),
@@ -1126,102 +1688,1336 @@ pub async fn like_item(
     Ok(response)
 }
 
-#[derive(Deserialize)]
-pub struct TimerStartForm {
-    pub duration: Option<i32>,
-}
-
-/// Verify the item exists and the user has access to its retro.
-async fn require_timer_access(
-    state: &AppState,
-    user: &AuthUser,
-    item_id: i32,
-) -> Result<(), HandlerError> {
-    let retro_id = match sqlx::query_scalar!("SELECT retro_id FROM items WHERE id = $1", item_id)
-        .fetch_optional(&state.pool)
-        .await
-    {
-        Ok(Some(id)) => id,
-        Ok(None) => return Err(not_found_response(state, "").into()),
-        Err(error) => {
-            log_database_error("load_item_retro_id_for_timer", &error);
-            return Err(database_error_response().into());
-        }
-    };
-
-    match require_retro_access_by_id(state, user, retro_id).await? {
-        Some(_) => Ok(()),
-        None => Err(forbidden(state, "You do not have access to this retrospective").into()),
-    }
-}
-
-/// Start the highlight timer for an item. The deadline is computed by the DB
-/// (`timer_ends_at`), so every client shows the same countdown.
-pub async fn start_item_timer(
+/// `POST /items/{id}/reorder` — place a card right after another card of its
+/// column (`after`), or first when `after` is missing. Facilitators set the
+/// order in which cards are discussed, so only they may reorder.
+pub async fn reorder_item(
     State(state): State<AppState>,
     user: AuthUser,
     Path(item_id): Path<i32>,
-    Form(form): Form<TimerStartForm>,
+    Form(form): Form<ReorderItemForm>,
 ) -> Result<Response, HandlerError> {
-    require_timer_access(&state, &user, item_id).await?;
+    let mut conn = state.pool.acquire().await.map_err(|error| {
+        log_database_error("load_item_for_reorder_acquire", &error);
+        database_error_response()
+    })?;
+    let item = load_item_with_initials(&mut conn, item_id)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => not_found_page(&state),
+            _ => {
+                log_database_error("load_item_for_reorder", &error);
+                database_error_response()
+            }
+        })?;
+    drop(conn);
 
-    let duration = form.duration.unwrap_or(300).clamp(1, 3600);
+    let Some(retro) = require_retro_access_by_id(&state, &user, item.retro_id).await? else {
+        return Err(not_found_page(&state).into());
+    };
+    if !retro.is_facilitator(&user) {
+        return Err(forbidden(&state, "Only facilitators can reorder cards").into());
+    }
+    if item.archive_id.is_some() {
+        return Err(bad_request(&state, "Archived cards cannot be reordered").into());
+    }
+    let edit_access = EditAccess::new(&retro, &user);
 
     let mut tx = state.pool.begin().await.map_err(|error| {
-        log_database_error("start_timer_begin_transaction", &error);
+        log_database_error("reorder_item_begin_transaction", &error);
         database_error_response()
     })?;
-
-    let result = sqlx::query!(
-        r#"UPDATE items
-           SET timer_started_at = NOW(),
-               timer_duration_seconds = $2,
-               timer_elapsed_at = NULL
-           WHERE id = $1 AND status = 'HIGHLIGHTED'::status"#,
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("reorder_item_set_actor", &error);
+        database_error_response()
+    })?;
+    // Concurrent reorders of the same retro must not compute the same key.
+    sqlx::query!("SELECT lock_item_positions($1)", item.retro_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("reorder_item_lock", &error);
+            database_error_response()
+        })?;
+    // The card may have been moved or archived since it was loaded above.
+    let current = sqlx::query!(
+        r#"SELECT category as "category: Category", archive_id
+           FROM items WHERE id = $1 FOR UPDATE"#,
+        item_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("reorder_item_reload", &error);
+        database_error_response()
+    })?
+    .ok_or_else(|| not_found_page(&state))?;
+    if current.archive_id.is_some() {
+        return Err(conflict(&state, "The card was archived in the meantime").into());
+    }
+    let category = current.category;
+
+    let after_position = match form.after {
+        Some(after_id) => {
+            let position = sqlx::query_scalar!(
+                "SELECT position FROM items
+                 WHERE id = $1 AND id <> $2 AND retro_id = $3 AND category = $4
+                   AND archive_id IS NULL",
+                after_id,
+                item_id,
+                item.retro_id,
+                category as Category
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|error| {
+                log_database_error("reorder_item_after_position", &error);
+                database_error_response()
+            })?;
+            match position {
+                Some(position) => Some(position),
+                None => {
+                    return Err(bad_request(
+                        &state,
+                        "Cards can only be placed next to a card of the same column",
+                    )
+                    .into())
+                }
+            }
+        }
+        None => None,
+    };
+    let before_position = sqlx::query_scalar!(
+        "SELECT min(position) FROM items
+         WHERE retro_id = $1 AND category = $2 AND archive_id IS NULL
+           AND id <> $3 AND position > COALESCE($4, '')",
+        item.retro_id,
+        category as Category,
         item_id,
-        duration
+        after_position
     )
-    .execute(&mut *tx)
+    .fetch_one(&mut *tx)
     .await
     .map_err(|error| {
-        log_database_error("start_item_timer", &error);
+        log_database_error("reorder_item_before_position", &error);
         database_error_response()
     })?;
 
-    let event_id = if result.rows_affected() > 0 {
+    // A card that already sits between the two keeps its key (and emits no
+    // event).
+    let reordered = sqlx::query!(
+        "UPDATE items SET position = item_position_between($1, $2)
+         WHERE id = $3
+           AND NOT (position > COALESCE($1, '') AND ($2::text IS NULL OR position < $2))",
+        after_position,
+        before_position,
+        item_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("reorder_item", &error);
+        database_error_response()
+    })?
+    .rows_affected()
+        > 0;
+
+    let event_id = if reordered {
+        sqlx::query_scalar!(
+            "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
+            item_id,
+            EventType::ItemReordered as EventType
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("reorder_item_event_id", &error);
+            database_error_response()
+        })?
+    } else {
+        None
+    };
+
+    let item = load_item_with_initials(&mut tx, item_id)
+        .await
+        .map_err(|error| {
+            log_database_error("load_reordered_item", &error);
+            database_error_response()
+        })?;
+
+    tx.commit().await.map_err(|error| {
+        log_database_error("reorder_item_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::debug!(
+        item_id,
+        after = form.after,
+        position = %item.position,
+        user_id = user.user_id,
+        "item reordered"
+    );
+
+    let mut response = Html(
+        ItemCardTemplate {
+            item,
+            error_message: None,
+            edit_access,
+        }
+        .render()
+        .unwrap(),
+    )
+    .into_response();
+    attach_event_id_header(&mut response, event_id);
+    Ok(response)
+}
+
+/// `POST /items/{id}/comments` — add a note to the card being discussed.
+/// Everyone with access to the retro can comment, whatever the edit policy.
+pub async fn add_item_comment(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+    Form(form): Form<NewItemComment>,
+) -> Result<Response, HandlerError> {
+    let mut conn = state.pool.acquire().await.map_err(|error| {
+        log_database_error("load_item_for_comment_acquire", &error);
+        database_error_response()
+    })?;
+    let item = load_item_with_initials(&mut conn, item_id)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => not_found_page(&state),
+            _ => {
+                log_database_error("load_item_for_comment", &error);
+                database_error_response()
+            }
+        })?;
+    drop(conn);
+
+    let Some(retro) = require_retro_access_by_id(&state, &user, item.retro_id).await? else {
+        return Err(not_found_page(&state).into());
+    };
+    let text = form.text.trim();
+    if text.is_empty() {
+        return Err(bad_request(&state, "Comment text is required").into());
+    }
+    if text.chars().count() > MAX_COMMENT_TEXT_LENGTH {
+        return Err(bad_request(
+            &state,
+            &format!("Comments must be {MAX_COMMENT_TEXT_LENGTH} characters or less"),
+        )
+        .into());
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("add_item_comment_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("add_item_comment_set_actor", &error);
+        database_error_response()
+    })?;
+    // Locked, so the card cannot be completed, archived or deleted before the
+    // comment is in.
+    let current = sqlx::query!(
+        r#"SELECT status = 'HIGHLIGHTED'::status AND archive_id IS NULL as "discussed!"
+           FROM items
+           WHERE id = $1
+           FOR UPDATE"#,
+        item_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("lock_item_for_comment", &error);
+        database_error_response()
+    })?;
+    match current {
+        None => return Err(not_found_page(&state).into()),
+        Some(current) if !current.discussed => {
+            return Err(bad_request(
+                &state,
+                "Comments can only be added while a card is discussed",
+            )
+            .into());
+        }
+        Some(_) => {}
+    }
+    let comment_id = sqlx::query_scalar!(
+        "INSERT INTO item_comments (item_id, created_by, text) VALUES ($1, $2, $3) RETURNING id",
+        item_id,
+        user.user_id,
+        text
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("add_item_comment", &error);
+        database_error_response()
+    })?;
+
+    let event_id = sqlx::query_scalar!(
+        "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
+        item_id,
+        EventType::ItemCommentAdded as EventType
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("add_item_comment_event_id", &error);
+        database_error_response()
+    })?;
+
+    let item = load_item_with_initials(&mut tx, item_id)
+        .await
+        .map_err(|error| {
+            log_database_error("load_commented_item", &error);
+            database_error_response()
+        })?;
+
+    tx.commit().await.map_err(|error| {
+        log_database_error("add_item_comment_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::debug!(item_id, comment_id, user_id = user.user_id, "comment added");
+
+    let mut response = Html(
+        ItemCardTemplate {
+            item,
+            error_message: None,
+            edit_access: EditAccess::new(&retro, &user),
+        }
+        .render()
+        .unwrap(),
+    )
+    .into_response();
+    attach_event_id_header(&mut response, event_id);
+    Ok(response)
+}
+
+/// `DELETE /items/{id}/comments/{comment_id}` — remove a note, for its author
+/// and the facilitators of the retro.
+pub async fn delete_item_comment(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((item_id, comment_id)): Path<(i32, i64)>,
+) -> Result<Response, HandlerError> {
+    let mut conn = state.pool.acquire().await.map_err(|error| {
+        log_database_error("load_item_for_comment_deletion_acquire", &error);
+        database_error_response()
+    })?;
+    let item = load_item_with_initials(&mut conn, item_id)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => not_found_page(&state),
+            _ => {
+                log_database_error("load_item_for_comment_deletion", &error);
+                database_error_response()
+            }
+        })?;
+    drop(conn);
+
+    let Some(retro) = require_retro_access_by_id(&state, &user, item.retro_id).await? else {
+        return Err(not_found_page(&state).into());
+    };
+    let Some(comment) = item
+        .comments
+        .iter()
+        .find(|comment| comment.id == comment_id)
+    else {
+        return Err(not_found_page(&state).into());
+    };
+    let edit_access = EditAccess::new(&retro, &user);
+    if !edit_access.allows_deleting_comment(comment) {
+        return Err(forbidden(
+            &state,
+            "Only the author of a comment and the facilitators can delete it",
+        )
+        .into());
+    }
+    if item.archive_id.is_some() {
+        return Err(bad_request(&state, "Comments of archived cards cannot be deleted").into());
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("delete_item_comment_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("delete_item_comment_set_actor", &error);
+        database_error_response()
+    })?;
+    let deleted = sqlx::query!(
+        "DELETE FROM item_comments WHERE id = $1 AND item_id = $2",
+        comment_id,
+        item_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("delete_item_comment", &error);
+        database_error_response()
+    })?
+    .rows_affected();
+    // Someone else deleted it in the meantime, so there is no event of ours.
+    if deleted == 0 {
+        return Err(not_found_page(&state).into());
+    }
+
+    let event_id = sqlx::query_scalar!(
+        "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
+        item_id,
+        EventType::ItemCommentDeleted as EventType
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("delete_item_comment_event_id", &error);
+        database_error_response()
+    })?;
+
+    let item = load_item_with_initials(&mut tx, item_id)
+        .await
+        .map_err(|error| {
+            log_database_error("load_uncommented_item", &error);
+            database_error_response()
+        })?;
+
+    tx.commit().await.map_err(|error| {
+        log_database_error("delete_item_comment_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::debug!(
+        item_id,
+        comment_id,
+        user_id = user.user_id,
+        "comment deleted"
+    );
+
+    let mut response = Html(
+        ItemCardTemplate {
+            item,
+            error_message: None,
+            edit_access,
+        }
+        .render()
+        .unwrap(),
+    )
+    .into_response();
+    attach_event_id_header(&mut response, event_id);
+    Ok(response)
+}
+
+/// `POST /items/{id}/notes` — save the discussion notes of the highlighted
+/// card. Everyone on the board writes the same notes and the last save wins;
+/// the response carries the new `notes_revision`.
+pub async fn update_item_notes(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+    Form(form): Form<ItemNotesForm>,
+) -> Result<Response, HandlerError> {
+    let mut conn = state.pool.acquire().await.map_err(|error| {
+        log_database_error("load_item_for_notes_acquire", &error);
+        database_error_response()
+    })?;
+    let item = load_item_with_initials(&mut conn, item_id)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => not_found_page(&state),
+            _ => {
+                log_database_error("load_item_for_notes", &error);
+                database_error_response()
+            }
+        })?;
+    drop(conn);
+
+    if require_retro_access_by_id(&state, &user, item.retro_id)
+        .await?
+        .is_none()
+    {
+        return Err(not_found_page(&state).into());
+    }
+    if form.notes.chars().count() > MAX_NOTES_LENGTH {
+        return Err(bad_request(
+            &state,
+            &format!("Notes must be {MAX_NOTES_LENGTH} characters or less"),
+        )
+        .into());
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("update_item_notes_begin_transaction", &error);
+        database_error_response()
+    })?;
+    // Recorded as the editor in the ITEM_NOTES_UPDATED event.
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("update_item_notes_set_actor", &error);
+        database_error_response()
+    })?;
+    let current = sqlx::query!(
+        "SELECT notes, notes_revision FROM items
+         WHERE id = $1 AND status = 'HIGHLIGHTED'::status AND archive_id IS NULL
+         FOR UPDATE",
+        item_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("lock_item_notes", &error);
+        database_error_response()
+    })?;
+    let Some(current) = current else {
+        return Err(
+            bad_request(&state, "Notes can only be taken while a card is discussed").into(),
+        );
+    };
+
+    let (notes_revision, event_id) = if current.notes == form.notes {
+        (current.notes_revision, None)
+    } else {
+        let notes_revision = sqlx::query_scalar!(
+            "UPDATE items SET notes = $1, notes_revision = notes_revision + 1
+             WHERE id = $2
+             RETURNING notes_revision",
+            form.notes,
+            item_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("update_item_notes", &error);
+            database_error_response()
+        })?;
+        let event_id = sqlx::query_scalar!(
+            "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
+            item_id,
+            EventType::ItemNotesUpdated as EventType
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("update_item_notes_event_id", &error);
+            database_error_response()
+        })?;
+        (notes_revision, event_id)
+    };
+
+    tx.commit().await.map_err(|error| {
+        log_database_error("update_item_notes_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::debug!(
+        item_id,
+        notes_revision,
+        user_id = user.user_id,
+        "item notes saved"
+    );
+
+    let mut response = Json(serde_json::json!({
+        "item_id": item_id,
+        "notes_revision": notes_revision,
+    }))
+    .into_response();
+    attach_event_id_header(&mut response, event_id);
+    Ok(response)
+}
+
+/// `DELETE /items/{id}` — remove a card from the board, for its author and the
+/// facilitators of the retro. Its likes and edit history go with it, and so
+/// does a running timer; other clients remove the card on `ITEM_DELETED`.
+pub async fn delete_item(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+) -> Result<Response, HandlerError> {
+    let mut conn = state.pool.acquire().await.map_err(|error| {
+        log_database_error("load_item_for_delete_acquire", &error);
+        database_error_response()
+    })?;
+    let item = load_item_with_initials(&mut conn, item_id)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => not_found_page(&state),
+            _ => {
+                log_database_error("load_item_for_delete", &error);
+                database_error_response()
+            }
+        })?;
+    drop(conn);
+
+    let retro = require_retro_access_by_id(&state, &user, item.retro_id)
+        .await?
+        .ok_or_else(|| not_found_page(&state))?;
+    if item.archive_id.is_some() {
+        return Err(bad_request(&state, "Archived cards cannot be deleted").into());
+    }
+    if !EditAccess::new(&retro, &user).allows_deleting(&item) {
+        return Err(forbidden(
+            &state,
+            "Only the author of a card or a facilitator can delete it",
+        )
+        .into());
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("delete_item_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("delete_item_set_actor", &error);
+        database_error_response()
+    })?;
+    let deleted = sqlx::query!(
+        "DELETE FROM items WHERE id = $1 AND archive_id IS NULL",
+        item_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("delete_item", &error);
+        database_error_response()
+    })?;
+    // Archived (or deleted) concurrently.
+    if deleted.rows_affected() == 0 {
+        return Err(not_found_page(&state).into());
+    }
+
+    let event_id = sqlx::query_scalar!(
+        "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
+        item_id,
+        EventType::ItemDeleted as EventType
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("delete_item_event_id", &error);
+        database_error_response()
+    })?;
+
+    tx.commit().await.map_err(|error| {
+        log_database_error("delete_item_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::info!(
+        item_id,
+        retro_id = item.retro_id,
+        user_id = user.user_id,
+        "item deleted"
+    );
+
+    // An empty body: the board swaps the card out.
+    let mut response = StatusCode::OK.into_response();
+    attach_event_id_header(&mut response, event_id);
+    Ok(response)
+}
+
+/// Like a card, or take the like back if the user already liked it.
+pub(crate) async fn toggle_like(
+    state: &AppState,
+    user: &AuthUser,
+    item_id: i32,
+) -> Result<ItemMutation, MutationError> {
+    let retro = item_retro_for_mutation(state, user, item_id).await?;
+    let retro_id = retro.id;
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("like_item_begin_transaction", &error);
+        MutationError::Database
+    })?;
+
+    let already_liked = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM likes WHERE item_id = $1 AND user_id = $2)"#,
+        item_id,
+        user.user_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("check_existing_like", &error);
+        MutationError::Database
+    })?
+    .unwrap_or(false);
+
+    // The lookup runs in the same transaction as the mutation, so the returned
+    // id is the event this mutation's trigger wrote (unless a concurrent
+    // client's event for the same item committed in between, which the client
+    // side dedup tolerates).
+    if already_liked {
+        sqlx::query!(
+            r#"DELETE FROM likes WHERE item_id = $1 AND user_id = $2"#,
+            item_id,
+            user.user_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("delete_like", &error);
+            MutationError::Database
+        })?;
+    } else {
+        sqlx::query!(
+            r#"INSERT INTO likes (item_id, user_id) VALUES ($1, $2)"#,
+            item_id,
+            user.user_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("insert_like", &error);
+            MutationError::Database
+        })?;
+    }
+
+    let event_id = if already_liked {
+        sqlx::query_scalar!(
+            "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
+            item_id,
+            EventType::ItemUnliked as EventType
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("delete_like_event_id", &error);
+            MutationError::Database
+        })?
+    } else {
+        sqlx::query_scalar!(
+            "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
+            item_id,
+            EventType::ItemLiked as EventType
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("insert_like_event_id", &error);
+            MutationError::Database
+        })?
+    };
+
+    let item = load_item_with_initials(&mut tx, item_id)
+        .await
+        .map_err(|error| {
+            log_database_error("load_item_after_like", &error);
+            MutationError::Database
+        })?;
+
+    tx.commit().await.map_err(|error| {
+        log_database_error("like_item_commit_transaction", &error);
+        MutationError::Database
+    })?;
+
+    tracing::debug!(
+        item_id,
+        retro_id,
+        user_id = user.user_id,
+        liked = !already_liked,
+        "item like toggled"
+    );
+
+    Ok(ItemMutation {
+        item,
+        event_id,
+        edit_access: EditAccess::new(&retro, user),
+        initials_changed: false,
+    })
+}
+
+pub async fn like_item(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+) -> Result<Response, HandlerError> {
+    let ItemMutation {
+        item,
+        event_id,
+        edit_access,
+        ..
+    } = toggle_like(&state, &user, item_id)
+        .await
+        .map_err(|error| error.into_response(&state))?;
+    let mut response = Html(
+        ItemCardTemplate {
+            item,
+            error_message: None,
+            edit_access,
+        }
+        .render()
+        .unwrap(),
+    )
+    .into_response();
+    attach_event_id_header(&mut response, event_id);
+    Ok(response)
+}
+
+/// Toggle one of `user`'s reactions to a card. Shared by the HTTP handler
+/// and the WebSocket endpoint, like `toggle_like`.
+pub(crate) async fn toggle_reaction(
+    state: &AppState,
+    user: &AuthUser,
+    item_id: i32,
+    reaction: Reaction,
+) -> Result<ItemMutation, MutationError> {
+    let retro = item_retro_for_mutation(state, user, item_id).await?;
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("react_to_item_begin_transaction", &error);
+        MutationError::Database
+    })?;
+
+    let removed = sqlx::query!(
+        "DELETE FROM reactions WHERE item_id = $1 AND user_id = $2 AND reaction = $3",
+        item_id,
+        user.user_id,
+        reaction as Reaction
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("delete_reaction", &error);
+        MutationError::Database
+    })?
+    .rows_affected()
+        > 0;
+    if !removed {
+        sqlx::query!(
+            "INSERT INTO reactions (item_id, user_id, reaction) VALUES ($1, $2, $3)
+             ON CONFLICT DO NOTHING",
+            item_id,
+            user.user_id,
+            reaction as Reaction
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("insert_reaction", &error);
+            MutationError::Database
+        })?;
+    }
+
+    let event_type = if removed {
+        EventType::ItemUnreacted
+    } else {
+        EventType::ItemReacted
+    };
+    let event_id = sqlx::query_scalar!(
+        "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
+        item_id,
+        event_type as EventType
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("reaction_event_id", &error);
+        MutationError::Database
+    })?;
+
+    let item = load_item_with_initials(&mut tx, item_id)
+        .await
+        .map_err(|error| {
+            log_database_error("load_item_after_reaction", &error);
+            MutationError::Database
+        })?;
+
+    tx.commit().await.map_err(|error| {
+        log_database_error("react_to_item_commit_transaction", &error);
+        MutationError::Database
+    })?;
+
+    tracing::debug!(
+        item_id,
+        retro_id = retro.id,
+        user_id = user.user_id,
+        %reaction,
+        given = !removed,
+        "item reaction toggled"
+    );
+
+    Ok(ItemMutation {
+        item,
+        event_id,
+        edit_access: EditAccess::new(&retro, user),
+        initials_changed: false,
+    })
+}
+
+/// `POST /items/{id}/reactions` — toggle one of the user's reactions.
+pub async fn react_to_item(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+    Form(form): Form<ReactionForm>,
+) -> Result<Response, HandlerError> {
+    let ItemMutation {
+        item,
+        event_id,
+        edit_access,
+        ..
+    } = toggle_reaction(&state, &user, item_id, form.reaction)
+        .await
+        .map_err(|error| error.into_response(&state))?;
+    let mut response = Html(
+        ItemCardTemplate {
+            item,
+            error_message: None,
+            edit_access,
+        }
+        .render()
+        .unwrap(),
+    )
+    .into_response();
+    attach_event_id_header(&mut response, event_id);
+    Ok(response)
+}
+
+#[derive(Deserialize)]
+pub struct ReactionForm {
+    reaction: Reaction,
+}
+
+#[derive(Deserialize)]
+pub struct TimerStartForm {
+    pub duration: Option<i32>,
+}
+
+/// Verify the item exists and the user has access to its retro; returns the
+/// retro (for its timer settings) and which of its cards the user may edit.
+async fn require_timer_access(
+    state: &AppState,
+    user: &AuthUser,
+    item_id: i32,
+) -> Result<(Retrospective, EditAccess), HandlerError> {
+    let retro_id = match sqlx::query_scalar!("SELECT retro_id FROM items WHERE id = $1", item_id)
+        .fetch_optional(&state.pool)
+        .await
+    {
+        Ok(Some(id)) => id,
+        Ok(None) => return Err(not_found_response(state, "").into()),
+        Err(error) => {
+            log_database_error("load_item_retro_id_for_timer", &error);
+            return Err(database_error_response().into());
+        }
+    };
+
+    match require_retro_access_by_id(state, user, retro_id).await? {
+        Some(retro) => {
+            let edit_access = EditAccess::new(&retro, user);
+            Ok((retro, edit_access))
+        }
+        None => Err(forbidden(state, ACCESS_DENIED_MESSAGE).into()),
+    }
+}
+
+/// Common tail of the timer mutations: look up the event the change produced
+/// (none if nothing changed), reload the card, commit and render it.
+async fn finish_timer_mutation(
+    mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
+    item_id: i32,
+    changed: bool,
+    event_type: EventType,
+    edit_access: EditAccess,
+) -> Result<Response, HandlerError> {
+    let event_id = if changed {
+        sqlx::query_scalar!(
+            "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
+            item_id,
+            event_type as EventType
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|error| {
+            log_database_error("timer_event_id", &error);
+            database_error_response()
+        })?
+    } else {
+        None
+    };
+
+    let item = load_item_with_initials(&mut tx, item_id)
+        .await
+        .map_err(|error| {
+            log_database_error("load_item_after_timer_change", &error);
+            database_error_response()
+        })?;
+
+    tx.commit().await.map_err(|error| {
+        log_database_error("timer_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    let mut response = Html(
+        ItemCardTemplate {
+            item,
+            error_message: None,
+            edit_access,
+        }
+        .render()
+        .unwrap(),
+    )
+    .into_response();
+    attach_event_id_header(&mut response, event_id);
+    Ok(response)
+}
+
+/// Start the highlight timer for an item, by default for the retro's
+/// discussion length. The deadline is computed by the DB (`timer_ends_at`),
+/// so every client shows the same countdown.
+pub async fn start_item_timer(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+    Form(form): Form<TimerStartForm>,
+) -> Result<Response, HandlerError> {
+    let (retro, edit_access) = require_timer_access(&state, &user, item_id).await?;
+
+    let mut duration = form
+        .duration
+        .unwrap_or(retro.timer_default_seconds)
+        .clamp(1, 3600);
+    if let Some(max) = retro.timer_max_seconds {
+        duration = duration.min(max);
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("start_timer_begin_transaction", &error);
+        database_error_response()
+    })?;
+
+    let result = sqlx::query!(
+        r#"UPDATE items
+           SET timer_started_at = NOW(),
+               timer_duration_seconds = $2,
+               timer_elapsed_at = NULL,
+               timer_paused_at = NULL
+           WHERE id = $1 AND status = 'HIGHLIGHTED'::status"#,
+        item_id,
+        duration
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("start_item_timer", &error);
+        database_error_response()
+    })?;
+
+    tracing::debug!(item_id, duration, user_id = user.user_id, "timer started");
+
+    finish_timer_mutation(
+        tx,
+        item_id,
+        result.rows_affected() > 0,
+        EventType::TimerStarted,
+        edit_access,
+    )
+    .await
+}
+
+/// Extend a running timer by the retro's extension step, up to its maximum
+/// length (restarting it if it already elapsed).
+pub async fn extend_item_timer(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+) -> Result<Response, HandlerError> {
+    let (retro, edit_access) = require_timer_access(&state, &user, item_id).await?;
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("extend_timer_begin_transaction", &error);
+        database_error_response()
+    })?;
+
+    let result = sqlx::query!(
+        r#"UPDATE items
+           SET timer_duration_seconds = LEAST(timer_duration_seconds + $2, COALESCE($3, 2147483647)),
+               timer_elapsed_at = NULL
+           WHERE id = $1 AND timer_started_at IS NOT NULL
+             AND timer_duration_seconds < COALESCE($3, 2147483647)"#,
+        item_id,
+        retro.timer_extend_seconds,
+        retro.timer_max_seconds
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("extend_item_timer", &error);
+        database_error_response()
+    })?;
+
+    if result.rows_affected() == 0 {
+        if let Some(max) = retro.timer_max_seconds {
+            let at_max = sqlx::query_scalar!(
+                r#"SELECT EXISTS(
+                       SELECT 1 FROM items
+                       WHERE id = $1 AND timer_started_at IS NOT NULL AND timer_duration_seconds >= $2
+                   ) as "at_max!""#,
+                item_id,
+                max
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|error| {
+                log_database_error("extend_timer_at_max", &error);
+                database_error_response()
+            })?;
+            if at_max {
+                return Err(bad_request(
+                    &state,
+                    &format!(
+                        "The timer is already at its maximum of {}",
+                        format_minutes(max)
+                    ),
+                )
+                .into());
+            }
+        }
+    }
+
+    tracing::debug!(item_id, user_id = user.user_id, "timer extended");
+
+    finish_timer_mutation(
+        tx,
+        item_id,
+        result.rows_affected() > 0,
+        EventType::TimerExtended,
+        edit_access,
+    )
+    .await
+}
+
+/// Pause a running timer: the time left is frozen until it is resumed.
+/// Facilitators only.
+pub async fn pause_item_timer(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+) -> Result<Response, HandlerError> {
+    let (_, edit_access) = require_timer_access(&state, &user, item_id).await?;
+    if !edit_access.is_facilitator {
+        return Err(forbidden(&state, "Only facilitators can pause the timer").into());
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("pause_timer_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("pause_timer_set_actor", &error);
+        database_error_response()
+    })?;
+
+    let result = sqlx::query!(
+        r#"UPDATE items
+           SET timer_paused_at = NOW()
+           WHERE id = $1 AND status = 'HIGHLIGHTED'::status
+             AND timer_started_at IS NOT NULL
+             AND timer_paused_at IS NULL
+             AND timer_elapsed_at IS NULL
+             AND timer_ends_at > NOW()"#,
+        item_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("pause_item_timer", &error);
+        database_error_response()
+    })?;
+
+    tracing::debug!(item_id, user_id = user.user_id, "timer paused");
+
+    finish_timer_mutation(
+        tx,
+        item_id,
+        result.rows_affected() > 0,
+        EventType::TimerPaused,
+        edit_access,
+    )
+    .await
+}
+
+/// Resume a paused timer with the time it had left. Facilitators only.
+pub async fn resume_item_timer(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+) -> Result<Response, HandlerError> {
+    let (_, edit_access) = require_timer_access(&state, &user, item_id).await?;
+    if !edit_access.is_facilitator {
+        return Err(forbidden(&state, "Only facilitators can resume the timer").into());
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("resume_timer_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("resume_timer_set_actor", &error);
+        database_error_response()
+    })?;
+
+    // Moving the start forward by the length of the pause keeps the total
+    // duration and puts the deadline the time left from now.
+    let result = sqlx::query!(
+        r#"UPDATE items
+           SET timer_started_at = timer_started_at + (NOW() - timer_paused_at),
+               timer_paused_at = NULL
+           WHERE id = $1 AND status = 'HIGHLIGHTED'::status AND timer_paused_at IS NOT NULL"#,
+        item_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("resume_item_timer", &error);
+        database_error_response()
+    })?;
+
+    tracing::debug!(item_id, user_id = user.user_id, "timer resumed");
+
+    finish_timer_mutation(
+        tx,
+        item_id,
+        result.rows_affected() > 0,
+        EventType::TimerResumed,
+        edit_access,
+    )
+    .await
+}
+
+/// "5 min", or "90 s" for lengths that are not whole minutes.
+fn format_minutes(seconds: i32) -> String {
+    if seconds % 60 == 0 {
+        format!("{} min", seconds / 60)
+    } else {
+        format!("{seconds} s")
+    }
+}
+
+async fn load_retro_timers(
+    executor: impl sqlx::PgExecutor<'_>,
+    retro_id: i32,
+) -> Result<RetroTimers, sqlx::Error> {
+    let rows = sqlx::query_as!(
+        RetroTimer,
+        r#"SELECT kind as "kind: _", label, started_at, duration_seconds,
+                  ends_at as "ends_at: _", elapsed_at
+           FROM retro_timers
+           WHERE retro_id = $1"#,
+        retro_id
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(RetroTimers::from_rows(rows))
+}
+
+/// The session and phase timers of a board; fetched by other clients when
+/// the timers change.
+pub async fn show_retro_timers(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(slug): Path<String>,
+) -> Result<Response, HandlerError> {
+    let retro = match require_retro_access(&state, &user, &slug).await? {
+        Some(retro) => retro,
+        None => return Err(not_found_response(&state, &slug).into()),
+    };
+    let timers = load_retro_timers(&state.pool, retro.id)
+        .await
+        .map_err(|error| {
+            log_database_error("show_retro_timers", &error);
+            database_error_response()
+        })?;
+    let edit_access = EditAccess::new(&retro, &user);
+    Ok(Html(
+        RetroTimersTemplate {
+            retro,
+            timers,
+            edit_access,
+        }
+        .render()
+        .unwrap(),
+    )
+    .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct RetroTimerStartForm {
+    pub minutes: i32,
+    /// Name of the phase; ignored for the session timer.
+    pub label: Option<String>,
+}
+
+/// Verify the user facilitates the retro and the timer kind in the URL exists.
+async fn require_retro_timer_access(
+    state: &AppState,
+    user: &AuthUser,
+    slug: &str,
+    kind: &str,
+) -> Result<(Retrospective, RetroTimerKind), HandlerError> {
+    let retro = match require_retro_access(state, user, slug).await? {
+        Some(retro) => retro,
+        None => return Err(not_found_response(state, slug).into()),
+    };
+    let Some(kind) = RetroTimerKind::from_path(kind) else {
+        return Err(not_found_response(state, slug).into());
+    };
+    if !retro.is_facilitator(user) {
+        return Err(forbidden(state, "Only facilitators can run the session timers").into());
+    }
+    Ok((retro, kind))
+}
+
+/// Common tail of the retro timer mutations: look up the event the change
+/// produced (none if nothing changed), reload the timers, commit and render
+/// them.
+async fn finish_retro_timer_mutation(
+    mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
+    retro: Retrospective,
+    user: &AuthUser,
+    kind: RetroTimerKind,
+    changed: bool,
+    event_type: EventType,
+) -> Result<Response, HandlerError> {
+    let event_id = if changed {
         sqlx::query_scalar!(
-            "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
-            item_id,
-            EventType::TimerStarted as EventType
+            "SELECT id FROM events
+             WHERE retro_id = $1 AND event_type = $2 AND payload->>'kind' = $3
+             ORDER BY id DESC LIMIT 1",
+            retro.id,
+            event_type as EventType,
+            kind.to_string()
         )
         .fetch_optional(&mut *tx)
         .await
         .map_err(|error| {
-            log_database_error("start_timer_event_id", &error);
+            log_database_error("retro_timer_event_id", &error);
             database_error_response()
         })?
     } else {
         None
     };
 
-    let item = load_item_with_initials(&mut tx, item_id)
+    let timers = load_retro_timers(&mut *tx, retro.id)
         .await
         .map_err(|error| {
-            log_database_error("load_item_after_timer_start", &error);
+            log_database_error("load_retro_timers_after_change", &error);
             database_error_response()
         })?;
 
     tx.commit().await.map_err(|error| {
-        log_database_error("start_timer_commit_transaction", &error);
+        log_database_error("retro_timer_commit_transaction", &error);
         database_error_response()
     })?;
 
-    tracing::debug!(item_id, duration, user_id = user.user_id, "timer started");
-
+    let edit_access = EditAccess::new(&retro, user);
     let mut response = Html(
-        ItemCardTemplate {
-            item,
-            error_message: None,
+        RetroTimersTemplate {
+            retro,
+            timers,
+            edit_access,
         }
         .render()
         .unwrap(),
@@ -1231,106 +3027,177 @@ pub async fn start_item_timer(
     Ok(response)
 }
 
-/// Extend a running timer by two minutes (restarting it if it already
-/// elapsed).
-pub async fn extend_item_timer(
+/// Start (or restart) the session timer or the timer of an agenda phase.
+/// Facilitators only.
+pub async fn start_retro_timer(
     State(state): State<AppState>,
     user: AuthUser,
-    Path(item_id): Path<i32>,
+    Path((slug, kind)): Path<(String, String)>,
+    Form(form): Form<RetroTimerStartForm>,
 ) -> Result<Response, HandlerError> {
-    require_timer_access(&state, &user, item_id).await?;
+    let (retro, kind) = require_retro_timer_access(&state, &user, &slug, &kind).await?;
+
+    let max_minutes = match kind {
+        RetroTimerKind::Session => MAX_TIMER_TOTAL_MINUTES,
+        RetroTimerKind::Phase => MAX_TIMER_MINUTES,
+    };
+    if !(1..=max_minutes).contains(&form.minutes) {
+        return Err(bad_request(
+            &state,
+            &format!("The timer must run between 1 and {max_minutes} minutes"),
+        )
+        .into());
+    }
+    let label = match kind {
+        RetroTimerKind::Session => None,
+        RetroTimerKind::Phase => form
+            .label
+            .as_deref()
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map(str::to_string),
+    };
+    if label
+        .as_ref()
+        .is_some_and(|label| label.chars().count() > MAX_PHASE_LABEL_LENGTH)
+    {
+        return Err(bad_request(
+            &state,
+            &format!("The name of a phase can be at most {MAX_PHASE_LABEL_LENGTH} characters long"),
+        )
+        .into());
+    }
 
     let mut tx = state.pool.begin().await.map_err(|error| {
-        log_database_error("extend_timer_begin_transaction", &error);
+        log_database_error("start_retro_timer_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("start_retro_timer_set_actor", &error);
         database_error_response()
     })?;
 
-    let result = sqlx::query!(
-        r#"UPDATE items
-           SET timer_duration_seconds = timer_duration_seconds + 120,
-               timer_elapsed_at = NULL
-           WHERE id = $1 AND timer_started_at IS NOT NULL"#,
-        item_id
+    sqlx::query!(
+        r#"INSERT INTO retro_timers (retro_id, kind, label, duration_seconds)
+           VALUES ($1, $2, $3, $4)
+           ON CONFLICT (retro_id, kind) DO UPDATE
+           SET label = EXCLUDED.label,
+               started_at = EXCLUDED.started_at,
+               duration_seconds = EXCLUDED.duration_seconds,
+               elapsed_at = NULL"#,
+        retro.id,
+        kind as RetroTimerKind,
+        label,
+        form.minutes * 60
     )
     .execute(&mut *tx)
     .await
     .map_err(|error| {
-        log_database_error("extend_item_timer", &error);
+        log_database_error("start_retro_timer", &error);
         database_error_response()
     })?;
 
-    let event_id = if result.rows_affected() > 0 {
-        sqlx::query_scalar!(
-            "SELECT id FROM events WHERE item_id = $1 AND event_type = $2 ORDER BY id DESC LIMIT 1",
-            item_id,
-            EventType::TimerExtended as EventType
-        )
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|error| {
-            log_database_error("extend_timer_event_id", &error);
-            database_error_response()
-        })?
-    } else {
-        None
-    };
+    tracing::debug!(
+        retro_id = retro.id,
+        %kind,
+        minutes = form.minutes,
+        user_id = user.user_id,
+        "retro timer started"
+    );
 
-    let item = load_item_with_initials(&mut tx, item_id)
-        .await
-        .map_err(|error| {
-            log_database_error("load_item_after_timer_extend", &error);
-            database_error_response()
-        })?;
+    finish_retro_timer_mutation(tx, retro, &user, kind, true, EventType::RetroTimerStarted).await
+}
 
-    tx.commit().await.map_err(|error| {
-        log_database_error("extend_timer_commit_transaction", &error);
+/// Extend a session or phase timer by the retro's extension step. An elapsed
+/// timer gets the step from now on.
+pub async fn extend_retro_timer(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((slug, kind)): Path<(String, String)>,
+) -> Result<Response, HandlerError> {
+    let (retro, kind) = require_retro_timer_access(&state, &user, &slug, &kind).await?;
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("extend_retro_timer_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("extend_retro_timer_set_actor", &error);
         database_error_response()
     })?;
 
-    tracing::debug!(item_id, user_id = user.user_id, "timer extended");
+    let result = sqlx::query!(
+        r#"UPDATE retro_timers
+           SET duration_seconds = GREATEST(
+                   duration_seconds,
+                   CEIL(EXTRACT(EPOCH FROM (NOW() - started_at)))::integer
+               ) + $3,
+               elapsed_at = NULL
+           WHERE retro_id = $1 AND kind = $2"#,
+        retro.id,
+        kind as RetroTimerKind,
+        retro.timer_extend_seconds
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("extend_retro_timer", &error);
+        database_error_response()
+    })?;
 
-    let mut response = Html(
-        ItemCardTemplate {
-            item,
-            error_message: None,
-        }
-        .render()
-        .unwrap(),
+    tracing::debug!(retro_id = retro.id, %kind, user_id = user.user_id, "retro timer extended");
+
+    finish_retro_timer_mutation(
+        tx,
+        retro,
+        &user,
+        kind,
+        result.rows_affected() > 0,
+        EventType::RetroTimerExtended,
     )
-    .into_response();
-    attach_event_id_header(&mut response, event_id);
-    Ok(response)
+    .await
 }
 
-/// Background task: marks highlight timers as elapsed once their deadline
-/// passes, so all clients see 0:00 and the +2 min button at the same time.
-/// Runs every second; the idempotent UPDATE makes concurrent sweeps (e.g.
-/// multiple app instances) safe.
-pub async fn timer_sweep_loop(pool: PgPool) {
-    let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
-    loop {
-        interval.tick().await;
-        let result = sqlx::query!(
-            r#"UPDATE items
-               SET timer_elapsed_at = NOW()
-               WHERE status = 'HIGHLIGHTED'::status
-                 AND timer_ends_at <= NOW()
-                 AND timer_elapsed_at IS NULL"#
-        )
-        .execute(&pool)
-        .await;
-        match result {
-            Ok(result) => {
-                if result.rows_affected() > 0 {
-                    tracing::debug!(
-                        count = result.rows_affected(),
-                        "highlight timers marked elapsed"
-                    );
-                }
-            }
-            Err(error) => log_database_error("timer_sweep", &error),
-        }
-    }
+/// Stop a session or phase timer. Facilitators only.
+pub async fn stop_retro_timer(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((slug, kind)): Path<(String, String)>,
+) -> Result<Response, HandlerError> {
+    let (retro, kind) = require_retro_timer_access(&state, &user, &slug, &kind).await?;
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("stop_retro_timer_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("stop_retro_timer_set_actor", &error);
+        database_error_response()
+    })?;
+
+    let result = sqlx::query!(
+        "DELETE FROM retro_timers WHERE retro_id = $1 AND kind = $2",
+        retro.id,
+        kind as RetroTimerKind
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("stop_retro_timer", &error);
+        database_error_response()
+    })?;
+
+    tracing::debug!(retro_id = retro.id, %kind, user_id = user.user_id, "retro timer stopped");
+
+    finish_retro_timer_mutation(
+        tx,
+        retro,
+        &user,
+        kind,
+        result.rows_affected() > 0,
+        EventType::RetroTimerStopped,
+    )
+    .await
 }
 
 pub async fn add_action_item(
@@ -1356,23 +3223,86 @@ pub async fn add_action_item(
         .into());
     }
 
-    let action_item = sqlx::query_as!(
+    let action_item = insert_action_item(&state.pool, retro_id, text, None)
+        .await
+        .map_err(|error| {
+            log_database_error("add_action_item", &error);
+            database_error_response()
+        })?;
+
+    Ok(Html(ActionItemTemplate { action_item }.render().unwrap()))
+}
+
+/// Create an action item from a card: its text is the card's, and it links
+/// back to the card on the board and in the archive.
+pub async fn add_action_item_from_item(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(item_id): Path<i32>,
+) -> Result<Html<String>, HandlerError> {
+    let mut conn = state.pool.acquire().await.map_err(|error| {
+        log_database_error("load_item_for_action_item_acquire", &error);
+        database_error_response()
+    })?;
+    let item = load_item_with_initials(&mut conn, item_id)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => not_found_page(&state),
+            _ => {
+                log_database_error("load_item_for_action_item", &error);
+                database_error_response()
+            }
+        })?;
+    drop(conn);
+
+    if require_retro_access_by_id(&state, &user, item.retro_id)
+        .await?
+        .is_none()
+    {
+        return Err(not_found_page(&state).into());
+    }
+    if item.archive_id.is_some() {
+        return Err(
+            bad_request(&state, "Archived cards cannot be turned into action items").into(),
+        );
+    }
+
+    let action_item = insert_action_item(&state.pool, item.retro_id, &item.text, Some(item.id))
+        .await
+        .map_err(|error| {
+            log_database_error("add_action_item_from_item", &error);
+            database_error_response()
+        })?;
+    tracing::debug!(
+        item_id,
+        action_item_id = action_item.id,
+        user_id = user.user_id,
+        "action item created from card"
+    );
+
+    Ok(Html(ActionItemTemplate { action_item }.render().unwrap()))
+}
+
+async fn insert_action_item(
+    pool: &PgPool,
+    retro_id: i32,
+    text: &str,
+    source_item_id: Option<i32>,
+) -> Result<ActionItem, sqlx::Error> {
+    sqlx::query_as!(
         ActionItem,
-        r#"INSERT INTO action_items (retro_id, text)
-           VALUES ($1, $2)
+        r#"INSERT INTO action_items (retro_id, text, source_item_id)
+           VALUES ($1, $2, $3)
            RETURNING id as "id!", retro_id as "retro_id!", text as "text!", created_at as "created_at!",
-                     completed_at as "completed_at: _", archive_id as "archive_id: _", archived_at as "archived_at: _""#,
+                     completed_at as "completed_at: _", archive_id as "archive_id: _", archived_at as "archived_at: _",
+                     source_item_id as "source_item_id: _",
+                     (SELECT text FROM items WHERE items.id = action_items.source_item_id) as "source_item_text: _""#,
         retro_id,
-        text
+        text,
+        source_item_id
     )
-    .fetch_one(&state.pool)
+    .fetch_one(pool)
     .await
-    .map_err(|error| {
-        log_database_error("add_action_item", &error);
-        database_error_response()
-    })?;
-
-    Ok(Html(ActionItemTemplate { action_item }.render().unwrap()))
 }
 
 pub async fn show_action_item(
@@ -1416,7 +3346,7 @@ pub async fn update_action_item(
     user: AuthUser,
     Path(action_item_id): Path<i32>,
     Form(form): Form<NewActionItem>,
-) -> Result<Html<String>, HandlerError> {
+) -> Result<Response, HandlerError> {
     let existing = load_action_item(&state.pool, action_item_id)
         .await
         .map_err(|error| match error {
@@ -1438,21 +3368,37 @@ pub async fn update_action_item(
         )
         .into());
     }
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("update_action_item_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("update_action_item_set_actor", &error);
+        database_error_response()
+    })?;
     sqlx::query!(
         "UPDATE action_items SET text = $1 WHERE id = $2",
         text,
         action_item_id
     )
-    .execute(&state.pool)
+    .execute(&mut *tx)
     .await
     .map_err(|error| {
         log_database_error("update_action_item", &error);
         database_error_response()
     })?;
+    tx.commit().await.map_err(|error| {
+        log_database_error("update_action_item_commit_transaction", &error);
+        database_error_response()
+    })?;
     let action_item = load_action_item(&state.pool, action_item_id)
         .await
         .map_err(|_| database_error_response())?;
-    Ok(Html(ActionItemTemplate { action_item }.render().unwrap()))
+    let mut response = Html(ActionItemTemplate { action_item }.render().unwrap()).into_response();
+    if existing.text != text {
+        attach_undo_header(&mut response, "Action item edited");
+    }
+    Ok(response)
 }
 
 pub async fn complete_action_item(
@@ -1489,7 +3435,7 @@ pub async fn delete_action_item(
     State(state): State<AppState>,
     user: AuthUser,
     Path(action_item_id): Path<i32>,
-) -> Result<StatusCode, HandlerError> {
+) -> Result<Response, HandlerError> {
     let existing = load_action_item(&state.pool, action_item_id)
         .await
         .map_err(|error| match error {
@@ -1499,19 +3445,34 @@ pub async fn delete_action_item(
     require_retro_access_by_id(&state, &user, existing.retro_id)
         .await?
         .ok_or_else(|| not_found_page(&state))?;
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("delete_action_item_begin_transaction", &error);
+        database_error_response()
+    })?;
+    set_actor(&mut tx, user.user_id).await.map_err(|error| {
+        log_database_error("delete_action_item_set_actor", &error);
+        database_error_response()
+    })?;
     sqlx::query!("DELETE FROM action_items WHERE id = $1", action_item_id)
-        .execute(&state.pool)
+        .execute(&mut *tx)
         .await
         .map_err(|error| {
             log_database_error("delete_action_item", &error);
             database_error_response()
         })?;
-    Ok(StatusCode::OK)
+    tx.commit().await.map_err(|error| {
+        log_database_error("delete_action_item_commit_transaction", &error);
+        database_error_response()
+    })?;
+    let mut response = StatusCode::OK.into_response();
+    attach_undo_header(&mut response, "Action item deleted");
+    Ok(response)
 }
 
 pub async fn archive_retro(
     State(state): State<AppState>,
     user: AuthUser,
+    meta: RequestMeta,
     Path(retro_id): Path<i32>,
 ) -> Result<impl IntoResponse, HandlerError> {
     let retro = match require_retro_access_by_id(&state, &user, retro_id).await? {
@@ -1549,8 +3510,17 @@ pub async fn archive_retro(
             log_database_error("archive_retro_begin_transaction", &error);
             database_error_response()
         })?;
+        // Everyone present right now plus whoever wrote the archived cards.
         let archive_id = sqlx::query_scalar!(
-            "INSERT INTO archives (retro_id) VALUES ($1) RETURNING id",
+            "INSERT INTO archives (retro_id, participants_count)
+             VALUES ($1, (
+                 SELECT COUNT(*) FROM (
+                     SELECT user_id FROM presence WHERE retro_id = $1
+                     UNION
+                     SELECT created_by FROM items WHERE retro_id = $1 AND archive_id IS NULL
+                 ) participants
+             ))
+             RETURNING id",
             retro_id
         )
         .fetch_one(&mut *tx)
@@ -1583,6 +3553,31 @@ pub async fn archive_retro(
             log_database_error("archive_retro_action_items", &error);
             database_error_response()
         })?;
+        // Archiving ends the session: its timers stop.
+        sqlx::query!("DELETE FROM retro_timers WHERE retro_id = $1", retro_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|error| {
+                log_database_error("archive_retro_timers", &error);
+                database_error_response()
+            })?;
+        audit::record(
+            &mut *tx,
+            user.user_id,
+            AuditAction::RetroArchived,
+            &retro.slug,
+            serde_json::json!({
+                "archive_id": archive_id,
+                "items": active_items_count,
+                "action_items": active_action_items_count,
+            }),
+            &meta,
+        )
+        .await
+        .map_err(|error| {
+            log_database_error("archive_retro_audit", &error);
+            database_error_response()
+        })?;
         tx.commit().await.map_err(|error| {
             log_database_error("archive_retro_commit_transaction", &error);
             database_error_response()
@@ -1610,55 +3605,83 @@ pub async fn archive_retro(
         .into_response())
 }
 
+/// Step-up re-authentication for destructive admin actions: require a login
+/// that is at most ADMIN_REAUTH_MAX_AGE_SECONDS old. Older admins are sent
+/// back through the OAuth flow, which creates a fresh session. `action`
+/// completes "Re-authentication required to …".
+pub(crate) async fn require_fresh_login(
+    state: &AppState,
+    user: &AuthUser,
+    parts: &Parts,
+    action: &str,
+) -> Result<(), HandlerError> {
+    if state.config.demo_mode() {
+        return Ok(());
+    }
+    let created_at = match read_cookie(parts, SESSION_COOKIE) {
+        Some(session_id) => {
+            sqlx::query_scalar!("SELECT created_at FROM sessions WHERE id = $1", session_id)
+                .fetch_optional(&state.pool)
+                .await
+                .ok()
+                .flatten()
+        }
+        None => None,
+    };
+    let fresh_login = created_at.is_some_and(|created_at| {
+        Utc::now() - created_at
+            < chrono::Duration::try_seconds(ADMIN_REAUTH_MAX_AGE_SECONDS).unwrap()
+    });
+    if fresh_login {
+        return Ok(());
+    }
+
+    tracing::info!(
+        user_id = user.user_id,
+        action,
+        "admin re-authentication required"
+    );
+    Err((
+        StatusCode::FORBIDDEN,
+        [("HX-Redirect", "/auth/login"), ("Location", "/auth/login")],
+        format!("Re-authentication required to {action}"),
+    )
+        .into_response()
+        .into())
+}
+
 pub async fn delete_retro(
     State(state): State<AppState>,
     user: AuthUser,
     Path(slug): Path<String>,
+    meta: RequestMeta,
     parts: Parts,
 ) -> impl IntoResponse {
     if !user.is_admin {
         return forbidden(&state, "Only admins can delete retrospectives");
     }
 
-    // Step-up re-authentication: deleting a retro is destructive, so require
-    // a login that is at most ADMIN_REAUTH_MAX_AGE_SECONDS old. Older admins
-    // are sent back through the OAuth flow, which creates a fresh session.
-    if !state.config.demo_mode() {
-        let created_at = match read_cookie(&parts, SESSION_COOKIE) {
-            Some(session_id) => {
-                sqlx::query_scalar!("SELECT created_at FROM sessions WHERE id = $1", session_id)
-                    .fetch_optional(&state.pool)
-                    .await
-                    .ok()
-                    .flatten()
-            }
-            None => None,
-        };
-        let fresh_login = created_at.is_some_and(|created_at| {
-            Utc::now() - created_at
-                < chrono::Duration::try_seconds(ADMIN_REAUTH_MAX_AGE_SECONDS).unwrap()
-        });
+    if let Err(error) = require_fresh_login(&state, &user, &parts, "delete a retro").await {
+        return error.into_response();
+    }
 
-        if !fresh_login {
-            tracing::info!(
-                user_id = user.user_id,
-                "admin re-authentication required before deleting a retro"
-            );
-            return (
-                StatusCode::FORBIDDEN,
-                [("HX-Redirect", "/auth/login"), ("Location", "/auth/login")],
-                "Re-authentication required to delete a retro",
-            )
-                .into_response();
+    let mut tx = match state.pool.begin().await {
+        Ok(tx) => tx,
+        Err(error) => {
+            log_database_error("delete_retro_begin_transaction", &error);
+            return database_error_response();
         }
-    }
+    };
 
     let retro = match sqlx::query_as!(
         Retrospective,
-        "DELETE FROM retrospectives WHERE slug = $1 RETURNING *",
+        r#"DELETE FROM retrospectives WHERE slug = $1
+           RETURNING id, title, slug, created_at, updated_at, team_slug, created_by,
+                     edit_policy as "edit_policy: EditPolicy", timer_default_seconds, timer_extend_seconds,
+                     timer_max_seconds, timer_auto_start"#,
         slug
     )
-    .fetch_one(&state.pool)
+    .fetch_one(&mut *tx)
     .await
     {
         Ok(retro) => retro,
@@ -1669,6 +3692,24 @@ pub async fn delete_retro(
         }
     };
 
+    if let Err(error) = audit::record(
+        &mut *tx,
+        user.user_id,
+        AuditAction::RetroDeleted,
+        &retro.slug,
+        serde_json::json!({ "title": retro.title, "team": retro.team_slug }),
+        &meta,
+    )
+    .await
+    {
+        log_database_error("delete_retro_audit", &error);
+        return database_error_response();
+    }
+    if let Err(error) = tx.commit().await {
+        log_database_error("delete_retro_commit_transaction", &error);
+        return database_error_response();
+    }
+
     tracing::info!(
         retro_id = retro.id,
         user_id = user.user_id,
@@ -1690,7 +3731,7 @@ pub async fn list_archives(
     let archives = sqlx::query_as!(
         Archive,
         r#"
-        SELECT id, retro_id, created_at
+        SELECT id, retro_id, created_at, participants_count
         FROM archives
         WHERE retro_id = $1
         ORDER BY created_at DESC
@@ -1778,7 +3819,7 @@ pub async fn show_archive(
     let archive = match sqlx::query_as!(
         Archive,
         r#"
-        SELECT id, retro_id, created_at
+        SELECT id, retro_id, created_at, participants_count
         FROM archives
         WHERE id = $1 AND retro_id = $2
         "#,
@@ -1802,15 +3843,23 @@ pub async fn show_archive(
                   i.category as "category: _", i.created_at as "created_at!", i.status as "status: _",
                   i.created_by as "author_id!", u.display_name as "author_name!",
                   ''::text as "author_initials!",
+                  u.initials as "author_custom_initials: _",
+                  CASE WHEN u.show_avatar THEN u.avatar_url END as "author_avatar_url: _",
                   (SELECT COUNT(*) FROM likes WHERE item_id = i.id) as "likes_count!",
+                  item_reactions_json(i.id) as "reactions!: sqlx::types::Json<Vec<ReactionCount>>",
+                  (SELECT COUNT(*) FROM item_revisions WHERE item_id = i.id) as "revisions_count!",
+                  i.position as "position!",
+                  item_comments_json(i.id) as "comments!: sqlx::types::Json<Vec<ItemComment>>",
+                  i.notes as "notes!", i.notes_revision as "notes_revision!",
                   i.archive_id as "archive_id: _", i.archived_at as "archived_at: _",
                   i.timer_started_at as "timer_started_at: _", i.timer_duration_seconds as "timer_duration_seconds: _",
-                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _"
+                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _",
+                  i.timer_paused_at as "timer_paused_at: _"
            FROM items i
            JOIN users u ON u.id = i.created_by
            WHERE i.archive_id = $1
            AND i.category = 'GOOD'
-           ORDER BY i.created_at ASC"#,
+           ORDER BY i.position, i.id"#,
         archive.id
     )
     .fetch_all(&state.pool)
@@ -1826,15 +3875,23 @@ pub async fn show_archive(
                   i.category as "category: _", i.created_at as "created_at!", i.status as "status: _",
                   i.created_by as "author_id!", u.display_name as "author_name!",
                   ''::text as "author_initials!",
+                  u.initials as "author_custom_initials: _",
+                  CASE WHEN u.show_avatar THEN u.avatar_url END as "author_avatar_url: _",
                   (SELECT COUNT(*) FROM likes WHERE item_id = i.id) as "likes_count!",
+                  item_reactions_json(i.id) as "reactions!: sqlx::types::Json<Vec<ReactionCount>>",
+                  (SELECT COUNT(*) FROM item_revisions WHERE item_id = i.id) as "revisions_count!",
+                  i.position as "position!",
+                  item_comments_json(i.id) as "comments!: sqlx::types::Json<Vec<ItemComment>>",
+                  i.notes as "notes!", i.notes_revision as "notes_revision!",
                   i.archive_id as "archive_id: _", i.archived_at as "archived_at: _",
                   i.timer_started_at as "timer_started_at: _", i.timer_duration_seconds as "timer_duration_seconds: _",
-                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _"
+                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _",
+                  i.timer_paused_at as "timer_paused_at: _"
            FROM items i
            JOIN users u ON u.id = i.created_by
            WHERE i.archive_id = $1
            AND i.category = 'BAD'
-           ORDER BY i.created_at ASC"#,
+           ORDER BY i.position, i.id"#,
         archive.id
     )
     .fetch_all(&state.pool)
@@ -1850,15 +3907,23 @@ pub async fn show_archive(
                   i.category as "category: _", i.created_at as "created_at!", i.status as "status: _",
                   i.created_by as "author_id!", u.display_name as "author_name!",
                   ''::text as "author_initials!",
+                  u.initials as "author_custom_initials: _",
+                  CASE WHEN u.show_avatar THEN u.avatar_url END as "author_avatar_url: _",
                   (SELECT COUNT(*) FROM likes WHERE item_id = i.id) as "likes_count!",
+                  item_reactions_json(i.id) as "reactions!: sqlx::types::Json<Vec<ReactionCount>>",
+                  (SELECT COUNT(*) FROM item_revisions WHERE item_id = i.id) as "revisions_count!",
+                  i.position as "position!",
+                  item_comments_json(i.id) as "comments!: sqlx::types::Json<Vec<ItemComment>>",
+                  i.notes as "notes!", i.notes_revision as "notes_revision!",
                   i.archive_id as "archive_id: _", i.archived_at as "archived_at: _",
                   i.timer_started_at as "timer_started_at: _", i.timer_duration_seconds as "timer_duration_seconds: _",
-                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _"
+                  i.timer_ends_at as "timer_ends_at: _", i.timer_elapsed_at as "timer_elapsed_at: _",
+                  i.timer_paused_at as "timer_paused_at: _"
            FROM items i
            JOIN users u ON u.id = i.created_by
            WHERE i.archive_id = $1
            AND i.category = 'WATCH'
-           ORDER BY i.created_at ASC"#,
+           ORDER BY i.position, i.id"#,
         archive.id
     )
     .fetch_all(&state.pool)
@@ -1869,11 +3934,14 @@ pub async fn show_archive(
     })?;
 
     apply_author_initials(&mut [&mut good_items, &mut bad_items, &mut watch_items]);
+    let participants = item_authors(&[&good_items, &bad_items, &watch_items]);
 
     let action_items = sqlx::query_as!(
         ActionItem,
         r#"SELECT id as "id!", retro_id as "retro_id!", text as "text!", created_at as "created_at!",
-                  completed_at as "completed_at: _", archive_id as "archive_id: _", archived_at as "archived_at: _"
+                  completed_at as "completed_at: _", archive_id as "archive_id: _", archived_at as "archived_at: _",
+                  source_item_id as "source_item_id: _",
+                  (SELECT text FROM items WHERE items.id = action_items.source_item_id) as "source_item_text: _"
            FROM action_items WHERE archive_id = $1 ORDER BY created_at ASC"#,
         archive.id
     )
@@ -1905,6 +3973,7 @@ pub async fn show_archive(
             bad_items,
             watch_items,
             action_items,
+            participants,
             is_admin: user.is_admin,
             user: Some(user),
             demo_mode: state.config.demo_mode(),
@@ -1925,6 +3994,33 @@ pub struct NewRetro {
     title: String,
     slug: String,
     team_slug: Option<String>,
+    #[serde(default)]
+    edit_policy: EditPolicy,
+}
+
+#[derive(Deserialize)]
+pub struct MoveItemForm {
+    category: Category,
+}
+
+#[derive(Deserialize)]
+pub struct ReorderItemForm {
+    after: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct EditPolicyForm {
+    edit_policy: EditPolicy,
+}
+
+#[derive(Deserialize)]
+pub struct TimerSettingsForm {
+    discussion_minutes: i32,
+    extension_minutes: i32,
+    /// Empty for no maximum.
+    max_minutes: String,
+    /// Checkbox: only present when checked.
+    auto_start: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -1932,6 +4028,16 @@ pub struct NewItem {
     text: String,
 }
 
+#[derive(Deserialize)]
+pub struct NewItemComment {
+    text: String,
+}
+
+#[derive(Deserialize)]
+pub struct ItemNotesForm {
+    notes: String,
+}
+
 #[derive(Deserialize)]
 pub struct NewActionItem {
     text: String,
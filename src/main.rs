@@ -7,6 +7,7 @@ use axum::{
 use clap::Parser;
 use config::Config;
 use events::EventHub;
+use presence::PresenceTracker;
 use sqlx::PgPool;
 use tower::Layer;
 use tower_http::{
@@ -34,8 +35,11 @@ pub struct AppState {
     pub config: Config,
     pub demo_user_id: Option<i32>,
     pub events: EventHub,
+    pub presence: PresenceTracker,
 }
 
+mod admin;
+mod audit;
 mod auth;
 mod config;
 mod csrf;
@@ -43,8 +47,14 @@ mod events;
 mod github;
 mod handlers;
 mod models;
+mod presence;
+mod profile;
+mod retention;
 mod security_headers;
 pub mod templates;
+mod timers;
+mod undo;
+mod websocket;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -92,14 +102,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // before the HTTP server so no mutation can race the subscription.
     let events = EventHub::new();
     tokio::spawn(events::notifier_loop(pool.clone(), events.clone()));
-    // Mark elapsed highlight timers so every client sees them expire together.
-    tokio::spawn(handlers::timer_sweep_loop(pool.clone()));
+    tokio::spawn(events::typing_expiry_loop(events.clone()));
+    // Mark elapsed highlight, session and phase timers so every client sees
+    // them expire together.
+    tokio::spawn(timers::scheduler_loop(pool.clone()));
+    // Keep this instance's presence rows alive and expire those of others
+    // that went away.
+    let presence = PresenceTracker::new(pool.clone());
+    tokio::spawn(presence::heartbeat_loop(presence.clone()));
+    // Keep the event log from growing forever.
+    tokio::spawn(retention::retention_loop(
+        pool.clone(),
+        config.event_retention_days,
+        config.event_retention_prune_archived,
+    ));
 
     let state = AppState {
         pool,
         config,
         demo_user_id,
         events,
+        presence,
     };
 
     let app: Router = Router::new()
@@ -109,21 +132,66 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/retros", post(handlers::create_retro))
         .route("/retro/{slug}", get(handlers::show_retro))
         .route("/retro/{slug}/events", get(events::retro_events))
+        .route("/retro/{slug}/ws", get(websocket::retro_socket))
+        .route("/retro/{slug}/typing", post(events::announce_typing))
+        .route("/retro/{slug}/undo", post(undo::undo_last_action))
+        .route(
+            "/retro/{slug}/edit-policy",
+            post(handlers::update_edit_policy),
+        )
+        .route(
+            "/retro/{slug}/timer-settings",
+            post(handlers::update_timer_settings),
+        )
+        .route("/retro/{slug}/timers", get(handlers::show_retro_timers))
+        .route(
+            "/retro/{slug}/timers/{kind}/start",
+            post(handlers::start_retro_timer),
+        )
+        .route(
+            "/retro/{slug}/timers/{kind}/extend",
+            post(handlers::extend_retro_timer),
+        )
+        .route(
+            "/retro/{slug}/timers/{kind}/stop",
+            post(handlers::stop_retro_timer),
+        )
         .route("/retro/{slug}/archives", get(handlers::list_archives))
         .route("/retro/{slug}/archives/{id}", get(handlers::show_archive))
         .route("/items/{category}/{retro_id}", post(handlers::add_item))
         .route(
             "/items/{id}",
-            get(handlers::show_item).post(handlers::update_item),
+            get(handlers::show_item)
+                .post(handlers::update_item)
+                .delete(handlers::delete_item),
         )
         .route("/items/{id}/edit", get(handlers::edit_item))
+        .route("/items/{id}/history", get(handlers::show_item_history))
         .route("/items/{id}/status", post(handlers::change_item_status))
         .route("/items/{id}/like", post(handlers::like_item))
+        .route("/items/{id}/reactions", post(handlers::react_to_item))
+        .route("/items/{id}/move", post(handlers::move_item))
+        .route("/items/{id}/reorder", post(handlers::reorder_item))
+        .route("/items/{id}/comments", post(handlers::add_item_comment))
+        .route("/items/{id}/notes", post(handlers::update_item_notes))
+        .route(
+            "/items/{id}/action-items",
+            post(handlers::add_action_item_from_item),
+        )
+        .route(
+            "/items/{id}/comments/{comment_id}",
+            delete(handlers::delete_item_comment),
+        )
         .route("/items/{id}/timer/start", post(handlers::start_item_timer))
         .route(
             "/items/{id}/timer/extend",
             post(handlers::extend_item_timer),
         )
+        .route("/items/{id}/timer/pause", post(handlers::pause_item_timer))
+        .route(
+            "/items/{id}/timer/resume",
+            post(handlers::resume_item_timer),
+        )
         .route(
             "/retro/{retro_id}/action-items",
             post(handlers::add_action_item),
@@ -141,16 +209,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .route("/retro/{retro_id}/archive", post(handlers::archive_retro))
         .route("/retro/{slug}/delete", delete(handlers::delete_retro))
+        .route("/admin", get(admin::admin_console))
+        .route(
+            "/admin/sessions/{public_id}/revoke",
+            post(admin::revoke_session),
+        )
+        .route(
+            "/admin/users/{id}/sessions/revoke",
+            post(admin::revoke_user_sessions),
+        )
+        .route("/admin/audit", get(audit::audit_log))
+        .route("/admin/audit.csv", get(audit::audit_log_csv))
         .route("/auth/login", get(auth::login))
         .route("/auth/callback", get(auth::callback))
         .route("/auth/logout", post(auth::logout))
+        .route("/auth/logout/everywhere", post(auth::logout_everywhere))
+        .route(
+            "/profile",
+            get(profile::show_profile).post(profile::update_profile),
+        )
+        .route(
+            "/profile/sessions/{public_id}/revoke",
+            post(profile::revoke_own_session),
+        )
         .nest_service("/static", ServeDir::new("static"))
         .fallback(handlers::not_found)
         // CSRF defense-in-depth for cookie-authenticated mutations: rejects
         // state-changing requests from foreign origins.
         .layer(middleware::from_fn_with_state(state.clone(), csrf::check))
         // Security headers (CSP, HSTS, frame protection, ...) on every response.
-        .layer(middleware::from_fn(security_headers::apply))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            security_headers::apply,
+        ))
         // Cap the request body so oversized form payloads cannot exhaust memory.
         .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
         .layer(
@@ -1,3 +1,4 @@
+use crate::auth::AuthUser;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::collections::HashMap;
@@ -12,6 +13,259 @@ pub struct Retrospective {
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub team_slug: String,
     pub created_by: i32,
+    pub edit_policy: EditPolicy,
+    /// Highlight timer settings; see `migrations/042_timer_settings.sql`.
+    pub timer_default_seconds: i32,
+    pub timer_extend_seconds: i32,
+    /// Cap on the total length of a timer, extensions included.
+    pub timer_max_seconds: Option<i32>,
+    /// Whether highlighting a card starts its timer.
+    pub timer_auto_start: bool,
+}
+
+impl Retrospective {
+    /// Facilitators are the creator of the retro and the admins.
+    pub fn is_facilitator(&self, user: &AuthUser) -> bool {
+        user.is_admin || self.created_by == user.user_id
+    }
+}
+
+/// Which of the retro-wide timers a `RetroTimer` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "retro_timer_kind", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RetroTimerKind {
+    /// The whole session.
+    Session,
+    /// The current phase of the agenda (writing, voting, ...).
+    Phase,
+}
+
+impl RetroTimerKind {
+    /// The kind in the timer URLs (`/retro/{slug}/timers/{kind}/...`).
+    pub fn from_path(segment: &str) -> Option<Self> {
+        match segment {
+            "session" => Some(RetroTimerKind::Session),
+            "phase" => Some(RetroTimerKind::Phase),
+            _ => None,
+        }
+    }
+
+    pub const fn path(&self) -> &'static str {
+        match self {
+            RetroTimerKind::Session => "session",
+            RetroTimerKind::Phase => "phase",
+        }
+    }
+}
+
+impl Display for RetroTimerKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetroTimerKind::Session => write!(f, "SESSION"),
+            RetroTimerKind::Phase => write!(f, "PHASE"),
+        }
+    }
+}
+
+/// A countdown for the whole retro or for one phase of its agenda; see
+/// `migrations/043_retro_timers.sql`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RetroTimer {
+    pub kind: RetroTimerKind,
+    /// Name of the phase; `None` for the session timer.
+    pub label: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub duration_seconds: i32,
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub elapsed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl RetroTimer {
+    /// What the board shows next to the countdown.
+    pub fn title(&self) -> &str {
+        match (self.kind, &self.label) {
+            (RetroTimerKind::Phase, Some(label)) => label,
+            (RetroTimerKind::Phase, None) => "Phase",
+            (RetroTimerKind::Session, _) => "Session",
+        }
+    }
+}
+
+/// The running (or elapsed) timers of a retro.
+#[derive(Debug, Clone, Default)]
+pub struct RetroTimers {
+    pub session: Option<RetroTimer>,
+    pub phase: Option<RetroTimer>,
+}
+
+impl RetroTimers {
+    pub fn from_rows(rows: Vec<RetroTimer>) -> Self {
+        let mut timers = RetroTimers::default();
+        for timer in rows {
+            match timer.kind {
+                RetroTimerKind::Session => timers.session = Some(timer),
+                RetroTimerKind::Phase => timers.phase = Some(timer),
+            }
+        }
+        timers
+    }
+}
+
+/// Who may change the text of a card in a retro.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "edit_policy", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EditPolicy {
+    /// Only the author of the card.
+    Author,
+    /// The author of the card and the facilitators of the retro.
+    AuthorAndFacilitator,
+    /// Everyone with access to the retro.
+    #[default]
+    Open,
+}
+
+impl EditPolicy {
+    /// All policies, in the order the settings list them.
+    pub const ALL: [EditPolicy; 3] = [
+        EditPolicy::Open,
+        EditPolicy::AuthorAndFacilitator,
+        EditPolicy::Author,
+    ];
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            EditPolicy::Author => "Only the author",
+            EditPolicy::AuthorAndFacilitator => "The author and facilitators",
+            EditPolicy::Open => "Everyone",
+        }
+    }
+
+    /// Why a user the policy does not allow cannot edit a card.
+    pub const fn denial_message(&self) -> &'static str {
+        match self {
+            EditPolicy::Author => "Only the author of a card can edit it",
+            EditPolicy::AuthorAndFacilitator => {
+                "Only the author of a card or a facilitator can edit it"
+            }
+            EditPolicy::Open => "",
+        }
+    }
+}
+
+impl Display for EditPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditPolicy::Author => write!(f, "AUTHOR"),
+            EditPolicy::AuthorAndFacilitator => write!(f, "AUTHOR_AND_FACILITATOR"),
+            EditPolicy::Open => write!(f, "OPEN"),
+        }
+    }
+}
+
+/// An emoji reaction to a card, next to the likes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "reaction", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Reaction {
+    PlusOne,
+    Party,
+    Laugh,
+    Confused,
+}
+
+impl Reaction {
+    /// All reactions, in the order the reaction picker lists them.
+    pub const ALL: [Reaction; 4] = [
+        Reaction::PlusOne,
+        Reaction::Party,
+        Reaction::Laugh,
+        Reaction::Confused,
+    ];
+
+    pub const fn emoji(&self) -> &'static str {
+        match self {
+            Reaction::PlusOne => "👍",
+            Reaction::Party => "🎉",
+            Reaction::Laugh => "😄",
+            Reaction::Confused => "😕",
+        }
+    }
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Reaction::PlusOne => "+1",
+            Reaction::Party => "Hooray",
+            Reaction::Laugh => "Laugh",
+            Reaction::Confused => "Confused",
+        }
+    }
+}
+
+impl Display for Reaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reaction::PlusOne => write!(f, "PLUS_ONE"),
+            Reaction::Party => write!(f, "PARTY"),
+            Reaction::Laugh => write!(f, "LAUGH"),
+            Reaction::Confused => write!(f, "CONFUSED"),
+        }
+    }
+}
+
+/// How often a reaction was given to a card, and by whom.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReactionCount {
+    pub reaction: Reaction,
+    pub count: i64,
+    pub user_ids: Vec<i32>,
+}
+
+/// Which cards of a retro a user may edit, under the retro's edit policy, and
+/// delete.
+#[derive(Debug, Clone, Copy)]
+pub struct EditAccess {
+    pub policy: EditPolicy,
+    pub user_id: i32,
+    pub is_facilitator: bool,
+}
+
+impl EditAccess {
+    pub fn new(retro: &Retrospective, user: &AuthUser) -> Self {
+        Self {
+            policy: retro.edit_policy,
+            user_id: user.user_id,
+            is_facilitator: retro.is_facilitator(user),
+        }
+    }
+
+    pub fn allows(&self, item: &Item) -> bool {
+        match self.policy {
+            EditPolicy::Author => item.author_id == self.user_id,
+            EditPolicy::AuthorAndFacilitator => {
+                item.author_id == self.user_id || self.is_facilitator
+            }
+            EditPolicy::Open => true,
+        }
+    }
+
+    /// Cards the user may move to another column (`allows`) or, as a
+    /// facilitator, reorder within their column.
+    pub fn allows_dragging(&self, item: &Item) -> bool {
+        self.allows(item) || self.is_facilitator
+    }
+
+    /// Comments can be deleted by their author and the facilitators.
+    pub fn allows_deleting_comment(&self, comment: &ItemComment) -> bool {
+        comment.author_id == self.user_id || self.is_facilitator
+    }
+
+    /// Cards can be deleted by their author and the facilitators, whatever
+    /// the edit policy.
+    pub fn allows_deleting(&self, item: &Item) -> bool {
+        item.author_id == self.user_id || self.is_facilitator
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -19,6 +273,9 @@ pub struct Archive {
     pub id: i32,
     pub retro_id: i32,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// People present at archive time plus card authors; `None` for archives
+    /// made before presence was tracked.
+    pub participants_count: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -30,6 +287,9 @@ pub struct ActionItem {
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
     pub archive_id: Option<i32>,
     pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The card this action item was created from, if any.
+    pub source_item_id: Option<i32>,
+    pub source_item_text: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -43,37 +303,139 @@ pub struct Item {
     pub author_id: i32,
     pub author_name: String,
     pub author_initials: String,
+    /// Initials the author chose on their profile; they replace the computed
+    /// ones (see `apply_author_initials`).
+    pub author_custom_initials: Option<String>,
+    /// GitHub avatar of the author; `None` when unknown or hidden by the author.
+    pub author_avatar_url: Option<String>,
     pub likes_count: i64,
+    /// Reactions given to the card; only those given at least once.
+    pub reactions: sqlx::types::Json<Vec<ReactionCount>>,
+    /// Number of text changes; see `ItemRevision`.
+    pub revisions_count: i64,
+    /// Sort key of the card within its column (a fractional index, see
+    /// `migrations/037_item_positions.sql`).
+    pub position: String,
+    /// Notes taken while the card was discussed, oldest first.
+    pub comments: sqlx::types::Json<Vec<ItemComment>>,
+    /// Shared discussion notes; every save bumps `notes_revision`.
+    pub notes: String,
+    pub notes_revision: i32,
     pub archive_id: Option<i32>,
     pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
     pub timer_started_at: Option<chrono::DateTime<chrono::Utc>>,
     pub timer_duration_seconds: Option<i32>,
     pub timer_ends_at: Option<chrono::DateTime<chrono::Utc>>,
     pub timer_elapsed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub timer_paused_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Item {
+    /// Seconds left on a paused timer; `None` unless the timer is paused.
+    pub fn timer_paused_remaining_seconds(&self) -> Option<i64> {
+        let ends_at = self.timer_ends_at?;
+        let paused_at = self.timer_paused_at?;
+        Some((ends_at - paused_at).num_seconds().max(0))
+    }
+}
+
+/// A note on a card, taken while it was discussed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemComment {
+    pub id: i64,
+    pub author_id: i32,
+    pub author_name: String,
+    pub text: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// One change of a card's text.
+#[derive(Debug, Serialize, FromRow)]
+pub struct ItemRevision {
+    pub id: i64,
+    /// `None` when the editor is unknown (e.g. changed outside the app).
+    pub editor_name: Option<String>,
+    pub old_text: String,
+    pub new_text: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Fill in `author_initials` for all items of a board.
 pub fn apply_author_initials(items: &mut [&mut Vec<Item>]) {
-    let mut base_initial_counts = HashMap::new();
-    let mut authors = HashMap::new();
+    let initials = initials_by_user(items.iter().flat_map(|items| items.iter()).map(|item| {
+        (
+            item.author_id,
+            item.author_name.as_str(),
+            item.author_custom_initials.as_deref(),
+        )
+    }));
+    for item in items.iter_mut().flat_map(|items| items.iter_mut()) {
+        if let Some(initials) = initials.get(&item.author_id) {
+            item.author_initials = initials.clone();
+        }
+    }
+}
 
-    for item in items.iter().flat_map(|items| items.iter()) {
-        authors
-            .entry(item.author_id)
-            .or_insert_with(|| item.author_name.clone());
+/// Initials for a set of people, given as `(user id, name, custom initials)`.
+/// Custom initials are kept as chosen; computed initials are extended when
+/// they clash with those of someone else (including custom ones).
+pub fn initials_by_user<'a>(
+    people: impl Iterator<Item = (i32, &'a str, Option<&'a str>)>,
+) -> HashMap<i32, String> {
+    let mut people_by_id = HashMap::new();
+    for (user_id, name, custom) in people {
+        people_by_id.entry(user_id).or_insert((name, custom));
     }
-    for name in authors.values() {
-        *base_initial_counts
-            .entry(initials(name, false))
-            .or_insert(0) += 1;
+
+    let mut base_initial_counts = HashMap::new();
+    for (name, custom) in people_by_id.values() {
+        let base = custom.map_or_else(|| initials(name, false), str::to_string);
+        *base_initial_counts.entry(base).or_insert(0) += 1;
     }
 
-    for item in items.iter_mut().flat_map(|items| items.iter_mut()) {
-        let base = initials(&item.author_name, false);
-        item.author_initials = initials(
-            &item.author_name,
-            base_initial_counts.get(&base).copied().unwrap_or(0) > 1,
-        );
+    people_by_id
+        .into_iter()
+        .map(|(user_id, (name, custom))| {
+            let initials = match custom {
+                Some(custom) => custom.to_string(),
+                None => initials(
+                    name,
+                    base_initial_counts
+                        .get(&initials(name, false))
+                        .copied()
+                        .unwrap_or(0)
+                        > 1,
+                ),
+            };
+            (user_id, initials)
+        })
+        .collect()
+}
+
+/// Someone who took part in a retro, as shown in participant lists.
+#[derive(Debug, Clone, Serialize)]
+pub struct Participant {
+    pub user_id: i32,
+    pub name: String,
+    pub initials: String,
+    pub avatar_url: Option<String>,
+}
+
+/// The distinct authors of the given items, in order of first appearance.
+/// Expects `apply_author_initials` to have run on the items.
+pub fn item_authors(items: &[&Vec<Item>]) -> Vec<Participant> {
+    let mut participants: Vec<Participant> = Vec::new();
+    for item in items.iter().flat_map(|items| items.iter()) {
+        if participants.iter().all(|p| p.user_id != item.author_id) {
+            participants.push(Participant {
+                user_id: item.author_id,
+                name: item.author_name.clone(),
+                initials: item.author_initials.clone(),
+                avatar_url: item.author_avatar_url.clone(),
+            });
+        }
     }
+    participants
 }
 
 fn initials(name: &str, disambiguate: bool) -> String {
@@ -100,7 +462,7 @@ fn initials(name: &str, disambiguate: bool) -> String {
         .to_uppercase()
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "category", rename_all = "UPPERCASE")]
 pub enum Category {
     Good,
@@ -177,7 +539,94 @@ impl Display for Category {
 
 #[cfg(test)]
 mod tests {
-    use super::initials;
+    use super::*;
+
+    fn item(author_id: i32, author_name: &str, custom_initials: Option<&str>) -> Item {
+        Item {
+            id: author_id,
+            retro_id: 1,
+            text: "text".to_string(),
+            category: Category::Good,
+            created_at: chrono::Utc::now(),
+            status: Status::Created,
+            author_id,
+            author_name: author_name.to_string(),
+            author_initials: String::new(),
+            author_custom_initials: custom_initials.map(str::to_string),
+            author_avatar_url: None,
+            likes_count: 0,
+            reactions: sqlx::types::Json(Vec::new()),
+            revisions_count: 0,
+            position: "V".to_string(),
+            comments: sqlx::types::Json(Vec::new()),
+            notes: String::new(),
+            notes_revision: 0,
+            archive_id: None,
+            archived_at: None,
+            timer_started_at: None,
+            timer_duration_seconds: None,
+            timer_ends_at: None,
+            timer_elapsed_at: None,
+            timer_paused_at: None,
+        }
+    }
+
+    #[test]
+    fn edit_access_follows_the_edit_policy() {
+        let own = item(1, "Ada Lovelace", None);
+        let other = item(2, "Grace Hopper", None);
+        let access = |policy, is_facilitator| EditAccess {
+            policy,
+            user_id: 1,
+            is_facilitator,
+        };
+
+        assert!(access(EditPolicy::Open, false).allows(&other));
+        assert!(access(EditPolicy::Author, true).allows(&own));
+        assert!(!access(EditPolicy::Author, true).allows(&other));
+        assert!(access(EditPolicy::AuthorAndFacilitator, true).allows(&other));
+        assert!(!access(EditPolicy::AuthorAndFacilitator, false).allows(&other));
+        assert!(access(EditPolicy::AuthorAndFacilitator, false).allows(&own));
+
+        assert!(access(EditPolicy::Open, false).allows_deleting(&own));
+        assert!(!access(EditPolicy::Open, false).allows_deleting(&other));
+        assert!(access(EditPolicy::Author, true).allows_deleting(&other));
+    }
+
+    #[test]
+    fn paused_timers_keep_the_time_left_at_the_pause() {
+        let started_at = chrono::Utc::now();
+        let mut paused = item(1, "Ada Lovelace", None);
+        paused.timer_started_at = Some(started_at);
+        paused.timer_duration_seconds = Some(300);
+        paused.timer_ends_at = Some(started_at + chrono::Duration::seconds(300));
+        assert_eq!(paused.timer_paused_remaining_seconds(), None);
+
+        paused.timer_paused_at = Some(started_at + chrono::Duration::seconds(120));
+        assert_eq!(paused.timer_paused_remaining_seconds(), Some(180));
+    }
+
+    #[test]
+    fn custom_initials_are_kept_and_disambiguate_computed_ones() {
+        let mut items = vec![
+            item(1, "Stefan Uhlig", None),
+            item(2, "Sarah Unger", Some("SU")),
+            item(3, "Mona Lisa", Some("ML")),
+        ];
+        apply_author_initials(&mut [&mut items]);
+        assert_eq!(items[0].author_initials, "SUH");
+        assert_eq!(items[1].author_initials, "SU");
+        assert_eq!(items[2].author_initials, "ML");
+    }
+
+    #[test]
+    fn item_authors_are_distinct_and_in_order_of_appearance() {
+        let good = vec![item(2, "Sarah Unger", None), item(1, "Stefan Uhlig", None)];
+        let bad = vec![item(2, "Sarah Unger", None)];
+        let authors = item_authors(&[&good, &bad]);
+        let ids: Vec<i32> = authors.iter().map(|p| p.user_id).collect();
+        assert_eq!(ids, vec![2, 1]);
+    }
 
     #[test]
     fn builds_initials_from_first_and_last_names() {
@@ -0,0 +1,280 @@
+use crate::handlers::log_database_error;
+use crate::models::{initials_by_user, Participant};
+use sqlx::PgPool;
+
+/// Postgres channel the presence trigger NOTIFYs with the retro id.
+pub const PRESENCE_CHANNEL: &str = "rostfacto_presence";
+/// How often each instance confirms the connections it still serves.
+const PRESENCE_HEARTBEAT_SECONDS: u64 = 30;
+/// Presence rows without a heartbeat for this long belong to an instance that
+/// went away without cleaning up, and are removed.
+const PRESENCE_TIMEOUT_SECONDS: f64 = 90.0;
+
+/// Records which users have an SSE connection open to which retro on this
+/// app instance, in the `presence` table shared by all instances.
+#[derive(Clone)]
+pub struct PresenceTracker {
+    pool: PgPool,
+    instance_id: String,
+}
+
+impl PresenceTracker {
+    /// A tracker with a fresh instance id. Rows left behind by a previous run
+    /// of this process expire like those of any other vanished instance.
+    pub fn new(pool: PgPool) -> Self {
+        let mut bytes = [0u8; 16];
+        rand::fill(&mut bytes);
+        let instance_id = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        Self { pool, instance_id }
+    }
+
+    /// Count one more connection of the user to the retro. The user stays
+    /// present until the returned guard is dropped.
+    pub async fn join(&self, retro_id: i32, user_id: i32) -> Result<PresenceGuard, sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO presence (retro_id, user_id, instance_id, connections)
+             VALUES ($1, $2, $3, 1)
+             ON CONFLICT (retro_id, user_id, instance_id) DO UPDATE
+             SET connections = presence.connections + 1, heartbeat_at = NOW()",
+            retro_id,
+            user_id,
+            self.instance_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(PresenceGuard {
+            tracker: self.clone(),
+            retro_id,
+            user_id,
+        })
+    }
+
+    async fn leave(&self, retro_id: i32, user_id: i32) -> Result<(), sqlx::Error> {
+        // Lock the row, so a concurrent join or leave waits for this one
+        // instead of acting on a stale count: the last connection deletes the
+        // row (and notifies), any other decrements it.
+        let mut tx = self.pool.begin().await?;
+        let connections = sqlx::query_scalar!(
+            "SELECT connections FROM presence
+             WHERE retro_id = $1 AND user_id = $2 AND instance_id = $3
+             FOR UPDATE",
+            retro_id,
+            user_id,
+            self.instance_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        match connections {
+            Some(connections) if connections > 1 => {
+                sqlx::query!(
+                    "UPDATE presence SET connections = connections - 1
+                     WHERE retro_id = $1 AND user_id = $2 AND instance_id = $3",
+                    retro_id,
+                    user_id,
+                    self.instance_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            Some(_) => {
+                sqlx::query!(
+                    "DELETE FROM presence
+                     WHERE retro_id = $1 AND user_id = $2 AND instance_id = $3",
+                    retro_id,
+                    user_id,
+                    self.instance_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            None => {} // expired already
+        }
+        tx.commit().await
+    }
+}
+
+/// Keeps a user present in a retro while alive; leaving happens in the
+/// background on drop (e.g. when the client closes its SSE stream).
+pub struct PresenceGuard {
+    tracker: PresenceTracker,
+    retro_id: i32,
+    user_id: i32,
+}
+
+impl Drop for PresenceGuard {
+    fn drop(&mut self) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return; // shutting down; the row expires without heartbeats
+        };
+        let tracker = self.tracker.clone();
+        let (retro_id, user_id) = (self.retro_id, self.user_id);
+        runtime.spawn(async move {
+            if let Err(error) = tracker.leave(retro_id, user_id).await {
+                log_database_error("presence_leave", &error);
+            }
+        });
+    }
+}
+
+/// Background task: refreshes the heartbeat of this instance's presence rows
+/// and removes the rows of instances that stopped heartbeating. Removing
+/// rows notifies, so their retros get an updated participant list.
+pub async fn heartbeat_loop(tracker: PresenceTracker) {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(PRESENCE_HEARTBEAT_SECONDS));
+    loop {
+        interval.tick().await;
+        if let Err(error) = sqlx::query!(
+            "UPDATE presence SET heartbeat_at = NOW() WHERE instance_id = $1",
+            tracker.instance_id
+        )
+        .execute(&tracker.pool)
+        .await
+        {
+            log_database_error("presence_heartbeat", &error);
+        }
+        match sqlx::query!(
+            "DELETE FROM presence WHERE heartbeat_at < NOW() - make_interval(secs => $1)",
+            PRESENCE_TIMEOUT_SECONDS
+        )
+        .execute(&tracker.pool)
+        .await
+        {
+            Ok(result) if result.rows_affected() > 0 => {
+                tracing::info!(
+                    count = result.rows_affected(),
+                    "expired presence of vanished instances"
+                );
+            }
+            Ok(_) => {}
+            Err(error) => log_database_error("presence_expire", &error),
+        }
+    }
+}
+
+/// The people currently present in a retro (across all instances), in the
+/// order they joined.
+pub async fn load_participants(
+    pool: &PgPool,
+    retro_id: i32,
+) -> Result<Vec<Participant>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT u.id, u.display_name as "name!", u.initials as "custom_initials",
+                  CASE WHEN u.show_avatar THEN u.avatar_url END as "avatar_url"
+           FROM presence p
+           JOIN users u ON u.id = p.user_id
+           WHERE p.retro_id = $1 AND p.heartbeat_at >= NOW() - make_interval(secs => $2)
+           GROUP BY u.id
+           ORDER BY MIN(p.joined_at), u.id"#,
+        retro_id,
+        PRESENCE_TIMEOUT_SECONDS
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let initials = initials_by_user(
+        rows.iter()
+            .map(|row| (row.id, row.name.as_str(), row.custom_initials.as_deref())),
+    );
+    Ok(rows
+        .into_iter()
+        .map(|row| Participant {
+            user_id: row.id,
+            initials: initials.get(&row.id).cloned().unwrap_or_default(),
+            name: row.name,
+            avatar_url: row.avatar_url,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn connections(pool: &PgPool, retro_id: i32) -> Option<i32> {
+        sqlx::query_scalar!(
+            "SELECT SUM(connections)::int FROM presence WHERE retro_id = $1",
+            retro_id
+        )
+        .fetch_one(pool)
+        .await
+        .expect("Failed to read presence")
+    }
+
+    #[tokio::test]
+    async fn concurrent_joins_and_leaves_keep_the_count() {
+        let database_url =
+            std::env::var("DATABASE_URL").expect("DATABASE_URL environment variable must be set");
+        // A connection per tab, so joins and leaves really run concurrently.
+        const TABS: i32 = 25;
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(2 * TABS as u32)
+            .connect(&database_url)
+            .await
+            .expect("Failed to connect to database");
+        let user_id = crate::auth::ensure_demo_user(&pool)
+            .await
+            .expect("Failed to ensure demo user");
+        let slug = format!("presence-race-{}", std::process::id());
+        let retro_id = sqlx::query_scalar!(
+            "INSERT INTO retrospectives (title, slug, team_slug, created_by)
+             VALUES ('Presence', $1, 'demo', $2) RETURNING id",
+            slug,
+            user_id
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to create retro");
+        let tracker = PresenceTracker::new(pool.clone());
+        // Open the connections up front; opening them lazily would spread the
+        // tabs out in time.
+        let mut warm = Vec::new();
+        for _ in 0..2 * TABS {
+            warm.push(pool.acquire().await.expect("Failed to connect"));
+        }
+        drop(warm);
+
+        // Leaves are awaited here instead of spawned by the guards.
+        let join = |tracker: PresenceTracker| async move {
+            let guard = tracker
+                .join(retro_id, user_id)
+                .await
+                .expect("Failed to join");
+            std::mem::forget(guard);
+        };
+        let leave = |tracker: PresenceTracker| async move {
+            tracker
+                .leave(retro_id, user_id)
+                .await
+                .expect("Failed to leave");
+        };
+
+        // The races are narrow, so play the scenario a few times.
+        for _ in 0..10 {
+            // Page reloads: tabs leave while others join.
+            for _ in 0..TABS {
+                join(tracker.clone()).await;
+            }
+            let mut tasks = tokio::task::JoinSet::new();
+            for _ in 0..TABS {
+                tasks.spawn(join(tracker.clone()));
+                tasks.spawn(leave(tracker.clone()));
+            }
+            tasks.join_all().await;
+            assert_eq!(connections(&pool, retro_id).await, Some(TABS));
+
+            // Closing all tabs at once ends the presence.
+            let mut tasks = tokio::task::JoinSet::new();
+            for _ in 0..TABS {
+                tasks.spawn(leave(tracker.clone()));
+            }
+            tasks.join_all().await;
+            assert_eq!(connections(&pool, retro_id).await, None);
+        }
+
+        sqlx::query!("DELETE FROM retrospectives WHERE id = $1", retro_id)
+            .execute(&pool)
+            .await
+            .expect("Failed to delete test retro");
+    }
+}
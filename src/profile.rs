@@ -0,0 +1,259 @@
+use crate::audit::{self, AuditAction, RequestMeta};
+use crate::auth::{clear_session_cookie, read_cookie, AuthUser, SESSION_COOKIE};
+use crate::handlers::{
+    bad_request, database_error_response, log_database_error, not_found_page, HandlerError,
+};
+use crate::templates::ProfileTemplate;
+use crate::AppState;
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    http::{request::Parts, StatusCode},
+    response::{Html, IntoResponse, Response},
+    Form,
+};
+use serde::Deserialize;
+
+pub const MAX_NICKNAME_LENGTH: usize = 100;
+pub const MAX_INITIALS_LENGTH: usize = 4;
+
+/// How the signed-in user appears on cards, next to what GitHub knows.
+pub struct ProfileDetails {
+    pub username: String,
+    pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub nickname: Option<String>,
+    pub initials: Option<String>,
+    pub show_avatar: bool,
+}
+
+/// One of the signed-in user's own sessions.
+pub struct ProfileSession {
+    pub public_id: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    /// True for the session the current request was made with.
+    pub is_current: bool,
+}
+
+pub async fn show_profile(
+    State(state): State<AppState>,
+    user: AuthUser,
+    parts: Parts,
+) -> Result<Html<String>, HandlerError> {
+    let current_session = read_cookie(&parts, SESSION_COOKIE).unwrap_or_default();
+    let sessions = sqlx::query_as!(
+        ProfileSession,
+        r#"SELECT public_id, created_at, last_seen_at, expires_at, user_agent, ip_address,
+                  id = $2 as "is_current!"
+           FROM sessions
+           WHERE user_id = $1 AND expires_at > NOW()
+           ORDER BY (id = $2) DESC, last_seen_at DESC"#,
+        user.user_id,
+        current_session
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|error| {
+        log_database_error("profile_list_sessions", &error);
+        database_error_response()
+    })?;
+
+    let profile = sqlx::query_as!(
+        ProfileDetails,
+        "SELECT username, full_name, avatar_url, nickname, initials, show_avatar
+         FROM users WHERE id = $1",
+        user.user_id
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|error| {
+        log_database_error("profile_load_user", &error);
+        database_error_response()
+    })?;
+
+    let template = ProfileTemplate {
+        profile,
+        max_nickname_length: MAX_NICKNAME_LENGTH,
+        max_initials_length: MAX_INITIALS_LENGTH,
+        sessions,
+        demo_mode: state.config.demo_mode(),
+        user: Some(user),
+    };
+    Ok(Html(template.render().unwrap()))
+}
+
+/// Revoke one of the user's own sessions. Revoking the current session is a
+/// logout; any other session leads back to the profile page.
+pub async fn revoke_own_session(
+    State(state): State<AppState>,
+    user: AuthUser,
+    meta: RequestMeta,
+    Path(public_id): Path<String>,
+    parts: Parts,
+) -> Result<Response, HandlerError> {
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("revoke_own_session_begin_transaction", &error);
+        database_error_response()
+    })?;
+    // Scoped to the user: other users' sessions look like unknown ones.
+    let session_id = sqlx::query_scalar!(
+        "DELETE FROM sessions WHERE public_id = $1 AND user_id = $2 RETURNING id",
+        public_id,
+        user.user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("revoke_own_session", &error);
+        database_error_response()
+    })?
+    .ok_or_else(|| not_found_page(&state))?;
+    audit::record(
+        &mut *tx,
+        user.user_id,
+        AuditAction::SessionRevoked,
+        &user.username,
+        serde_json::json!({ "session": public_id }),
+        &meta,
+    )
+    .await
+    .map_err(|error| {
+        log_database_error("revoke_own_session_audit", &error);
+        database_error_response()
+    })?;
+    tx.commit().await.map_err(|error| {
+        log_database_error("revoke_own_session_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::info!(user_id = user.user_id, "own session revoked");
+
+    if read_cookie(&parts, SESSION_COOKIE).as_deref() == Some(session_id.as_str()) {
+        return Ok((
+            StatusCode::SEE_OTHER,
+            [
+                ("Location", "/".to_string()),
+                (
+                    "Set-Cookie",
+                    clear_session_cookie(state.config.cookies_secure()),
+                ),
+            ],
+        )
+            .into_response());
+    }
+    Ok((StatusCode::SEE_OTHER, [("Location", "/profile")]).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct ProfileForm {
+    nickname: String,
+    initials: String,
+    /// Checkbox: only present when checked.
+    show_avatar: Option<String>,
+}
+
+/// Normalize custom initials: trimmed and upper-cased; empty means "computed".
+fn parse_initials(initials: &str) -> Result<Option<String>, String> {
+    let initials = initials.trim().to_uppercase();
+    if initials.is_empty() {
+        return Ok(None);
+    }
+    if initials.chars().count() > MAX_INITIALS_LENGTH {
+        return Err(format!(
+            "Initials must be {MAX_INITIALS_LENGTH} characters or less"
+        ));
+    }
+    if !initials.chars().all(char::is_alphanumeric) {
+        return Err("Initials can only contain letters and numbers".to_string());
+    }
+    Ok(Some(initials))
+}
+
+/// Update how the user appears on cards. Empty fields fall back to what
+/// GitHub provides (name) or what is computed (initials).
+pub async fn update_profile(
+    State(state): State<AppState>,
+    user: AuthUser,
+    meta: RequestMeta,
+    Form(form): Form<ProfileForm>,
+) -> Result<Response, HandlerError> {
+    let nickname = Some(form.nickname.trim()).filter(|nickname| !nickname.is_empty());
+    if nickname.is_some_and(|nickname| nickname.chars().count() > MAX_NICKNAME_LENGTH) {
+        return Err(bad_request(
+            &state,
+            &format!("Nickname must be {MAX_NICKNAME_LENGTH} characters or less"),
+        )
+        .into());
+    }
+    let initials =
+        parse_initials(&form.initials).map_err(|message| bad_request(&state, &message))?;
+    let show_avatar = form.show_avatar.is_some();
+
+    let mut tx = state.pool.begin().await.map_err(|error| {
+        log_database_error("update_profile_begin_transaction", &error);
+        database_error_response()
+    })?;
+    sqlx::query!(
+        "UPDATE users SET nickname = $2, initials = $3, show_avatar = $4 WHERE id = $1",
+        user.user_id,
+        nickname,
+        initials,
+        show_avatar
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|error| {
+        log_database_error("update_profile", &error);
+        database_error_response()
+    })?;
+    audit::record(
+        &mut *tx,
+        user.user_id,
+        AuditAction::ProfileUpdated,
+        &user.username,
+        serde_json::json!({
+            "nickname": nickname,
+            "initials": initials,
+            "show_avatar": show_avatar,
+        }),
+        &meta,
+    )
+    .await
+    .map_err(|error| {
+        log_database_error("update_profile_audit", &error);
+        database_error_response()
+    })?;
+    tx.commit().await.map_err(|error| {
+        log_database_error("update_profile_commit_transaction", &error);
+        database_error_response()
+    })?;
+
+    tracing::info!(user_id = user.user_id, "profile updated");
+
+    Ok((StatusCode::SEE_OTHER, [("Location", "/profile")]).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_initials;
+
+    #[test]
+    fn initials_are_trimmed_and_upper_cased() {
+        assert_eq!(parse_initials(" su "), Ok(Some("SU".to_string())));
+    }
+
+    #[test]
+    fn empty_initials_fall_back_to_computed_ones() {
+        assert_eq!(parse_initials("  "), Ok(None));
+    }
+
+    #[test]
+    fn rejects_long_or_non_alphanumeric_initials() {
+        assert!(parse_initials("ABCDE").is_err());
+        assert!(parse_initials("S.U").is_err());
+    }
+}
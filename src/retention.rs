@@ -0,0 +1,73 @@
+use crate::handlers::log_database_error;
+use sqlx::PgPool;
+
+/// How often the event log is pruned.
+const RETENTION_INTERVAL_SECONDS: u64 = 60 * 60;
+
+/// Delete the events that are past retention: those older than `days` (if
+/// set) and, if `prune_archived`, those older than the latest archive of
+/// their retro. The highest deleted id per retro is recorded in
+/// `event_retention` in the same statement. Returns the number of deleted
+/// events.
+async fn prune_events(
+    pool: &PgPool,
+    days: Option<u16>,
+    prune_archived: bool,
+) -> Result<i64, sqlx::Error> {
+    // Events written in the archiving transaction share the archive's
+    // timestamp, so RETRO_ARCHIVED itself is kept.
+    sqlx::query_scalar!(
+        r#"WITH deleted AS (
+               DELETE FROM events e
+               WHERE e.created_at < NOW() - make_interval(days => $1)
+                  OR ($2 AND e.created_at < (SELECT MAX(a.created_at)
+                                             FROM archives a
+                                             WHERE a.retro_id = e.retro_id))
+               RETURNING e.retro_id, e.id
+           ),
+           marked AS (
+               INSERT INTO event_retention (retro_id, pruned_through)
+               SELECT retro_id, MAX(id) FROM deleted GROUP BY retro_id
+               ON CONFLICT (retro_id) DO UPDATE
+               SET pruned_through = GREATEST(event_retention.pruned_through,
+                                             EXCLUDED.pruned_through),
+                   pruned_at = NOW()
+           )
+           SELECT COUNT(*) as "count!" FROM deleted"#,
+        days.map(i32::from),
+        prune_archived
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// The highest id of the retro's events that may have been deleted; a client
+/// that has not seen that event cannot catch up by replay.
+pub async fn pruned_through(pool: &PgPool, retro_id: i32) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT pruned_through FROM event_retention WHERE retro_id = $1",
+        retro_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Background task: prunes the event log once at startup and then every
+/// hour. Several instances may prune concurrently; deletes of the same rows
+/// serialize and the recorded mark only ever grows.
+pub async fn retention_loop(pool: PgPool, days: Option<u16>, prune_archived: bool) {
+    if days.is_none() && !prune_archived {
+        tracing::info!("event retention disabled; keeping all events");
+        return;
+    }
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(RETENTION_INTERVAL_SECONDS));
+    loop {
+        interval.tick().await;
+        match prune_events(&pool, days, prune_archived).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(count, "pruned events past retention"),
+            Err(error) => log_database_error("event_retention", &error),
+        }
+    }
+}
@@ -1,34 +1,52 @@
+use crate::config::Config;
+use crate::AppState;
 use axum::{
+    extract::State,
     http::{header, HeaderValue, Request},
     middleware::Next,
     response::Response,
 };
 
-/// Security headers applied to every response.
+/// The Content-Security-Policy of every page.
 ///
 /// The CSP is strict: all JavaScript is served from `/static` (or the pinned,
 /// SRI-protected htmx CDN bundle), so inline scripts and `eval` are banned.
 /// All inline `onclick`/`hx-on` handlers were removed from the templates to
-/// make this possible.
-pub async fn apply(request: Request<axum::body::Body>, next: Next) -> Response {
+/// make this possible. Images may additionally come from GitHub's avatar
+/// origins, as cards show the avatars of their authors.
+pub fn content_security_policy(config: &Config) -> String {
+    format!(
+        "default-src 'self'; \
+         script-src 'self' https://cdn.jsdelivr.net; \
+         style-src 'self' https://fonts.googleapis.com; \
+         font-src 'self' https://fonts.gstatic.com; \
+         img-src 'self' data: {}; \
+         connect-src 'self'; \
+         base-uri 'self'; \
+         form-action 'self'; \
+         frame-ancestors 'self'; \
+         object-src 'none'",
+        config.avatar_origins().join(" ")
+    )
+}
+
+/// Security headers applied to every response.
+pub async fn apply(
+    State(state): State<AppState>,
+    request: Request<axum::body::Body>,
+    next: Next,
+) -> Response {
     let mut response = next.run(request).await;
     let headers = response.headers_mut();
 
-    headers.insert(
-        header::CONTENT_SECURITY_POLICY,
-        HeaderValue::from_static(
-            "default-src 'self'; \
-             script-src 'self' https://cdn.jsdelivr.net; \
-             style-src 'self' https://fonts.googleapis.com; \
-             font-src 'self' https://fonts.gstatic.com; \
-             img-src 'self' data:; \
-             connect-src 'self'; \
-             base-uri 'self'; \
-             form-action 'self'; \
-             frame-ancestors 'self'; \
-             object-src 'none'",
-        ),
-    );
+    match HeaderValue::from_str(&content_security_policy(&state.config)) {
+        Ok(csp) => {
+            headers.insert(header::CONTENT_SECURITY_POLICY, csp);
+        }
+        Err(error) => {
+            tracing::error!(error = %error, "invalid Content-Security-Policy");
+        }
+    }
     // Older browsers that ignore frame-ancestors.
     headers.insert(
         header::X_FRAME_OPTIONS,
@@ -56,3 +74,40 @@ pub async fn apply(request: Request<axum::body::Body>, next: Next) -> Response {
 
     response
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(github_enterprise_url: Option<&str>) -> Config {
+        Config {
+            bind_address: "0.0.0.0:3000".to_string(),
+            database_url: "postgres://unused".to_string(),
+            public_url: "https://retro.example.com".to_string(),
+            github_client_id: String::new(),
+            github_client_secret: String::new(),
+            github_enterprise_url: github_enterprise_url.map(str::to_string),
+            github_admin_org: Some("org".to_string()),
+            github_admin_team_slug: Some("team".to_string()),
+            github_user_orgs: Vec::new(),
+            github_app_owner: None,
+            demo_mode: false,
+            event_retention_days: None,
+            event_retention_prune_archived: false,
+        }
+    }
+
+    #[test]
+    fn allows_github_avatars() {
+        let csp = content_security_policy(&config(None));
+        assert!(csp.contains("img-src 'self' data: https://avatars.githubusercontent.com;"));
+    }
+
+    #[test]
+    fn allows_github_enterprise_avatars() {
+        let csp = content_security_policy(&config(Some("https://github.example.com/")));
+        assert!(csp.contains(
+            "img-src 'self' data: https://github.example.com https://avatars.github.example.com;"
+        ));
+    }
+}
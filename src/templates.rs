@@ -1,5 +1,12 @@
+use crate::admin::{AdminUser, TeamRetroStats};
+use crate::audit::{AuditAction, AuditEntry};
 use crate::auth::AuthUser;
-use crate::models::{ActionItem, Archive, Category, Item, Retrospective, Status};
+use crate::events::HubMetrics;
+use crate::models::{
+    ActionItem, Archive, Category, EditAccess, EditPolicy, Item, ItemRevision, Participant,
+    Reaction, RetroTimers, Retrospective, Status,
+};
+use crate::profile::{ProfileDetails, ProfileSession};
 use askama::Template;
 
 #[derive(Template)]
@@ -7,6 +14,8 @@ use askama::Template;
 pub struct ItemCardTemplate {
     pub item: Item,
     pub error_message: Option<String>,
+    /// Whether the viewer gets the edit affordance.
+    pub edit_access: EditAccess,
 }
 
 #[derive(Template)]
@@ -15,6 +24,14 @@ pub struct ItemEditTemplate {
     pub item: Item,
 }
 
+#[derive(Template)]
+#[template(path = "item_history.html")]
+pub struct ItemHistoryTemplate {
+    pub item: Item,
+    /// Oldest first.
+    pub revisions: Vec<ItemRevision>,
+}
+
 #[derive(Template)]
 #[template(path = "action_item.html")]
 pub struct ActionItemTemplate {
@@ -32,6 +49,7 @@ pub struct ActionItemEditTemplate {
 pub struct ArchiveModalTemplate {
     pub item: Item,
     pub error_message: Option<String>,
+    pub edit_access: EditAccess,
 }
 
 #[derive(Template)]
@@ -83,12 +101,26 @@ pub struct RetroTemplate {
     pub bad_items: Vec<Item>,
     pub watch_items: Vec<Item>,
     pub action_items: Vec<ActionItem>,
+    /// People with the board open right now (kept current via SSE).
+    pub participants: Vec<Participant>,
+    /// The session and phase countdowns (kept current via SSE).
+    pub timers: RetroTimers,
     pub show_archive_modal: bool,
     pub is_admin: bool,
     pub user: Option<AuthUser>,
     pub demo_mode: bool,
     pub error_message: Option<String>,
     pub can_archive: bool,
+    pub edit_access: EditAccess,
+}
+
+/// The session and phase timers of a board, re-rendered when they change.
+#[derive(Template)]
+#[template(path = "retro_timers.html")]
+pub struct RetroTimersTemplate {
+    pub retro: Retrospective,
+    pub timers: RetroTimers,
+    pub edit_access: EditAccess,
 }
 
 #[derive(Template)]
@@ -117,6 +149,8 @@ pub struct ArchiveTemplate {
     pub bad_items: Vec<Item>,
     pub watch_items: Vec<Item>,
     pub action_items: Vec<ActionItem>,
+    /// Authors of the archived cards.
+    pub participants: Vec<Participant>,
     pub is_admin: bool,
     pub user: Option<AuthUser>,
     pub demo_mode: bool,
@@ -126,3 +160,43 @@ pub struct ArchiveTemplate {
 pub struct GitHubTeam {
     pub slug: String,
 }
+
+#[derive(Template)]
+#[template(path = "admin_audit.html")]
+pub struct AuditLogTemplate {
+    pub entries: Vec<AuditEntry>,
+    /// True when the page limit cut off older entries (the CSV export has them all).
+    pub truncated: bool,
+    pub actions: Vec<AuditAction>,
+    pub filter_action: String,
+    pub filter_actor: String,
+    pub filter_target: String,
+    pub filter_from: String,
+    pub filter_to: String,
+    pub csv_url: String,
+    pub user: Option<AuthUser>,
+    pub demo_mode: bool,
+}
+
+#[derive(Template)]
+#[template(path = "admin.html")]
+pub struct AdminConsoleTemplate {
+    pub users: Vec<AdminUser>,
+    pub active_sessions: usize,
+    pub team_stats: Vec<TeamRetroStats>,
+    /// Live connections served by this instance.
+    pub hub: HubMetrics,
+    pub user: Option<AuthUser>,
+    pub demo_mode: bool,
+}
+
+#[derive(Template)]
+#[template(path = "profile.html")]
+pub struct ProfileTemplate {
+    pub profile: ProfileDetails,
+    pub max_nickname_length: usize,
+    pub max_initials_length: usize,
+    pub sessions: Vec<ProfileSession>,
+    pub user: Option<AuthUser>,
+    pub demo_mode: bool,
+}
@@ -0,0 +1,159 @@
+use crate::handlers::log_database_error;
+use sqlx::postgres::{PgConnection, PgListener};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Postgres channel the timer triggers NOTIFY when a deadline may have moved
+/// (see `migrations/044_timer_scheduler.sql`).
+const TIMERS_CHANNEL: &str = "rostfacto_timers";
+/// First key of the advisory lock held by the instance that marks the timers
+/// elapsed (the second key is 0).
+const SCHEDULER_LOCK_KEY: i32 = 0x7469_6d72; // "timr"
+/// The scheduler looks at the deadlines at least this often, so a lost
+/// notification delays a timer by at most this long.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+/// Pause before competing for the lock again after a database error.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+/// How long before its deadline a highlight timer is announced as running out
+/// (TIMER_WARNING). Shorter timers get no warning.
+/// `migrations/045_timer_warning.sql` uses the same value to decide when an
+/// extension re-arms the warning.
+const WARNING_SECONDS: i32 = 60;
+
+/// Mark the highlight timers whose deadline passed as elapsed. Idempotent, so
+/// an instance that lost the lock without noticing yet does no harm.
+async fn mark_elapsed_item_timers(conn: &mut PgConnection) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"UPDATE items
+           SET timer_elapsed_at = NOW()
+           WHERE status = 'HIGHLIGHTED'::status
+             AND timer_ends_at <= NOW()
+             AND timer_elapsed_at IS NULL
+             AND timer_paused_at IS NULL"#
+    )
+    .execute(conn)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Mark the running highlight timers with less than a minute left as warned,
+/// which the item trigger broadcasts as TIMER_WARNING.
+async fn mark_warned_item_timers(conn: &mut PgConnection) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"UPDATE items
+           SET timer_warned_at = NOW()
+           WHERE status = 'HIGHLIGHTED'::status
+             AND timer_duration_seconds > $1::int4
+             AND timer_ends_at - make_interval(secs => $1::int4) <= NOW()
+             AND timer_ends_at > NOW()
+             AND timer_warned_at IS NULL
+             AND timer_elapsed_at IS NULL
+             AND timer_paused_at IS NULL"#,
+        WARNING_SECONDS
+    )
+    .execute(conn)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Mark the session and phase timers whose deadline passed as elapsed.
+async fn mark_elapsed_retro_timers(conn: &mut PgConnection) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"UPDATE retro_timers
+           SET elapsed_at = NOW()
+           WHERE ends_at <= NOW()
+             AND elapsed_at IS NULL"#
+    )
+    .execute(conn)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Time until the next deadline (or one-minute warning) of a running timer,
+/// by the database clock (the deadlines are computed from its `NOW()`, so the
+/// app's clock must not be trusted); `None` if no timer is running.
+async fn until_next_deadline(conn: &mut PgConnection) -> Result<Option<Duration>, sqlx::Error> {
+    let seconds = sqlx::query_scalar!(
+        r#"SELECT EXTRACT(EPOCH FROM (MIN(ends_at) - NOW()))::float8 as "seconds"
+           FROM (
+               SELECT timer_ends_at as ends_at
+               FROM items
+               WHERE status = 'HIGHLIGHTED'::status
+                 AND timer_elapsed_at IS NULL
+                 AND timer_paused_at IS NULL
+               UNION ALL
+               SELECT timer_ends_at - make_interval(secs => $1::int4)
+               FROM items
+               WHERE status = 'HIGHLIGHTED'::status
+                 AND timer_duration_seconds > $1::int4
+                 AND timer_warned_at IS NULL
+                 AND timer_elapsed_at IS NULL
+                 AND timer_paused_at IS NULL
+               UNION ALL
+               SELECT ends_at
+               FROM retro_timers
+               WHERE elapsed_at IS NULL
+           ) deadlines"#,
+        WARNING_SECONDS
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(seconds.map(|seconds| Duration::from_secs_f64(seconds.max(0.0))))
+}
+
+/// Background task: marks highlight, session and phase timers as elapsed
+/// exactly at their deadline, so all clients see 0:00 and the extend button
+/// at the same time, and highlight timers as warned a minute before. Spawned
+/// once per app process; of all instances, only the one holding the
+/// scheduler's advisory lock does the work, the others wait for the lock and
+/// take over when that instance goes away.
+pub async fn scheduler_loop(pool: PgPool) {
+    loop {
+        if let Err(error) = schedule(&pool).await {
+            log_database_error("timer_scheduler", &error);
+            tokio::time::sleep(RETRY_DELAY).await;
+        }
+    }
+}
+
+async fn schedule(pool: &PgPool) -> Result<(), sqlx::Error> {
+    // The lock belongs to the session, so it lives on a connection of its own
+    // that never goes back to the pool: Postgres releases the lock when the
+    // connection closes, also when this instance dies.
+    let mut conn = pool.acquire().await?.detach();
+    sqlx::query!("SELECT pg_advisory_lock($1, 0)", SCHEDULER_LOCK_KEY)
+        .execute(&mut conn)
+        .await?;
+    tracing::info!("this instance now marks elapsed timers");
+
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(TIMERS_CHANNEL).await?;
+    loop {
+        // Everything goes through the locked connection: if it breaks, the
+        // lock is gone, and the error hands the work over to whoever gets it
+        // next.
+        let items = mark_elapsed_item_timers(&mut conn).await?;
+        let retro_timers = mark_elapsed_retro_timers(&mut conn).await?;
+        if items + retro_timers > 0 {
+            tracing::debug!(items, retro_timers, "timers marked elapsed");
+        }
+        let warned = mark_warned_item_timers(&mut conn).await?;
+        if warned > 0 {
+            tracing::debug!(items = warned, "timers marked one minute left");
+        }
+
+        let sleep = match until_next_deadline(&mut conn).await? {
+            Some(until) if until.is_zero() => continue,
+            Some(until) => until.min(MAX_SLEEP),
+            None => MAX_SLEEP,
+        };
+        tokio::select! {
+            _ = tokio::time::sleep(sleep) => {}
+            // A lost listener connection (None) may have swallowed
+            // notifications, so recompute either way.
+            notification = listener.try_recv() => {
+                notification?;
+            }
+        }
+    }
+}
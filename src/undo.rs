@@ -0,0 +1,359 @@
+use crate::auth::AuthUser;
+use crate::events::{EventType, EVENTS_CHANNEL};
+use crate::handlers::{
+    database_error_response, log_database_error, not_found_response, require_retro_access,
+    HandlerError,
+};
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::{types::Json as JsonColumn, Connection, PgConnection};
+
+/// How long after a destructive action its author can still undo it.
+pub const UNDO_WINDOW_SECONDS: f64 = 300.0;
+/// First key of the advisory lock that serializes the undos of one user (the
+/// second key is the user id).
+const UNDO_LOCK_KEY: i32 = 0x756e_646f; // "undo"
+
+/// Response header of undoable mutations; the board offers undo when it is
+/// present, using the value as the toast message.
+pub const UNDO_HEADER: &str = "X-Undo";
+
+/// Attribute the events the current transaction writes to `user_id`
+/// (`events.actor_id`), which makes them undoable by that user.
+pub(crate) async fn set_actor(conn: &mut PgConnection, user_id: i32) -> Result<(), sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT set_config('rostfacto.actor_id', $1, true)",
+        user_id.to_string()
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
+
+pub(crate) fn attach_undo_header(response: &mut Response, message: &'static str) {
+    response
+        .headers_mut()
+        .insert(UNDO_HEADER, HeaderValue::from_static(message));
+}
+
+/// The most recent event of the user that can still be undone.
+struct Undoable {
+    id: i64,
+    retro_id: i32,
+    event_type: EventType,
+    item_id: Option<i32>,
+    action_item_id: Option<i32>,
+    payload: JsonColumn<Value>,
+    /// Undoable events of the user in this retro, including this one.
+    count: i64,
+}
+
+/// What an `ACTION_ITEM_DELETED` event recorded about the deleted item.
+#[derive(Deserialize)]
+struct DeletedActionItem {
+    action_item_id: i32,
+    text: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Absent in events from before action items linked to cards.
+    source_item_id: Option<i32>,
+}
+
+enum UndoError {
+    NothingToUndo,
+    /// The state changed since the event, so reversing it would clobber
+    /// someone else's change.
+    Conflict(&'static str),
+    Database,
+}
+
+impl IntoResponse for UndoError {
+    fn into_response(self) -> Response {
+        match self {
+            UndoError::NothingToUndo => (StatusCode::CONFLICT, "Nothing to undo").into_response(),
+            UndoError::Conflict(message) => (StatusCode::CONFLICT, message).into_response(),
+            UndoError::Database => database_error_response(),
+        }
+    }
+}
+
+fn database_error(operation: &'static str) -> impl FnOnce(sqlx::Error) -> UndoError {
+    move |error| {
+        log_database_error(operation, &error);
+        UndoError::Database
+    }
+}
+
+/// `POST /retro/{slug}/undo` — reverse the user's most recent destructive
+/// action in this retro: completing a card, cancelling a highlight, and
+/// deleting or editing an action item, within the last five minutes.
+///
+/// Repeated calls walk further back. The reversal is an ordinary change, so
+/// every client learns about it from the usual events, followed by an
+/// `ACTION_UNDONE` event naming the undone one. Responds with the undone event
+/// and how many undoable actions remain, or with `409 Conflict` when there is
+/// nothing to undo or the card or action item changed since. A conflicting
+/// action is skipped (its `ACTION_UNDONE` event says `"skipped": true`), so
+/// the next call reaches the one before it.
+pub async fn undo_last_action(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(slug): Path<String>,
+) -> Result<Response, HandlerError> {
+    let retro = match require_retro_access(&state, &user, &slug).await? {
+        Some(retro) => retro,
+        None => return Err(not_found_response(&state, &slug).into()),
+    };
+    match undo(&state, &user, retro.id).await {
+        Ok(undone) => Ok(Json(undone).into_response()),
+        Err(error) => Ok(error.into_response()),
+    }
+}
+
+async fn undo(state: &AppState, user: &AuthUser, retro_id: i32) -> Result<Value, UndoError> {
+    let mut tx = state
+        .pool
+        .begin()
+        .await
+        .map_err(database_error("undo_begin_transaction"))?;
+    // Without the lock, two quick undos could both pick the same event.
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock($1, $2)",
+        UNDO_LOCK_KEY,
+        user.user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(database_error("undo_lock"))?;
+
+    let undoable = sqlx::query_as!(
+        Undoable,
+        r#"SELECT id, retro_id, event_type as "event_type: EventType", item_id, action_item_id,
+                  payload as "payload: JsonColumn<Value>", COUNT(*) OVER () as "count!"
+           FROM events e
+           WHERE retro_id = $1 AND actor_id = $2 AND undoes_event_id IS NULL
+             AND created_at > NOW() - make_interval(secs => $3)
+             AND ((event_type = 'ITEM_STATUS_CHANGED' AND payload->>'old_status' = 'HIGHLIGHTED')
+                  OR (event_type = 'ACTION_ITEM_DELETED' AND payload ? 'created_at')
+                  OR (event_type = 'ACTION_ITEM_UPDATED' AND payload ? 'old_text'))
+             AND NOT EXISTS (SELECT 1 FROM events u WHERE u.undoes_event_id = e.id)
+           ORDER BY id DESC
+           LIMIT 1"#,
+        retro_id,
+        user.user_id,
+        UNDO_WINDOW_SECONDS
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(database_error("undo_find_event"))?
+    .ok_or(UndoError::NothingToUndo)?;
+
+    // The events of the reversal are marked as undoing this one, which keeps
+    // them off the undo stack.
+    set_actor(&mut tx, user.user_id)
+        .await
+        .map_err(database_error("undo_set_actor"))?;
+    sqlx::query_scalar!(
+        "SELECT set_config('rostfacto.undoes_event_id', $1, true)",
+        undoable.id.to_string()
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(database_error("undo_mark_reversal"))?;
+
+    // A reversal that conflicts is rolled back, but its event still counts as
+    // undone (skipped): left on the stack, it would hide the user's older
+    // actions until it leaves the undo window.
+    let mut reversal = tx
+        .begin()
+        .await
+        .map_err(database_error("undo_begin_reversal"))?;
+    let reversed = match undoable.event_type {
+        EventType::ItemStatusChanged => restore_highlight(&mut reversal, &undoable).await,
+        EventType::ActionItemDeleted => restore_action_item(&mut reversal, &undoable).await,
+        EventType::ActionItemUpdated => restore_action_item_text(&mut reversal, &undoable).await,
+        event_type => unreachable!("{event_type} events are not undoable"),
+    };
+    let skipped = match reversed {
+        Ok(()) => {
+            reversal
+                .commit()
+                .await
+                .map_err(database_error("undo_commit_reversal"))?;
+            None
+        }
+        Err(UndoError::Conflict(message)) => {
+            reversal
+                .rollback()
+                .await
+                .map_err(database_error("undo_rollback_reversal"))?;
+            Some(message)
+        }
+        Err(error) => return Err(error),
+    };
+
+    let event_id = sqlx::query_scalar!(
+        r#"INSERT INTO events (retro_id, event_type, item_id, action_item_id, payload)
+           VALUES ($1, 'ACTION_UNDONE', $2, $3,
+                   $4::jsonb || jsonb_build_object(
+                       'user_name', (SELECT display_name FROM users WHERE id = $5)))
+           RETURNING id"#,
+        undoable.retro_id,
+        undoable.item_id,
+        undoable.action_item_id,
+        json!({
+            "undone_event_id": undoable.id,
+            "undone_event_type": undoable.event_type,
+            "item_id": undoable.item_id,
+            "action_item_id": undoable.action_item_id,
+            "user_id": user.user_id,
+            "skipped": skipped.is_some(),
+        }),
+        user.user_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(database_error("undo_record_event"))?;
+    sqlx::query!(
+        "SELECT pg_notify($1, $2)",
+        EVENTS_CHANNEL,
+        undoable.retro_id.to_string()
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(database_error("undo_notify"))?;
+
+    tx.commit()
+        .await
+        .map_err(database_error("undo_commit_transaction"))?;
+
+    if let Some(message) = skipped {
+        tracing::info!(
+            retro_id,
+            user_id = user.user_id,
+            skipped_event_id = undoable.id,
+            event_type = %undoable.event_type,
+            reason = message,
+            "conflicting action skipped by undo"
+        );
+        return Err(UndoError::Conflict(message));
+    }
+
+    tracing::info!(
+        retro_id,
+        user_id = user.user_id,
+        undone_event_id = undoable.id,
+        event_type = %undoable.event_type,
+        "action undone"
+    );
+
+    Ok(json!({
+        "undone_event_id": undoable.id,
+        "undone_event_type": undoable.event_type,
+        "event_id": event_id,
+        "remaining": undoable.count - 1,
+    }))
+}
+
+/// Undo completing a card or cancelling its highlight: highlight it again.
+/// Like highlighting, this starts a fresh timer of the retro's discussion
+/// length (unless the retro does not start timers on highlight); the original
+/// countdown is not recorded.
+async fn restore_highlight(conn: &mut PgConnection, undoable: &Undoable) -> Result<(), UndoError> {
+    let new_status = undoable.payload["new_status"].as_str().unwrap_or_default();
+    let restored = sqlx::query!(
+        r#"UPDATE items
+           SET status = 'HIGHLIGHTED'::status,
+               timer_started_at = CASE WHEN r.timer_auto_start THEN NOW() END,
+               timer_duration_seconds = CASE WHEN r.timer_auto_start THEN r.timer_default_seconds END,
+               timer_elapsed_at = NULL,
+               timer_paused_at = NULL
+           FROM retrospectives r
+           WHERE items.id = $1 AND r.id = items.retro_id
+             AND items.status::text = $2 AND items.archive_id IS NULL"#,
+        undoable.item_id,
+        new_status
+    )
+    .execute(conn)
+    .await;
+    match restored {
+        Ok(result) if result.rows_affected() == 0 => Err(UndoError::Conflict(
+            "The card was changed or archived since",
+        )),
+        Ok(_) => Ok(()),
+        Err(error)
+            if error
+                .as_database_error()
+                .and_then(|error| error.constraint())
+                .is_some_and(|constraint| {
+                    constraint.contains("single_highlighted_item_per_retro")
+                }) =>
+        {
+            Err(UndoError::Conflict("Another card is highlighted now"))
+        }
+        Err(error) => Err(database_error("undo_restore_highlight")(error)),
+    }
+}
+
+/// Undo deleting an action item: insert it again, with its original id and
+/// its link to the card it came from, unless that card is gone by now.
+async fn restore_action_item(
+    conn: &mut PgConnection,
+    undoable: &Undoable,
+) -> Result<(), UndoError> {
+    let deleted: DeletedActionItem =
+        serde_json::from_value(undoable.payload.0.clone()).map_err(|error| {
+            tracing::error!(event_id = undoable.id, %error, "malformed ACTION_ITEM_DELETED payload");
+            UndoError::Conflict("The action item cannot be restored")
+        })?;
+    let result = sqlx::query!(
+        "INSERT INTO action_items (id, retro_id, text, created_at, completed_at, source_item_id)
+         OVERRIDING SYSTEM VALUE
+         VALUES ($1, $2, $3, $4, $5, (SELECT id FROM items WHERE id = $6))
+         ON CONFLICT (id) DO NOTHING",
+        deleted.action_item_id,
+        undoable.retro_id,
+        deleted.text,
+        deleted.created_at,
+        deleted.completed_at,
+        deleted.source_item_id
+    )
+    .execute(conn)
+    .await
+    .map_err(database_error("undo_restore_action_item"))?;
+    if result.rows_affected() == 0 {
+        return Err(UndoError::Conflict("The action item was already restored"));
+    }
+    Ok(())
+}
+
+/// Undo editing an action item: put back the previous text, unless it was
+/// edited again since.
+async fn restore_action_item_text(
+    conn: &mut PgConnection,
+    undoable: &Undoable,
+) -> Result<(), UndoError> {
+    let result = sqlx::query!(
+        "UPDATE action_items SET text = $2
+         WHERE id = $1 AND text = $3 AND archive_id IS NULL",
+        undoable.action_item_id,
+        undoable.payload["old_text"].as_str(),
+        undoable.payload["text"].as_str()
+    )
+    .execute(conn)
+    .await
+    .map_err(database_error("undo_restore_action_item_text"))?;
+    if result.rows_affected() == 0 {
+        return Err(UndoError::Conflict(
+            "The action item was changed, deleted or archived since",
+        ));
+    }
+    Ok(())
+}
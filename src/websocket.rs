@@ -0,0 +1,288 @@
+use crate::auth::{load_session, read_cookie, AuthUser, SESSION_COOKIE};
+use crate::events::{
+    subscribe_with_catch_up, with_payload, Broadcast, CatchUp, Event, PayloadMode, Signal,
+    Subscription,
+};
+use crate::handlers::{
+    change_status, create_item, database_error_response, log_database_error, not_found_response,
+    require_retro_access, toggle_like, toggle_reaction, ItemMutation, ItemStatusChange,
+    MutationError,
+};
+use crate::models::{Category, Reaction, Retrospective};
+use crate::AppState;
+use axum::{
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    http::request::Parts,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Mutation messages are small; this leaves ample room for the longest card.
+const MAX_MESSAGE_SIZE: usize = 64 * 1024;
+
+#[derive(Deserialize)]
+pub struct SocketParams {
+    /// The WebSocket API cannot send headers, so the id of the last event the
+    /// client saw comes as a query parameter instead of `Last-Event-ID`.
+    last_event_id: Option<i64>,
+    /// `full` embeds the complete card or action item, as on the SSE endpoint.
+    #[serde(default)]
+    payload: PayloadMode,
+}
+
+/// A mutation sent by the client. `request_id` is chosen by the client and
+/// echoed in the reply.
+#[derive(Deserialize)]
+struct SocketRequest {
+    request_id: String,
+    #[serde(flatten)]
+    mutation: SocketMutation,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SocketMutation {
+    AddCard { category: Category, text: String },
+    Like { item_id: i32 },
+    React { item_id: i32, reaction: Reaction },
+    ChangeStatus { item_id: i32, action: String },
+}
+
+/// `GET /retro/{slug}/ws` — WebSocket alternative to `GET /retro/{slug}/events`.
+///
+/// Streams the same events and signals as the SSE endpoint, as JSON text
+/// messages (`{"event": …, "id": …, "data": …}`), including the catch-up
+/// after `?last_event_id=`. In the other direction it accepts the card
+/// mutations (add, like, reaction, status change) and acknowledges each with
+/// the id of the event it produced, like the `X-Event-Id` header of the HTTP
+/// endpoints.
+pub async fn retro_socket(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(slug): Path<String>,
+    Query(params): Query<SocketParams>,
+    parts: Parts,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let retro = match require_retro_access(&state, &user, &slug).await {
+        Ok(Some(retro)) => retro,
+        Ok(None) => return not_found_response(&state, &slug),
+        Err(response) => return response.into_response(),
+    };
+    let subscription =
+        match subscribe_with_catch_up(&state, retro.id, user.user_id, params.last_event_id).await {
+            Ok(subscription) => subscription,
+            Err(_) => return database_error_response(),
+        };
+    // Without demo mode, the session is checked again before every mutation,
+    // so revoking it also ends what an open socket may do.
+    let session_id = (!state.config.demo_mode())
+        .then(|| read_cookie(&parts, SESSION_COOKIE))
+        .flatten();
+    let mode = params.payload;
+
+    upgrade
+        .max_message_size(MAX_MESSAGE_SIZE)
+        .on_upgrade(move |socket| serve(socket, state, user, session_id, retro, subscription, mode))
+}
+
+async fn serve(
+    mut socket: WebSocket,
+    state: AppState,
+    user: AuthUser,
+    session_id: Option<String>,
+    retro: Retrospective,
+    subscription: Subscription,
+    mode: PayloadMode,
+) {
+    let Subscription {
+        mut receiver,
+        max_id,
+        catch_up,
+        presence: _presence,
+    } = subscription;
+
+    match catch_up {
+        CatchUp::Reset => {
+            let reset = json!({ "event": "RESET", "data": { "reason": "events_pruned" } });
+            if send_json(&mut socket, &reset).await.is_err() {
+                return;
+            }
+        }
+        CatchUp::Replay(replay) => {
+            for event in replay {
+                let event = with_payload(&state.pool, mode, event).await;
+                if send_json(&mut socket, &event_message(&event))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    }
+
+    let mut keepalive = tokio::time::interval(std::time::Duration::from_secs(15));
+    loop {
+        let outcome = tokio::select! {
+            received = receiver.recv() => match received {
+                Some(Broadcast::Event(event)) if event.id > max_id => {
+                    let event = with_payload(&state.pool, mode, event).await;
+                    send_json(&mut socket, &event_message(&event)).await
+                }
+                Some(Broadcast::Event(_)) => Ok(()), // already covered by the replay
+                Some(Broadcast::Signal(signal)) => {
+                    send_json(&mut socket, &signal_message(&signal)).await
+                }
+                None => {
+                    // Too slow: the client reconnects with its last event id.
+                    close(&mut socket, close_code::AGAIN, "Too far behind").await;
+                    return;
+                }
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    if !session_is_valid(&state, session_id.as_deref()).await {
+                        close(&mut socket, close_code::POLICY, "Session ended").await;
+                        return;
+                    }
+                    let reply = handle_request(&state, &user, &retro, &text).await;
+                    send_json(&mut socket, &reply).await
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => Ok(()), // pings are answered by axum
+            },
+            _ = keepalive.tick() => socket.send(Message::Ping(Default::default())).await,
+        };
+        if outcome.is_err() {
+            return; // the client went away
+        }
+    }
+}
+
+async fn session_is_valid(state: &AppState, session_id: Option<&str>) -> bool {
+    let Some(session_id) = session_id else {
+        return state.config.demo_mode();
+    };
+    match load_session(&state.pool, session_id).await {
+        Ok(session) => session.is_some(),
+        Err(error) => {
+            log_database_error("socket_check_session", &error);
+            false
+        }
+    }
+}
+
+/// Apply one mutation message and build the reply: `ACK` with the event id
+/// (`null` if the mutation produced no event), or `ERROR` with an HTTP-like
+/// status.
+async fn handle_request(
+    state: &AppState,
+    user: &AuthUser,
+    retro: &Retrospective,
+    text: &str,
+) -> Value {
+    let request: SocketRequest = match serde_json::from_str(text) {
+        Ok(request) => request,
+        Err(error) => {
+            return json!({
+                "event": "ERROR",
+                "request_id": Value::Null,
+                "status": 400,
+                "message": format!("Malformed request: {error}"),
+            });
+        }
+    };
+
+    let result = match request.mutation {
+        SocketMutation::AddCard { category, text } => {
+            create_item(state, user, retro.id, category, &text)
+                .await
+                .map(|ItemMutation { item, event_id, .. }| {
+                    json!({ "item_id": item.id, "event_id": event_id })
+                })
+        }
+        SocketMutation::Like { item_id } => toggle_like(state, user, item_id).await.map(
+            |ItemMutation { item, event_id, .. }| {
+                json!({ "item_id": item.id, "likes_count": item.likes_count, "event_id": event_id })
+            },
+        ),
+        SocketMutation::React { item_id, reaction } => {
+            toggle_reaction(state, user, item_id, reaction).await.map(
+                |ItemMutation { item, event_id, .. }| {
+                    json!({ "item_id": item.id, "reactions": item.reactions, "event_id": event_id })
+                },
+            )
+        }
+        SocketMutation::ChangeStatus { item_id, action } => {
+            change_status(state, user, item_id, Some(&action))
+                .await
+                .map(
+                    |ItemStatusChange {
+                         item,
+                         event_id,
+                         all_completed,
+                         ..
+                     }| {
+                        json!({
+                            "item_id": item.id,
+                            "status": item.status,
+                            "all_completed": all_completed,
+                            "event_id": event_id,
+                        })
+                    },
+                )
+        }
+    };
+
+    match result {
+        Ok(mut ack) => {
+            ack["event"] = json!("ACK");
+            ack["request_id"] = json!(request.request_id);
+            ack
+        }
+        Err(error) => error_message(&request.request_id, &error),
+    }
+}
+
+fn error_message(request_id: &str, error: &MutationError) -> Value {
+    json!({
+        "event": "ERROR",
+        "request_id": request_id,
+        "status": error.status().as_u16(),
+        "message": error.message(),
+    })
+}
+
+fn event_message(event: &Event) -> Value {
+    json!({
+        "event": event.event_type.to_string(),
+        "id": event.id,
+        "data": event.payload,
+    })
+}
+
+/// Like SSE signals, these carry no id.
+fn signal_message(signal: &Signal) -> Value {
+    json!({
+        "event": signal.signal_type.to_string(),
+        "data": signal.payload,
+    })
+}
+
+async fn send_json(socket: &mut WebSocket, message: &Value) -> Result<(), axum::Error> {
+    socket.send(Message::Text(message.to_string().into())).await
+}
+
+async fn close(socket: &mut WebSocket, code: u16, reason: &'static str) {
+    let frame = CloseFrame {
+        code,
+        reason: reason.into(),
+    };
+    // The client may already be gone; nothing left to do then.
+    let _ = socket.send(Message::Close(Some(frame))).await;
+}
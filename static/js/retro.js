@@ -42,6 +42,41 @@
     }
     document.body.addEventListener('htmx:afterSwap', removeDuplicateCards);
 
+    // Columns are ordered by the cards' position keys, compared bytewise like
+    // the server does, or by likes when the board is sorted by likes.
+    function likesOf(card) {
+      const badge = card.querySelector('.like-count');
+      return badge ? Number(badge.textContent) || 0 : 0;
+    }
+
+    function compareCards(a, b) {
+      if (document.body.dataset.cardSort === 'likes') {
+        const byLikes = likesOf(b) - likesOf(a);
+        if (byLikes !== 0) return byLikes;
+      }
+      const positionA = a.dataset.position || '';
+      const positionB = b.dataset.position || '';
+      if (positionA !== positionB) return positionA < positionB ? -1 : 1;
+      return Number(a.dataset.itemId) - Number(b.dataset.itemId);
+    }
+
+    function sortColumn(list) {
+      const cards = Array.from(list.querySelectorAll(':scope > article.card'));
+      const sorted = cards.slice().sort(compareCards);
+      // Re-appending a card blurs it, so leave columns in order alone.
+      if (sorted.every(function(card, index) { return card === cards[index]; })) return;
+      sorted.forEach(function(card) {
+        list.appendChild(card);
+      });
+    }
+
+    function sortColumns() {
+      document.querySelectorAll('.item-list').forEach(sortColumn);
+    }
+    sortColumns();
+    document.body.addEventListener('htmx:afterSwap', sortColumns);
+    document.body.addEventListener('card:sort', sortColumns);
+
     function cardExists(itemId) {
       return document.querySelector('article.card[data-item-id="' + itemId + '"]') !== null;
     }
@@ -90,8 +125,18 @@
             !newBadge.hasAttribute('data-elapsed')) {
           newBadge.setAttribute('data-end-at', oldBadge.getAttribute('data-end-at'));
         }
+        // Keep discussion notes someone is typing; the notes module saves them.
+        const oldNotes = current.querySelector('.card-notes-input');
+        const newNotes = replacement.querySelector('.card-notes-input');
+        const typingNotes = oldNotes && document.activeElement === oldNotes;
+        if (oldNotes && newNotes && (typingNotes || oldNotes.dataset.dirty)) {
+          newNotes.value = oldNotes.value;
+          newNotes.dataset.notesRevision = oldNotes.dataset.notesRevision;
+          if (oldNotes.dataset.dirty) newNotes.dataset.dirty = oldNotes.dataset.dirty;
+        }
         current.replaceWith(replacement);
         processWithHtmx(replacement);
+        if (typingNotes && newNotes) newNotes.focus();
         notifyCardSwapped();
       }
     }
@@ -103,8 +148,9 @@
       if (!card || cardExists(card.dataset.itemId)) return;
       const container = document.getElementById(containerId);
       if (!container) return;
-      container.insertBefore(card, container.firstChild);
+      container.appendChild(card);
       removeDuplicateCards();
+      sortColumn(container);
       processWithHtmx(card);
       notifyCardSwapped();
     }
@@ -114,6 +160,7 @@
       if (!card) return;
       const badge = card.querySelector('.like-count');
       if (badge) badge.textContent = String(count);
+      if (document.body.dataset.cardSort === 'likes') sortColumn(card.closest('.item-list'));
     }
 
     function parseEvent(event) {
@@ -174,9 +221,120 @@
       const data = parseEvent(event);
       if (!data) return;
       const card = document.querySelector('article.card[data-item-id="' + data.item_id + '"]');
+      if (!card || card.classList.contains('editing')) return; // keep an edit in progress
+      // Re-fetch rather than patch the text, so the "edited" marker appears.
+      fetchCardHtml(data.item_id, function(html) {
+        const current = document.querySelector('article.card[data-item-id="' + data.item_id + '"]');
+        if (current && current.classList.contains('editing')) return;
+        replaceCard(data.item_id, html);
+      });
+    });
+
+    // Move a card to the column of its (re-rendered) html.
+    function placeMovedCard(itemId, category, html) {
+      const current = document.querySelector('article.card[data-item-id="' + itemId + '"]');
+      if (current) current.remove();
+      insertCard(category.toLowerCase() + '-items', html);
+    }
+
+    source.addEventListener('ITEM_MOVED', function(event) {
+      if (appliedEventIds.has(event.lastEventId)) return;
+      const data = parseEvent(event);
+      if (!data) return;
+      const card = document.querySelector('article.card[data-item-id="' + data.item_id + '"]');
+      if (card && card.classList.contains('editing')) return; // keep an edit in progress
+      fetchCardHtml(data.item_id, function(html) {
+        placeMovedCard(data.item_id, data.new_category, html);
+      });
+    });
+
+    // Our own moves arrive from the move module with the response html.
+    document.body.addEventListener('card:moved', function(event) {
+      placeMovedCard(event.detail.itemId, event.detail.category, event.detail.html);
+    });
+
+    function placeReorderedCard(itemId, position) {
+      const card = document.querySelector('article.card[data-item-id="' + itemId + '"]');
       if (!card) return;
-      const text = card.querySelector('.card-text');
-      if (text) text.textContent = data.text;
+      card.dataset.position = position;
+      sortColumn(card.closest('.item-list'));
+    }
+
+    source.addEventListener('ITEM_REORDERED', function(event) {
+      if (appliedEventIds.has(event.lastEventId)) return;
+      const data = parseEvent(event);
+      if (!data) return;
+      placeReorderedCard(data.item_id, data.position);
+    });
+
+    // Our own reorders arrive from the move module with the new position.
+    document.body.addEventListener('card:reordered', function(event) {
+      placeReorderedCard(event.detail.itemId, event.detail.position);
+    });
+
+    // Comments and reactions only replace that part of the card, so a note
+    // someone is typing into the same card (and its running timer) is left
+    // alone.
+    function replaceCardPart(itemId, html, selector) {
+      const card = document.querySelector('article.card[data-item-id="' + itemId + '"]');
+      if (!card || card.classList.contains('editing')) return;
+      const template = document.createElement('template');
+      template.innerHTML = html.trim();
+      const incoming = template.content.querySelector(selector);
+      const current = card.querySelector(selector);
+      if (!incoming || !current) {
+        replaceCard(itemId, html);
+        return;
+      }
+      current.replaceWith(incoming);
+      processWithHtmx(incoming);
+      notifyCardSwapped();
+    }
+
+    ['ITEM_COMMENT_ADDED', 'ITEM_COMMENT_DELETED'].forEach(function(type) {
+      source.addEventListener(type, function(event) {
+        if (appliedEventIds.has(event.lastEventId)) return;
+        const data = parseEvent(event);
+        if (!data) return;
+        fetchCardHtml(data.item_id, function(html) {
+          replaceCardPart(data.item_id, html, '.card-comment-list');
+        });
+      });
+    });
+
+    // Discussion notes are applied by the notes module.
+    source.addEventListener('ITEM_NOTES_UPDATED', function(event) {
+      if (appliedEventIds.has(event.lastEventId)) return;
+      const data = parseEvent(event);
+      if (!data) return;
+      document.body.dispatchEvent(new CustomEvent('sse:notes-updated', { detail: data }));
+    });
+
+    ['ITEM_REACTED', 'ITEM_UNREACTED'].forEach(function(type) {
+      source.addEventListener(type, function(event) {
+        if (appliedEventIds.has(event.lastEventId)) return;
+        const data = parseEvent(event);
+        if (!data) return;
+        fetchCardHtml(data.item_id, function(html) {
+          replaceCardPart(data.item_id, html, '.card-reactions');
+        });
+      });
+    });
+
+    // A deleted card goes away with its timer. Our own deletions are swapped
+    // out by htmx already, but the all-done check applies to them, too.
+    source.addEventListener('ITEM_DELETED', function(event) {
+      const data = parseEvent(event);
+      if (!data) return;
+      const card = document.querySelector('article.card[data-item-id="' + data.item_id + '"]');
+      if (card) {
+        document.body.dispatchEvent(new CustomEvent('sse:timer-reset', {
+          detail: { itemId: data.item_id }
+        }));
+        card.remove();
+        notifyCardSwapped();
+      }
+      maybeShowArchiveModal();
     });
 
     source.addEventListener('ITEM_LIKED', function(event) {
@@ -194,15 +352,23 @@
     });
 
     // Timer events carry the authoritative deadline; the timer module renders
-    // the countdown from it.
+    // the countdown from it, or the frozen time left of a paused timer.
     source.addEventListener('TIMER_STARTED', handleTimerPayload);
     source.addEventListener('TIMER_EXTENDED', handleTimerPayload);
+    source.addEventListener('TIMER_PAUSED', handleTimerPayload);
+    source.addEventListener('TIMER_RESUMED', handleTimerPayload);
     function handleTimerPayload(event) {
       if (appliedEventIds.has(event.lastEventId)) return;
       const data = parseEvent(event);
       if (!data) return;
       const endsAt = Date.parse(data.ends_at);
       if (isNaN(endsAt)) return;
+      if (data.paused_at) {
+        document.body.dispatchEvent(new CustomEvent('sse:timer-paused', {
+          detail: { itemId: data.item_id, remainingMs: endsAt - Date.parse(data.paused_at) }
+        }));
+        return;
+      }
       document.body.dispatchEvent(new CustomEvent('sse:timer-updated', {
         detail: { itemId: data.item_id, endsAt: endsAt }
       }));
@@ -213,10 +379,133 @@
       const data = parseEvent(event);
       if (!data) return;
       document.body.dispatchEvent(new CustomEvent('sse:timer-elapsed', {
-        detail: { itemId: data.item_id }
+        detail: { itemId: data.item_id, endsAt: Date.parse(data.ends_at) }
       }));
     });
 
+    // Sent by the server a minute before the deadline, so every client alerts
+    // at the same moment.
+    source.addEventListener('TIMER_WARNING', function(event) {
+      const data = parseEvent(event);
+      if (!data) return;
+      document.body.dispatchEvent(new CustomEvent('sse:timer-warning', {
+        detail: { itemId: data.item_id, endsAt: Date.parse(data.ends_at) }
+      }));
+    });
+
+    // Session and phase timers: re-render them from the server, which has the
+    // authoritative deadlines and knows which controls the viewer gets.
+    ['RETRO_TIMER_STARTED', 'RETRO_TIMER_EXTENDED', 'RETRO_TIMER_ELAPSED', 'RETRO_TIMER_STOPPED'].forEach(function(type) {
+      source.addEventListener(type, function(event) {
+        if (appliedEventIds.has(event.lastEventId)) return;
+        fetch('/retro/' + slug + '/timers', { headers: { Accept: 'text/html' } })
+          .then(function(response) {
+            if (!response.ok) throw new Error('timers fetch failed: ' + response.status);
+            return response.text();
+          })
+          .then(function(html) {
+            const current = document.getElementById('retro-timers');
+            if (!current) return;
+            const template = document.createElement('template');
+            template.innerHTML = html.trim();
+            const updated = template.content.firstElementChild;
+            if (!updated) return;
+            current.replaceWith(updated);
+            processWithHtmx(updated);
+            document.body.dispatchEvent(new CustomEvent('sse:retro-timers-swapped'));
+          })
+          .catch(function(error) {
+            console.error('SSE: failed to fetch the session timers', error);
+          });
+      });
+    });
+
+    // Presence: the server sends the full participant list whenever someone
+    // joins or leaves (an ephemeral signal without an event id).
+    source.addEventListener('PARTICIPANTS', function(event) {
+      const data = parseEvent(event);
+      if (!data) return;
+      renderParticipants(data.participants);
+    });
+
+    function renderParticipants(participants) {
+      const bar = document.getElementById('participant-bar');
+      if (!bar) return;
+      const count = bar.querySelector('.participant-count');
+      if (count) count.textContent = participants.length + ' here';
+      const list = bar.querySelector('.participant-avatars');
+      if (!list) return;
+      list.replaceChildren.apply(list, participants.map(function(participant) {
+        const li = document.createElement('li');
+        li.title = participant.name;
+        if (participant.avatar_url) {
+          const img = document.createElement('img');
+          img.className = 'avatar';
+          img.src = participant.avatar_url;
+          img.alt = participant.name;
+          img.referrerPolicy = 'no-referrer';
+          li.appendChild(img);
+        } else {
+          const initials = document.createElement('span');
+          initials.className = 'avatar avatar-initials';
+          initials.setAttribute('role', 'img');
+          initials.setAttribute('aria-label', participant.name);
+          initials.textContent = participant.initials;
+          li.appendChild(initials);
+        }
+        return li;
+      }));
+    }
+
+    // Typing indicators: the server sends everyone currently drafting a card
+    // (ephemeral, like PARTICIPANTS); our own drafts are not shown.
+    source.addEventListener('TYPING', function(event) {
+      const data = parseEvent(event);
+      if (!data) return;
+      const ownId = Number(document.body.dataset.userId);
+      const namesByCategory = {};
+      data.typing.forEach(function(typist) {
+        if (typist.user_id === ownId) return;
+        (namesByCategory[typist.category] = namesByCategory[typist.category] || []).push(typist.name);
+      });
+      document.querySelectorAll('.typing-indicator').forEach(function(indicator) {
+        indicator.textContent = typingText(namesByCategory[indicator.dataset.typingCategory] || []);
+      });
+    });
+
+    function typingText(names) {
+      if (names.length === 0) return '';
+      if (names.length === 1) return names[0] + ' is typing…';
+      if (names.length === 2) return names[0] + ' and ' + names[1] + ' are typing…';
+      return names.length + ' people are typing…';
+    }
+
+    // Action items: the action items section fetches and places the item.
+    ['ACTION_ITEM_CREATED', 'ACTION_ITEM_UPDATED', 'ACTION_ITEM_COMPLETED', 'ACTION_ITEM_DELETED'].forEach(function(type) {
+      source.addEventListener(type, function(event) {
+        const data = parseEvent(event);
+        if (!data) return;
+        document.body.dispatchEvent(new CustomEvent('sse:action-item-changed', {
+          detail: { actionItemId: data.action_item_id, deleted: type === 'ACTION_ITEM_DELETED' }
+        }));
+      });
+    });
+
+    // Someone undid one of their actions; the reversal itself arrives as
+    // ordinary events.
+    source.addEventListener('ACTION_UNDONE', function(event) {
+      const data = parseEvent(event);
+      if (!data) return;
+      document.body.dispatchEvent(new CustomEvent('sse:action-undone', { detail: data }));
+    });
+
+    // Events this client missed while disconnected were deleted by retention,
+    // so it cannot catch up incrementally: start over with a fresh board.
+    source.addEventListener('RESET', function() {
+      source.close();
+      window.location.reload();
+    });
+
     // The retro was archived: clear the board and stop all timers (removing
     // the badges stops their countdowns).
     source.addEventListener('RETRO_ARCHIVED', function(event) {
@@ -230,6 +519,108 @@
     });
   })();
 
+  (function() {
+    // Optional alerts for the highlight timer: a beep and, while the tab is in
+    // the background, a desktop notification. Both are per-browser choices;
+    // the moments come from the server's TIMER_WARNING and TIMER_ELAPSED
+    // events. Events replayed after a reconnect are older than this and stay
+    // silent.
+    const STALE_ALERT_MS = 15000;
+    const soundKey = 'rostfacto:timer-alerts:sound';
+    const notifyKey = 'rostfacto:timer-alerts:notify';
+    const soundSwitch = document.querySelector('.timer-alerts-sound');
+    const notifySwitch = document.querySelector('.timer-alerts-notify');
+    const notifyBlocked = document.querySelector('.timer-alerts-notify-blocked');
+    if (!soundSwitch || !notifySwitch) return;
+    const canNotify = 'Notification' in window;
+
+    let audioContext = null;
+    // Browsers only let a page play sound after a user gesture, so the audio
+    // context is created (or resumed) on one.
+    function unlockAudio() {
+      if (!soundSwitch.checked) return;
+      const AudioContextClass = window.AudioContext || window.webkitAudioContext;
+      if (!AudioContextClass) return;
+      if (!audioContext) audioContext = new AudioContextClass();
+      if (audioContext.state === 'suspended') audioContext.resume();
+    }
+
+    function beep(times) {
+      if (!audioContext || audioContext.state !== 'running') return;
+      const start = audioContext.currentTime;
+      for (let i = 0; i < times; i++) {
+        const oscillator = audioContext.createOscillator();
+        const gain = audioContext.createGain();
+        oscillator.frequency.value = 880;
+        gain.gain.setValueAtTime(0.2, start + i * 0.3);
+        gain.gain.exponentialRampToValueAtTime(0.001, start + i * 0.3 + 0.2);
+        oscillator.connect(gain).connect(audioContext.destination);
+        oscillator.start(start + i * 0.3);
+        oscillator.stop(start + i * 0.3 + 0.2);
+      }
+    }
+
+    function showBlocked() {
+      if (notifyBlocked) notifyBlocked.hidden = !canNotify || Notification.permission !== 'denied';
+    }
+
+    function cardText(itemId) {
+      const text = document.querySelector('article.card[data-item-id="' + itemId + '"] .card-text');
+      return text ? text.textContent.trim() : '';
+    }
+
+    // Alert unless the event belongs to another moment than [fromMs, untilMs].
+    function raiseAlert(detail, title, fromMs, untilMs, times) {
+      const now = Date.now();
+      if (isNaN(untilMs) || now < fromMs - STALE_ALERT_MS || now > untilMs + STALE_ALERT_MS) return;
+      if (soundSwitch.checked) beep(times);
+      if (notifySwitch.checked && canNotify && Notification.permission === 'granted' && document.hidden) {
+        new Notification(title, { body: cardText(detail.itemId), tag: 'rostfacto-timer-' + detail.itemId });
+      }
+    }
+
+    soundSwitch.checked = localStorage.getItem(soundKey) === 'on';
+    notifySwitch.checked = canNotify && localStorage.getItem(notifyKey) === 'on'
+      && Notification.permission === 'granted';
+    notifySwitch.disabled = !canNotify;
+    showBlocked();
+
+    soundSwitch.addEventListener('change', function() {
+      if (soundSwitch.checked) {
+        localStorage.setItem(soundKey, 'on');
+        unlockAudio();
+        beep(1);
+      } else {
+        localStorage.removeItem(soundKey);
+      }
+    });
+    notifySwitch.addEventListener('change', function() {
+      if (!notifySwitch.checked) {
+        localStorage.removeItem(notifyKey);
+        return;
+      }
+      Notification.requestPermission().then(function(permission) {
+        notifySwitch.checked = permission === 'granted';
+        if (notifySwitch.checked) {
+          localStorage.setItem(notifyKey, 'on');
+        } else {
+          localStorage.removeItem(notifyKey);
+        }
+        showBlocked();
+      });
+    });
+    document.addEventListener('pointerdown', unlockAudio);
+    document.addEventListener('keydown', unlockAudio);
+
+    document.body.addEventListener('sse:timer-warning', function(event) {
+      // A timer resumed with less than a minute left is warned about late.
+      raiseAlert(event.detail, 'One minute left', event.detail.endsAt - 60000, event.detail.endsAt, 1);
+    });
+    document.body.addEventListener('sse:timer-elapsed', function(event) {
+      raiseAlert(event.detail, 'Time is up', event.detail.endsAt, event.detail.endsAt, 3);
+    });
+  })();
+
   (function() {
     // Server-authoritative countdown: the deadline comes from the DB
     // (timer_ends_at, rendered as data-end-at), never from a local map, so
@@ -249,6 +640,39 @@
       return m + ':' + String(s).padStart(2, '0');
     }
 
+    // The retro's timer settings, rendered on the board's body.
+    function timerSettings() {
+      const data = document.body.dataset;
+      return {
+        defaultSeconds: parseInt(data.timerDefaultSeconds || '300', 10),
+        extendSeconds: parseInt(data.timerExtendSeconds || '120', 10),
+        autoStart: data.timerAutoStart !== undefined
+      };
+    }
+
+    function formatStep(seconds) {
+      return seconds % 60 === 0 ? (seconds / 60) + ' min' : seconds + ' s';
+    }
+
+    // Show the timer buttons that apply to the state of the badge: start
+    // (unless highlighting starts the timer), extend once elapsed, and pause
+    // or resume (only rendered for facilitators).
+    function showControls(badge, state) {
+      const wrap = badge.closest('.timer-wrap');
+      if (!wrap) return;
+      const settings = timerSettings();
+      const show = function(selector, visible) {
+        const button = wrap.querySelector(selector);
+        if (button) button.hidden = !visible;
+      };
+      show('.timer-start', state === 'idle' && !settings.autoStart);
+      show('.timer-extend', state === 'elapsed');
+      show('.timer-pause', state === 'running');
+      show('.timer-resume', state === 'paused');
+      const extendBtn = wrap.querySelector('.timer-extend');
+      if (extendBtn) extendBtn.textContent = '+' + formatStep(settings.extendSeconds);
+    }
+
     function stopInterval(badge) {
       const interval = timerIntervals.get(badge);
       if (interval) {
@@ -258,18 +682,15 @@
     }
 
     function updateBadge(badge, remainingMs) {
-      const wrap = badge.closest('.timer-wrap');
-      const extendBtn = wrap ? wrap.querySelector('.timer-extend') : null;
-
       if (remainingMs <= 0) {
         badge.textContent = '0:00';
         badge.classList.remove('timer-warning');
         badge.classList.add('timer-over');
-        if (extendBtn) extendBtn.hidden = false;
+        showControls(badge, 'elapsed');
       } else {
         badge.textContent = formatTime(remainingMs / 1000);
         badge.classList.remove('timer-over');
-        if (extendBtn) extendBtn.hidden = true;
+        showControls(badge, 'running');
         if (remainingMs <= 30000) {
           badge.classList.add('timer-warning');
         } else {
@@ -280,6 +701,8 @@
 
     function setCountdown(badge, endAtMs) {
       stopInterval(badge);
+      badge.removeAttribute('data-paused');
+      badge.classList.remove('timer-paused');
       badge.dataset.endAt = String(endAtMs);
       const tick = function() {
         if (!badge.isConnected) {
@@ -298,12 +721,20 @@
 
     // Render one badge from its server-rendered state.
     function renderTimer(badge) {
+      if (badge.hasAttribute('data-paused')) {
+        stopInterval(badge);
+        badge.textContent = formatTime(parseInt(badge.dataset.remainingSeconds || '0', 10));
+        badge.classList.remove('timer-over');
+        badge.classList.remove('timer-warning');
+        badge.classList.add('timer-paused');
+        showControls(badge, 'paused');
+        return;
+      }
       if (badge.hasAttribute('data-elapsed')) {
         badge.textContent = '0:00';
         badge.classList.remove('timer-warning');
         badge.classList.add('timer-over');
-        const extendBtn = badge.closest('.timer-wrap') ? badge.closest('.timer-wrap').querySelector('.timer-extend') : null;
-        if (extendBtn) extendBtn.hidden = false;
+        showControls(badge, 'elapsed');
         stopInterval(badge);
         return;
       }
@@ -321,12 +752,12 @@
           setCountdown(badge, knownEndAt);
           return;
         }
-        // Highlighted but not started yet: show the initial duration statically.
-        badge.textContent = formatTime(parseInt(badge.dataset.initialSeconds || '300', 10));
+        // Highlighted but not started yet: show the retro's discussion
+        // length statically.
+        badge.textContent = formatTime(timerSettings().defaultSeconds);
         badge.classList.remove('timer-over');
         badge.classList.remove('timer-warning');
-        const extendBtn = badge.closest('.timer-wrap') ? badge.closest('.timer-wrap').querySelector('.timer-extend') : null;
-        if (extendBtn) extendBtn.hidden = true;
+        showControls(badge, 'idle');
         stopInterval(badge);
       }
     }
@@ -335,27 +766,20 @@
       document.querySelectorAll('.timer-badge').forEach(renderTimer);
     }
 
-    // Timer responses update the badge in place: replacing the whole card
+    // Timer responses update the timer in place: replacing the whole card
     // would detach it and break in-flight HTMX swaps (e.g. a quick "Done"
     // click after highlighting).
     function applyTimerResponseHtml(itemId, html) {
       const template = document.createElement('template');
       template.innerHTML = html.trim();
-      const newBadge = template.content.querySelector('.timer-badge');
-      const newExtend = template.content.querySelector('.timer-extend');
+      const newWrap = template.content.querySelector('.timer-wrap');
       const current = document.querySelector('article.card[data-item-id="' + itemId + '"]');
-      if (!current || !newBadge) return;
-      const oldBadge = current.querySelector('.timer-badge');
-      if (!oldBadge) return;
-      const wrap = oldBadge.closest('.timer-wrap');
-      if (wrap) {
-        const oldExtend = wrap.querySelector('.timer-extend');
-        oldBadge.replaceWith(newBadge);
-        if (oldExtend && newExtend) oldExtend.replaceWith(newExtend);
-      } else {
-        oldBadge.replaceWith(newBadge);
-      }
-      renderTimer(newBadge);
+      if (!current || !newWrap) return;
+      const oldWrap = current.querySelector('.timer-wrap');
+      if (!oldWrap) return;
+      oldWrap.replaceWith(newWrap);
+      const newBadge = newWrap.querySelector('.timer-badge');
+      if (newBadge) renderTimer(newBadge);
     }
 
     function postTimerAction(itemId, path, params) {
@@ -383,11 +807,10 @@
       });
     }
 
-    function startTimerRequest(itemId, durationSeconds) {
+    // The server starts the timer for the retro's discussion length.
+    function startTimerRequest(itemId) {
       pendingTimerPosts.set(String(itemId), true);
-      const params = new URLSearchParams();
-      params.set('duration', String(durationSeconds));
-      postTimerAction(itemId, '/items/' + itemId + '/timer/start', params);
+      postTimerAction(itemId, '/items/' + itemId + '/timer/start', null);
     }
 
     function extendTimerRequest(itemId) {
@@ -402,7 +825,7 @@
     // rather than using the request element.
     document.body.addEventListener('htmx:afterRequest', function(event) {
       const detail = event.detail;
-      if (!detail || !detail.successful) return;
+      if (!detail || !detail.successful || !timerSettings().autoStart) return;
       const path = (detail.pathInfo && detail.pathInfo.requestPath) || '';
       if (path.indexOf('action=highlight') === -1) return;
       const elt = (detail.requestConfig && detail.requestConfig.elt) || detail.elt;
@@ -412,8 +835,7 @@
       if (!card) return; // e.g. the single-highlight conflict re-rendered the created card
       const badge = card.querySelector('.timer-badge');
       if (!badge || badge.hasAttribute('data-end-at') || badge.hasAttribute('data-elapsed')) return;
-      const duration = parseInt(document.body.dataset.timerDefaultSeconds || '300', 10);
-      startTimerRequest(itemId, duration);
+      startTimerRequest(itemId);
     });
 
     // Another client's timer event: remember the authoritative deadline and
@@ -427,6 +849,21 @@
       if (badge) setCountdown(badge, event.detail.endsAt);
     });
 
+    // Another client paused the timer: freeze the time left. A stale render
+    // must not resume the countdown, so forget the deadline, too.
+    document.body.addEventListener('sse:timer-paused', function(event) {
+      timerDeadlines.delete(String(event.detail.itemId));
+      const card = document.querySelector('article.card[data-item-id="' + event.detail.itemId + '"]');
+      if (!card) return;
+      const badge = card.querySelector('.timer-badge');
+      if (!badge) return;
+      stopInterval(badge);
+      badge.removeAttribute('data-end-at');
+      badge.setAttribute('data-paused', '');
+      badge.dataset.remainingSeconds = String(Math.max(0, Math.round(event.detail.remainingMs / 1000)));
+      renderTimer(badge);
+    });
+
     // A status change ends the previous timer cycle (completing or cancelling
     // resets the timer columns); forget its deadline so a stale render of the
     // next highlight cannot pick it up.
@@ -443,7 +880,7 @@
       const itemId = elt && elt.dataset ? elt.dataset.itemId : null;
       if (itemId) timerDeadlines.delete(itemId);
       const path = (event.detail && event.detail.requestConfig && event.detail.requestConfig.path) || '';
-      if (path.indexOf('action=highlight') === -1) return;
+      if (path.indexOf('action=highlight') === -1 || !timerSettings().autoStart) return;
       if (itemId) armHighlightFallback(String(itemId));
     });
 
@@ -489,8 +926,7 @@
           highlightFallbacks.delete(key);
           return;
         }
-        const duration = parseInt(document.body.dataset.timerDefaultSeconds || '300', 10);
-        startTimerRequest(key, duration);
+        startTimerRequest(key);
       }, 1500);
       highlightFallbacks.set(key, interval);
     }
@@ -506,8 +942,7 @@
         // Keep the server-rendered deadline on the badge: it is the
         // authoritative record of when the timer ended, and a stale card
         // re-fetch may render an elapsed badge without a deadline of its own.
-        const extendBtn = card.querySelector('.timer-extend');
-        if (extendBtn) extendBtn.hidden = false;
+        showControls(badge, 'elapsed');
         stopInterval(badge);
       }
     });
@@ -515,12 +950,22 @@
     document.addEventListener('DOMContentLoaded', renderAllTimers);
     document.body.addEventListener('htmx:afterSettle', renderAllTimers);
     document.body.addEventListener('sse:card-swapped', renderAllTimers);
+    document.body.addEventListener('sse:retro-timers-swapped', renderAllTimers);
     document.body.addEventListener('click', function(e) {
-      const button = e.target.closest('.timer-extend');
-      if (button) {
-        e.stopPropagation();
-        const card = button.closest('article.card');
-        if (card && card.dataset.itemId) extendTimerRequest(card.dataset.itemId);
+      const button = e.target.closest('.timer-extend, .timer-start, .timer-pause, .timer-resume');
+      if (!button) return;
+      e.stopPropagation();
+      const card = button.closest('article.card');
+      if (!card || !card.dataset.itemId) return;
+      const itemId = card.dataset.itemId;
+      if (button.classList.contains('timer-extend')) {
+        extendTimerRequest(itemId);
+      } else if (button.classList.contains('timer-start')) {
+        startTimerRequest(itemId);
+      } else if (button.classList.contains('timer-pause')) {
+        postTimerAction(itemId, '/items/' + itemId + '/timer/pause', null);
+      } else {
+        postTimerAction(itemId, '/items/' + itemId + '/timer/resume', null);
       }
     });
   })();
@@ -597,10 +1042,25 @@
       return date.toLocaleDateString(undefined, { month: 'long', day: 'numeric' });
     }
 
+    // New items land in the pool (added here, or fetched after another
+    // client's change); grouping sorts all items into the columns, oldest
+    // first like the server renders them, and drops duplicates from the HTMX
+    // swap and the SSE fetch of the same item.
     function groupActionItems() {
       const now = new Date();
       const todayKey = localDateKey(now);
-      const items = Array.from(pool.querySelectorAll('.action-item'));
+      const seen = new Set();
+      const items = Array.from(section.querySelectorAll('.action-item'))
+        .filter(item => {
+          const id = item.dataset.actionItemId;
+          if (seen.has(id)) {
+            item.remove();
+            return false;
+          }
+          seen.add(id);
+          return true;
+        })
+        .sort((a, b) => new Date(a.dataset.createdAt) - new Date(b.dataset.createdAt));
       if (items.length === 0) return;
       const priorDates = items
         .map(item => new Date(item.dataset.createdAt))
@@ -624,17 +1084,194 @@
         groupActionItems();
       }
     });
+
+    function findActionItem(id) {
+      return section.querySelector('.action-item[data-action-item-id="' + id + '"]');
+    }
+
+    document.body.addEventListener('sse:action-item-changed', function(event) {
+      const id = event.detail.actionItemId;
+      if (event.detail.deleted) {
+        const current = findActionItem(id);
+        if (current) current.remove();
+        return;
+      }
+      fetch('/action-items/' + id, { headers: { Accept: 'text/html' } })
+        .then(function(response) {
+          if (!response.ok) throw new Error('action item fetch failed: ' + response.status);
+          return response.text();
+        })
+        .then(function(html) {
+          const template = document.createElement('template');
+          template.innerHTML = html.trim();
+          const replacement = template.content.firstElementChild;
+          if (!replacement) return;
+          const current = findActionItem(id);
+          if (current && current.classList.contains('editing')) return; // keep an edit in progress
+          if (current) {
+            current.replaceWith(replacement);
+          } else {
+            pool.appendChild(replacement);
+          }
+          if (window.htmx && window.htmx.process) window.htmx.process(replacement);
+          groupActionItems();
+        })
+        .catch(function(error) {
+          console.error('SSE: failed to fetch action item', id, error);
+        });
+    });
+  })();
+
+  (function() {
+    // Discussion notes of the highlighted card: everyone edits the same text
+    // and the last save wins. Typing saves after a short pause; updates from
+    // others replace the text unless it has unsaved changes here, and
+    // notes_revision drops updates that arrive out of order.
+    const SAVE_DELAY_MS = 700;
+    const saveTimeouts = {}; // item id -> timeout
+
+    function notesInput(itemId) {
+      return document.querySelector('article.card[data-item-id="' + itemId + '"] .card-notes-input');
+    }
+
+    function saveNotes(itemId) {
+      const input = notesInput(itemId);
+      if (!input) return;
+      const notes = input.value;
+      const body = new URLSearchParams();
+      body.set('notes', notes);
+      fetch('/items/' + itemId + '/notes', {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/x-www-form-urlencoded' },
+        body: body.toString()
+      }).then(function(response) {
+        if (!response.ok) throw new Error('notes save failed: ' + response.status);
+        const eventId = response.headers.get('X-Event-Id');
+        if (eventId) {
+          document.body.dispatchEvent(new CustomEvent('sse:event-applied', { detail: { id: eventId } }));
+        }
+        return response.json();
+      }).then(function(data) {
+        const current = notesInput(itemId);
+        if (!current) return;
+        current.dataset.notesRevision = String(data.notes_revision);
+        // Typing may have gone on while saving; that text is saved next.
+        if (current.value === notes) delete current.dataset.dirty;
+      }).catch(function(error) {
+        console.error('failed to save notes', itemId, error);
+      });
+    }
+
+    document.addEventListener('input', function(event) {
+      const input = event.target;
+      if (!input.matches || !input.matches('.card-notes-input')) return;
+      const itemId = input.closest('article.card').dataset.itemId;
+      input.dataset.dirty = '1';
+      clearTimeout(saveTimeouts[itemId]);
+      saveTimeouts[itemId] = setTimeout(function() {
+        saveNotes(itemId);
+      }, SAVE_DELAY_MS);
+    });
+
+    document.body.addEventListener('sse:notes-updated', function(event) {
+      const data = event.detail;
+      const input = notesInput(data.item_id);
+      if (!input || input.dataset.dirty) return;
+      if (Number(input.dataset.notesRevision) >= data.notes_revision) return;
+      const focused = document.activeElement === input;
+      const start = input.selectionStart;
+      const end = input.selectionEnd;
+      input.value = data.notes;
+      input.dataset.notesRevision = String(data.notes_revision);
+      if (focused) input.setSelectionRange(start, end);
+    });
+  })();
+
+  (function() {
+    // Undo: mutations that can be undone answer with an X-Undo header, shown
+    // in a toast with an Undo button. Each undo takes back the user's most
+    // recent undoable action, so repeating it walks further back.
+    const toast = document.getElementById('undo-toast');
+    const slug = document.body.dataset.retroSlug;
+    if (!toast || !slug) return;
+    const message = toast.querySelector('.undo-toast-message');
+    const button = toast.querySelector('.undo-toast-button');
+    const TOAST_DURATION_MS = 8000;
+    const UNDONE_MESSAGES = {
+      ITEM_STATUS_CHANGED: 'Card highlighted again',
+      ACTION_ITEM_DELETED: 'Action item restored',
+      ACTION_ITEM_UPDATED: 'Action item text restored'
+    };
+    let hideTimeout = null;
+
+    function showToast(text, canUndo) {
+      message.textContent = text;
+      button.hidden = !canUndo;
+      toast.hidden = false;
+      clearTimeout(hideTimeout);
+      hideTimeout = setTimeout(function() {
+        toast.hidden = true;
+      }, TOAST_DURATION_MS);
+    }
+
+    function undo() {
+      button.disabled = true;
+      fetch('/retro/' + slug + '/undo', { method: 'POST' })
+        .then(function(response) {
+          if (response.ok) {
+            return response.json().then(function(data) {
+              showToast(UNDONE_MESSAGES[data.undone_event_type] || 'Undone', data.remaining > 0);
+            });
+          }
+          return response.text().then(function(text) {
+            showToast(text || 'Undo failed', false);
+          });
+        })
+        .catch(function(error) {
+          console.error('undo failed', error);
+          showToast('Undo failed', false);
+        })
+        .finally(function() {
+          button.disabled = false;
+        });
+    }
+
+    button.addEventListener('click', undo);
+
+    document.body.addEventListener('htmx:afterRequest', function(event) {
+      const xhr = event.detail && event.detail.xhr;
+      if (!xhr || !event.detail.successful) return;
+      const undoMessage = xhr.getResponseHeader('X-Undo');
+      if (undoMessage) showToast(undoMessage, true);
+    });
+
+    document.body.addEventListener('sse:action-undone', function(event) {
+      const data = event.detail;
+      if (data.skipped || data.user_id === Number(document.body.dataset.userId)) return;
+      showToast(data.user_name + ' undid a change', false);
+    });
+
+    document.addEventListener('keydown', function(event) {
+      if (event.key !== 'z' && event.key !== 'Z') return;
+      if (event.metaKey || event.ctrlKey || event.altKey) return;
+      const target = event.target;
+      if (target.closest && target.closest('input, textarea, select, [contenteditable], dialog[open]')) return;
+      event.preventDefault();
+      undo();
+    });
   })();
 
   (function() {
     // hx-on replacements: the inline event handlers were removed so the page
     // can run under a strict Content-Security-Policy (no unsafe-inline/eval).
 
-    // Clicking a like or edit button inside a created card must not also
-    // trigger the card's hx-post (highlight). The guard stops the click from
-    // bubbling to the card; htmx attaches its own listener to the button.
+    // Clicking a like, edit, history, comment delete or action item button
+    // (or inside the history popover, the reactions or the notes) in a card
+    // must not also trigger the card's hx-post (highlight).
+    // The guard stops the click from bubbling to the card; htmx attaches its
+    // own listener to the button.
     function installClickGuards() {
-      document.querySelectorAll('.like-button, .card-text-edit').forEach(function(button) {
+      document.querySelectorAll('.like-button, .card-text-edit, .card-history-toggle, .card-history, .card-comment-delete, .card-reactions, .card-notes, .card-to-action-item').forEach(function(button) {
         if (button.dataset.clickGuard) return;
         button.dataset.clickGuard = '1';
         button.addEventListener('click', function(event) {
@@ -646,6 +1283,244 @@
     document.body.addEventListener('htmx:afterSettle', installClickGuards);
     document.body.addEventListener('sse:card-swapped', installClickGuards);
 
+    // The delete button and its confirmation dialog are inside the card, too.
+    // Their clicks cannot bubble up to the site-wide dialog wiring either, so
+    // they open and close the dialog themselves; submitting still reaches htmx.
+    function installDeleteGuards() {
+      document.querySelectorAll('.card-delete, .card-delete-dialog').forEach(function(element) {
+        if (element.dataset.clickGuard) return;
+        element.dataset.clickGuard = '1';
+        element.addEventListener('click', function(event) {
+          event.stopPropagation();
+          const opener = event.target.closest('[data-open-dialog]');
+          const closer = event.target.closest('[data-close-dialog]');
+          if (opener) {
+            const dialog = document.getElementById(opener.getAttribute('data-open-dialog'));
+            if (dialog && typeof dialog.showModal === 'function') dialog.showModal();
+          } else if (closer) {
+            closer.closest('dialog').close();
+          }
+        });
+      });
+    }
+    installDeleteGuards();
+    document.body.addEventListener('htmx:afterSettle', installDeleteGuards);
+    document.body.addEventListener('sse:card-swapped', installDeleteGuards);
+
+    // Moving cards between columns: drag a card onto another column, or press
+    // Shift+Left / Shift+Right on a focused card. Only cards the edit policy
+    // lets us change are movable; the server checks again.
+    function moveCard(card, category) {
+      const list = card.closest('.item-list');
+      if (!list || list.dataset.category === category || !card.hasAttribute('data-movable')) return;
+      const itemId = card.dataset.itemId;
+      const body = new URLSearchParams();
+      body.set('category', category);
+      fetch('/items/' + itemId + '/move', {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/x-www-form-urlencoded' },
+        body: body.toString()
+      }).then(function(response) {
+        if (!response.ok) throw new Error('move failed: ' + response.status);
+        const eventId = response.headers.get('X-Event-Id');
+        if (eventId) {
+          document.body.dispatchEvent(new CustomEvent('sse:event-applied', { detail: { id: eventId } }));
+        }
+        return response.text();
+      }).then(function(html) {
+        document.body.dispatchEvent(new CustomEvent('card:moved', {
+          detail: { itemId: itemId, category: category, html: html }
+        }));
+        const moved = document.querySelector('article.card[data-item-id="' + itemId + '"]');
+        if (moved) moved.focus();
+      }).catch(function(error) {
+        console.error('failed to move card', itemId, error);
+      });
+    }
+
+    // Facilitators also set the order within a column: drag a card up or
+    // down, or press Shift+Up / Shift+Down. The card is placed right after
+    // the card now above it; the order only applies while sorting manually.
+    function canReorder() {
+      return document.body.hasAttribute('data-facilitator') &&
+        document.body.dataset.cardSort !== 'likes';
+    }
+
+    function reorderCard(card) {
+      const itemId = card.dataset.itemId;
+      let previous = card.previousElementSibling;
+      while (previous && !previous.matches('article.card')) previous = previous.previousElementSibling;
+      const body = new URLSearchParams();
+      if (previous) body.set('after', previous.dataset.itemId);
+      fetch('/items/' + itemId + '/reorder', {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/x-www-form-urlencoded' },
+        body: body.toString()
+      }).then(function(response) {
+        if (!response.ok) throw new Error('reorder failed: ' + response.status);
+        const eventId = response.headers.get('X-Event-Id');
+        if (eventId) {
+          document.body.dispatchEvent(new CustomEvent('sse:event-applied', { detail: { id: eventId } }));
+        }
+        return response.text();
+      }).then(function(html) {
+        const template = document.createElement('template');
+        template.innerHTML = html.trim();
+        const reordered = template.content.firstElementChild;
+        if (!reordered) return;
+        document.body.dispatchEvent(new CustomEvent('card:reordered', {
+          detail: { itemId: itemId, position: reordered.dataset.position }
+        }));
+      }).catch(function(error) {
+        console.error('failed to reorder card', itemId, error);
+        document.body.dispatchEvent(new CustomEvent('card:sort')); // put it back
+      });
+    }
+
+    // The card the dragged one goes in front of, judged by the pointer.
+    function cardBelowPointer(list, dragged, y) {
+      const cards = Array.from(list.querySelectorAll(':scope > article.card'));
+      return cards.find(function(card) {
+        if (card === dragged) return false;
+        const rect = card.getBoundingClientRect();
+        return y < rect.top + rect.height / 2;
+      }) || null;
+    }
+
+    let dropped = false;
+
+    document.addEventListener('dragstart', function(event) {
+      const card = event.target.closest && event.target.closest('article.card[draggable="true"]');
+      if (!card) return;
+      event.dataTransfer.setData('text/plain', card.dataset.itemId);
+      event.dataTransfer.effectAllowed = 'move';
+      card.classList.add('dragging');
+      dropped = false;
+    });
+
+    document.addEventListener('dragend', function(event) {
+      const card = event.target.closest && event.target.closest('article.card');
+      if (card) card.classList.remove('dragging');
+      document.querySelectorAll('.drop-target').forEach(function(column) {
+        column.classList.remove('drop-target');
+      });
+      // A cancelled drag leaves the card where it was dragged within its column.
+      if (!dropped) document.body.dispatchEvent(new CustomEvent('card:sort'));
+    });
+
+    document.querySelectorAll('.item-list').forEach(function(list) {
+      const column = list.parentElement;
+      column.addEventListener('dragover', function(event) {
+        const card = document.querySelector('article.card.dragging');
+        if (!card) return;
+        event.preventDefault();
+        event.dataTransfer.dropEffect = 'move';
+        if (card.closest('.item-list') === list) {
+          if (!canReorder()) return;
+          const next = cardBelowPointer(list, card, event.clientY);
+          if (next !== card.nextElementSibling) list.insertBefore(card, next);
+          return;
+        }
+        if (card.hasAttribute('data-movable')) column.classList.add('drop-target');
+      });
+      column.addEventListener('dragleave', function(event) {
+        if (!column.contains(event.relatedTarget)) column.classList.remove('drop-target');
+      });
+      column.addEventListener('drop', function(event) {
+        event.preventDefault();
+        column.classList.remove('drop-target');
+        const card = document.querySelector('article.card.dragging');
+        if (!card) return;
+        dropped = true;
+        if (card.closest('.item-list') === list) {
+          if (canReorder()) reorderCard(card);
+        } else {
+          moveCard(card, list.dataset.category);
+        }
+      });
+    });
+
+    document.addEventListener('keydown', function(event) {
+      if (!event.shiftKey || (event.key !== 'ArrowLeft' && event.key !== 'ArrowRight')) return;
+      const card = event.target;
+      if (!card.matches || !card.matches('article.card[draggable="true"]')) return;
+      const lists = Array.from(document.querySelectorAll('.item-list'));
+      const index = lists.indexOf(card.closest('.item-list'));
+      const target = lists[index + (event.key === 'ArrowLeft' ? -1 : 1)];
+      if (index === -1 || !target) return;
+      event.preventDefault();
+      moveCard(card, target.dataset.category);
+    });
+
+    document.addEventListener('keydown', function(event) {
+      if (!event.shiftKey || (event.key !== 'ArrowUp' && event.key !== 'ArrowDown')) return;
+      const card = event.target;
+      if (!card.matches || !card.matches('article.card[draggable="true"]') || !canReorder()) return;
+      const sibling = event.key === 'ArrowUp' ? card.previousElementSibling : card.nextElementSibling;
+      if (!sibling || !sibling.matches('article.card')) return;
+      event.preventDefault();
+      card.parentElement.insertBefore(card, event.key === 'ArrowUp' ? sibling : sibling.nextElementSibling);
+      card.focus();
+      reorderCard(card);
+    });
+
+    // Sorting by likes is a per-browser view of the board; it is remembered
+    // per retro.
+    const sortToggle = document.querySelector('.card-sort-toggle');
+    if (sortToggle) {
+      const sortKey = 'rostfacto:card-sort:' + document.body.dataset.retroSlug;
+      const applySort = function(byLikes) {
+        if (byLikes) {
+          document.body.dataset.cardSort = 'likes';
+        } else {
+          delete document.body.dataset.cardSort;
+        }
+        sortToggle.setAttribute('aria-pressed', String(byLikes));
+        document.body.dispatchEvent(new CustomEvent('card:sort'));
+      };
+      applySort(localStorage.getItem(sortKey) === 'likes');
+      sortToggle.addEventListener('click', function() {
+        const byLikes = document.body.dataset.cardSort !== 'likes';
+        if (byLikes) {
+          localStorage.setItem(sortKey, 'likes');
+        } else {
+          localStorage.removeItem(sortKey);
+        }
+        applySort(byLikes);
+      });
+    }
+
+    // Announce drafting a card so others see a typing indicator. The server
+    // throttles and expires announcements; announcing every second while
+    // typing keeps the indicator alive.
+    const TYPING_ANNOUNCE_INTERVAL_MS = 1000;
+    const lastTypingAnnouncement = {}; // category -> epoch ms
+    function announceTyping(form, stopped) {
+      const category = form.dataset.category;
+      const slug = document.body.dataset.retroSlug;
+      if (!category || !slug) return;
+      const now = Date.now();
+      if (!stopped && now - (lastTypingAnnouncement[category] || 0) < TYPING_ANNOUNCE_INTERVAL_MS) return;
+      if (stopped && !lastTypingAnnouncement[category]) return;
+      lastTypingAnnouncement[category] = stopped ? 0 : now;
+      const body = new URLSearchParams({ category: category });
+      if (stopped) body.set('stopped', 'true');
+      fetch('/retro/' + slug + '/typing', { method: 'POST', body: body })
+        .catch(function(error) {
+          console.error('typing announcement failed', error);
+        });
+    }
+    document.body.addEventListener('input', function(event) {
+      const target = event.target;
+      if (!target.matches || !target.matches('textarea.add-card-input')) return;
+      announceTyping(target.closest('form'), target.value.trim() === '');
+    });
+    document.body.addEventListener('focusout', function(event) {
+      const target = event.target;
+      if (!target.matches || !target.matches('textarea.add-card-input')) return;
+      announceTyping(target.closest('form'), true);
+    });
+
     // Reset add-card and action-item forms after a successful submission
     // (replaces hx-on::after-request on those forms).
     document.body.addEventListener('htmx:afterRequest', function(event) {
@@ -654,6 +1529,9 @@
       if (elt.matches('form.add-card-form, form.action-items-form')) {
         elt.reset();
       }
+      if (elt.matches('form.add-card-form')) {
+        announceTyping(elt, true);
+      }
     });
 
     // Keyboard shortcuts on card text inputs (replaces hx-on:keydown on the
@@ -0,0 +1,336 @@
+mod test_helpers;
+
+use reqwest::Client;
+use sqlx::PgPool;
+use test_helpers::*;
+
+struct TestContext {
+    _db: TestDb,
+    _server: TestServer,
+    client: Client,
+    pool: PgPool,
+    base_url: String,
+}
+
+async fn setup() -> TestContext {
+    let db = TestDb::new().await;
+    let server = TestServer::start(&db.database_url).await;
+    let base_url = server.base_url();
+    let pool = PgPool::connect(&db.database_url)
+        .await
+        .expect("Failed to connect to test DB");
+    TestContext {
+        _db: db,
+        _server: server,
+        client: Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build HTTP client"),
+        pool,
+        base_url,
+    }
+}
+
+async fn create_retro(ctx: &TestContext, slug: &str) -> i32 {
+    let response = ctx
+        .client
+        .post(format!("{}/retros", ctx.base_url))
+        .header("User-Agent", "admin-test-agent")
+        .header("X-Forwarded-For", "203.0.113.9, 10.0.0.1")
+        .form(&[("title", "Audit Test"), ("slug", slug)])
+        .send()
+        .await
+        .expect("Failed to create retro");
+    assert_eq!(
+        response.status(),
+        reqwest::StatusCode::SEE_OTHER,
+        "creating a retro should redirect"
+    );
+    sqlx::query_scalar!("SELECT id FROM retrospectives WHERE slug = $1", slug)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Created retro should exist")
+}
+
+async fn audit_actions(ctx: &TestContext, target: &str) -> Vec<String> {
+    sqlx::query_scalar!(
+        r#"SELECT action::text as "action!" FROM audit_log WHERE target = $1 ORDER BY id"#,
+        target
+    )
+    .fetch_all(&ctx.pool)
+    .await
+    .expect("Failed to load audit log")
+}
+
+#[tokio::test]
+async fn retro_lifecycle_is_written_to_the_audit_log() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "audit-lifecycle").await;
+
+    let response = ctx
+        .client
+        .post(format!("{}/items/Good/{}", ctx.base_url, retro_id))
+        .form(&[("text", "Worth archiving")])
+        .send()
+        .await
+        .expect("Failed to add item");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    let response = ctx
+        .client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive retro");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+
+    let response = ctx
+        .client
+        .delete(format!("{}/retro/audit-lifecycle/delete", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to delete retro");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    assert_eq!(
+        audit_actions(&ctx, "audit-lifecycle").await,
+        vec!["RETRO_CREATED", "RETRO_ARCHIVED", "RETRO_DELETED"],
+        "every retro lifecycle step should be audited, even after the retro is gone"
+    );
+
+    let created = sqlx::query!(
+        r#"SELECT a.ip_address, a.user_agent, a.details, u.username as "username?"
+           FROM audit_log a LEFT JOIN users u ON u.id = a.actor_id
+           WHERE a.target = 'audit-lifecycle' AND a.action = 'RETRO_CREATED'"#
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Creation should be audited");
+    assert_eq!(created.username.as_deref(), Some("demo"));
+    assert_eq!(created.ip_address.as_deref(), Some("203.0.113.9"));
+    assert_eq!(created.user_agent.as_deref(), Some("admin-test-agent"));
+    assert_eq!(created.details["title"], "Audit Test");
+}
+
+#[tokio::test]
+async fn sign_out_is_written_to_the_audit_log() {
+    let ctx = setup().await;
+
+    let response = ctx
+        .client
+        .post(format!("{}/auth/logout", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to sign out");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+
+    assert_eq!(audit_actions(&ctx, "demo").await, vec!["USER_SIGNED_OUT"]);
+}
+
+#[tokio::test]
+async fn audit_page_filters_entries() {
+    let ctx = setup().await;
+    create_retro(&ctx, "audit-kept").await;
+    create_retro(&ctx, "audit-other").await;
+    let response = ctx
+        .client
+        .delete(format!("{}/retro/audit-other/delete", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to delete retro");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    let html = ctx
+        .client
+        .get(format!("{}/admin/audit", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to load audit page")
+        .text()
+        .await
+        .expect("Audit page should be HTML");
+    assert!(html.contains("audit-kept"));
+    assert!(html.contains("audit-other"));
+
+    let html = ctx
+        .client
+        .get(format!(
+            "{}/admin/audit?action=RETRO_DELETED&actor=&target=&from=&to=",
+            ctx.base_url
+        ))
+        .send()
+        .await
+        .expect("Failed to load filtered audit page")
+        .text()
+        .await
+        .expect("Audit page should be HTML");
+    assert!(html.contains("audit-other"));
+    assert!(
+        !html.contains("audit-kept"),
+        "filtering by action should hide other entries"
+    );
+
+    let response = ctx
+        .client
+        .get(format!("{}/admin/audit?from=yesterday", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to load audit page");
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn audit_log_exports_as_csv() {
+    let ctx = setup().await;
+    create_retro(&ctx, "audit-csv").await;
+
+    let response = ctx
+        .client
+        .get(format!("{}/admin/audit.csv?target=audit-csv", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to export audit log");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(response
+        .headers()
+        .get("content-type")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/csv")));
+    assert!(response
+        .headers()
+        .get("content-disposition")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("attachment")));
+
+    let csv = response.text().await.expect("CSV body");
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "timestamp,actor,action,target,ip_address,user_agent,details"
+    );
+    assert_eq!(lines.len(), 2, "only the filtered entry should be exported");
+    assert!(lines[1].contains(",demo,RETRO_CREATED,audit-csv,203.0.113.9,admin-test-agent,"));
+}
+
+async fn insert_user_with_sessions(ctx: &TestContext, username: &str, sessions: usize) -> i32 {
+    let user_id = sqlx::query_scalar!(
+        "INSERT INTO users (github_id, username) VALUES ($1, $2) RETURNING id",
+        rand::random::<i32>() as i64,
+        username
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to insert user");
+    for _ in 0..sessions {
+        sqlx::query!(
+            "INSERT INTO sessions (user_id, expires_at, is_admin) VALUES ($1, NOW() + interval '1 day', false)",
+            user_id
+        )
+        .execute(&ctx.pool)
+        .await
+        .expect("Failed to insert session");
+    }
+    user_id
+}
+
+async fn session_count(ctx: &TestContext, user_id: i32) -> i64 {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM sessions WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to count sessions")
+}
+
+#[tokio::test]
+async fn admin_console_lists_users_sessions_and_team_stats() {
+    let ctx = setup().await;
+    insert_user_with_sessions(&ctx, "octocat", 2).await;
+    create_retro(&ctx, "console-retro").await;
+
+    let response = ctx
+        .client
+        .get(format!("{}/admin", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to load admin console");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let html = response.text().await.expect("Admin console should be HTML");
+    assert!(html.contains("octocat"));
+    assert_eq!(
+        html.matches("/admin/sessions/").count(),
+        2,
+        "each active session should offer a revoke action"
+    );
+    assert!(html.contains("Retros by team"));
+    assert!(
+        html.contains("<td>demo</td>"),
+        "team stats should list the demo team"
+    );
+    assert!(html.contains("Live connections"));
+    assert!(html.contains("Slow subscribers dropped"));
+}
+
+#[tokio::test]
+async fn admin_can_revoke_a_single_session() {
+    let ctx = setup().await;
+    let user_id = insert_user_with_sessions(&ctx, "hubot", 2).await;
+    let public_id = sqlx::query_scalar!(
+        "SELECT public_id FROM sessions WHERE user_id = $1 LIMIT 1",
+        user_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Session should exist");
+
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/admin/sessions/{}/revoke",
+            ctx.base_url, public_id
+        ))
+        .send()
+        .await
+        .expect("Failed to revoke session");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    assert_eq!(session_count(&ctx, user_id).await, 1);
+    assert_eq!(audit_actions(&ctx, "hubot").await, vec!["SESSION_REVOKED"]);
+
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/admin/sessions/{}/revoke",
+            ctx.base_url, public_id
+        ))
+        .send()
+        .await
+        .expect("Failed to revoke session");
+    assert_eq!(
+        response.status(),
+        reqwest::StatusCode::NOT_FOUND,
+        "revoking an unknown session should be a 404"
+    );
+}
+
+#[tokio::test]
+async fn admin_can_force_logout_a_user() {
+    let ctx = setup().await;
+    let user_id = insert_user_with_sessions(&ctx, "mona", 3).await;
+
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/admin/users/{}/sessions/revoke",
+            ctx.base_url, user_id
+        ))
+        .send()
+        .await
+        .expect("Failed to force logout");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    assert_eq!(session_count(&ctx, user_id).await, 0);
+    assert_eq!(
+        audit_actions(&ctx, "mona").await,
+        vec!["USER_SESSIONS_REVOKED"]
+    );
+}
@@ -82,7 +82,7 @@ async fn wait_for_sse_event(
 }
 
 struct TestContext {
-    _db: TestDb,
+    db: TestDb,
     _server: TestServer,
     client: Client,
     pool: PgPool,
@@ -97,7 +97,7 @@ async fn setup() -> TestContext {
         .await
         .expect("Failed to connect to test DB");
     TestContext {
-        _db: db,
+        db,
         _server: server,
         client: Client::builder()
             .redirect(reqwest::redirect::Policy::none())
@@ -108,6 +108,147 @@ async fn setup() -> TestContext {
     }
 }
 
+async fn presence_connections(ctx: &TestContext, retro_id: i32) -> Option<i32> {
+    sqlx::query_scalar!(
+        "SELECT SUM(connections)::int FROM presence WHERE retro_id = $1",
+        retro_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to read presence")
+}
+
+#[tokio::test]
+async fn sse_connections_are_tracked_as_presence() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "presence").await;
+
+    let first = ctx
+        .client
+        .get(format!("{}/retro/presence/events", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to open SSE stream");
+    let mut first_stream = first.bytes_stream();
+    let mut first_buffer = String::new();
+    let frame = wait_for_sse_event(&mut first_stream, &mut first_buffer, "PARTICIPANTS").await;
+    assert_eq!(frame.id, None, "signals must not carry an event id");
+    let participants = frame.data["participants"]
+        .as_array()
+        .expect("participants should be a list");
+    assert_eq!(participants.len(), 1);
+    assert_eq!(participants[0]["name"], "Demo User");
+    assert_eq!(participants[0]["initials"], "DU");
+    assert_eq!(presence_connections(&ctx, retro_id).await, Some(1));
+
+    // A second tab of the same user receives the list, too, without
+    // duplicating the participant.
+    let second = ctx
+        .client
+        .get(format!("{}/retro/presence/events", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to open second SSE stream");
+    let mut second_stream = second.bytes_stream();
+    let mut second_buffer = String::new();
+    let frame = wait_for_sse_event(&mut second_stream, &mut second_buffer, "PARTICIPANTS").await;
+    assert_eq!(frame.data["participants"].as_array().map(Vec::len), Some(1));
+    assert_eq!(presence_connections(&ctx, retro_id).await, Some(2));
+
+    drop(first_stream);
+    drop(second_stream);
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(10);
+    while presence_connections(&ctx, retro_id).await.is_some() {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "closing all streams should end the presence"
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+}
+
+#[tokio::test]
+async fn archive_records_the_number_of_participants() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "archive-participants").await;
+    add_item(&ctx, "Good", retro_id, "To archive").await;
+
+    let response = ctx
+        .client
+        .get(format!(
+            "{}/retro/archive-participants/events",
+            ctx.base_url
+        ))
+        .send()
+        .await
+        .expect("Failed to open SSE stream");
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+    wait_for_sse_event(&mut stream, &mut buffer, "PARTICIPANTS").await;
+
+    ctx.client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive retro");
+    let participants_count = sqlx::query_scalar!(
+        "SELECT participants_count FROM archives WHERE retro_id = $1",
+        retro_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Archive should exist");
+    assert_eq!(
+        participants_count,
+        Some(1),
+        "the present author counts once"
+    );
+}
+
+#[tokio::test]
+async fn typing_is_streamed_without_touching_the_event_log() {
+    let ctx = setup().await;
+    create_retro(&ctx, "typing").await;
+
+    let response = ctx
+        .client
+        .get(format!("{}/retro/typing/events", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to open SSE stream");
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+    wait_for_sse_event(&mut stream, &mut buffer, "PARTICIPANTS").await;
+
+    let response = ctx
+        .client
+        .post(format!("{}/retro/typing/typing", ctx.base_url))
+        .form(&[("category", "Bad")])
+        .send()
+        .await
+        .expect("Failed to announce typing");
+    assert_eq!(response.status(), reqwest::StatusCode::NO_CONTENT);
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "TYPING").await;
+    assert_eq!(frame.id, None, "signals must not carry an event id");
+    assert_eq!(frame.data["typing"][0]["name"], "Demo User");
+    assert_eq!(frame.data["typing"][0]["category"], "Bad");
+
+    ctx.client
+        .post(format!("{}/retro/typing/typing", ctx.base_url))
+        .form(&[("category", "Bad"), ("stopped", "true")])
+        .send()
+        .await
+        .expect("Failed to announce the end of typing");
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "TYPING").await;
+    assert_eq!(frame.data["typing"], serde_json::json!([]));
+
+    let events = sqlx::query_scalar!("SELECT COUNT(*) FROM events")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to count events");
+    assert_eq!(events, Some(0), "typing must not be persisted");
+}
+
 async fn create_retro(ctx: &TestContext, slug: &str) -> i32 {
     let response = ctx
         .client
@@ -256,6 +397,353 @@ async fn sse_replays_events_since_last_event_id() {
     );
 }
 
+#[tokio::test]
+async fn full_payloads_embed_the_current_card_and_action_item() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "sse-full").await;
+    let (item_id, _) = add_item(&ctx, "Good", retro_id, "Thin client").await;
+    let response = ctx
+        .client
+        .post(format!("{}/items/{}/like", ctx.base_url, item_id))
+        .send()
+        .await
+        .expect("Failed to like item");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let response = ctx
+        .client
+        .post(format!("{}/retro/{}/action-items", ctx.base_url, retro_id))
+        .form(&[("text", "Ship it")])
+        .send()
+        .await
+        .expect("Failed to add action item");
+    assert!(response.status().is_success());
+
+    let response = ctx
+        .client
+        .get(format!(
+            "{}/retro/sse-full/events?payload=full",
+            ctx.base_url
+        ))
+        .header("Last-Event-ID", "0")
+        .send()
+        .await
+        .expect("Failed to open SSE stream");
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "ITEM_CREATED").await;
+    let item = &frame.data["item"];
+    assert_eq!(item["id"].as_i64(), Some(item_id as i64));
+    assert_eq!(item["text"].as_str(), Some("Thin client"));
+    assert_eq!(item["category"].as_str(), Some("Good"));
+    assert_eq!(
+        item["likes_count"].as_i64(),
+        Some(1),
+        "the embedded card reflects the state at delivery time"
+    );
+    assert!(item.get("timer_ends_at").is_some());
+
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "ACTION_ITEM_CREATED").await;
+    assert_eq!(frame.data["action_item"]["text"].as_str(), Some("Ship it"));
+    assert_eq!(
+        frame.data["action_item"]["id"],
+        frame.data["action_item_id"],
+    );
+}
+
+#[tokio::test]
+async fn compact_payloads_are_the_default() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "sse-compact").await;
+    add_item(&ctx, "Good", retro_id, "Just the changes").await;
+
+    let response = open_sse_since(&ctx, "sse-compact", 0).await;
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "ITEM_CREATED").await;
+    assert_eq!(frame.data["text"].as_str(), Some("Just the changes"));
+    assert!(frame.data.get("item").is_none());
+
+    let response = ctx
+        .client
+        .get(format!(
+            "{}/retro/sse-compact/events?payload=everything",
+            ctx.base_url
+        ))
+        .send()
+        .await
+        .expect("Failed to open SSE stream");
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+async fn add_action_item(ctx: &TestContext, retro_id: i32, text: &str) -> i32 {
+    let response = ctx
+        .client
+        .post(format!("{}/retro/{}/action-items", ctx.base_url, retro_id))
+        .form(&[("text", text)])
+        .send()
+        .await
+        .expect("Failed to add action item");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    sqlx::query_scalar!(
+        "SELECT id FROM action_items WHERE retro_id = $1 AND text = $2",
+        retro_id,
+        text
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Added action item should exist")
+}
+
+async fn undo(ctx: &TestContext, slug: &str) -> reqwest::Response {
+    ctx.client
+        .post(format!("{}/retro/{}/undo", ctx.base_url, slug))
+        .send()
+        .await
+        .expect("Failed to undo")
+}
+
+async fn action_item_text(ctx: &TestContext, action_item_id: i32) -> Option<String> {
+    sqlx::query_scalar!(
+        "SELECT text FROM action_items WHERE id = $1",
+        action_item_id
+    )
+    .fetch_optional(&ctx.pool)
+    .await
+    .expect("Failed to load action item")
+}
+
+#[tokio::test]
+async fn undo_walks_back_action_item_deletion_and_edit() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "undo-actions").await;
+    let action_item_id = add_action_item(&ctx, retro_id, "Original").await;
+
+    let response = ctx
+        .client
+        .post(format!("{}/action-items/{}", ctx.base_url, action_item_id))
+        .form(&[("text", "Edited")])
+        .send()
+        .await
+        .expect("Failed to edit action item");
+    assert_eq!(response.headers()["x-undo"], "Action item edited");
+    let response = ctx
+        .client
+        .delete(format!("{}/action-items/{}", ctx.base_url, action_item_id))
+        .send()
+        .await
+        .expect("Failed to delete action item");
+    assert_eq!(response.headers()["x-undo"], "Action item deleted");
+    assert_eq!(action_item_text(&ctx, action_item_id).await, None);
+
+    let sse = open_sse_since(&ctx, "undo-actions", i64::MAX).await;
+    let response = undo(&ctx, "undo-actions").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let undone: Value = response.json().await.expect("Undo should answer JSON");
+    assert_eq!(undone["undone_event_type"], "ACTION_ITEM_DELETED");
+    assert_eq!(undone["remaining"], 1);
+    assert_eq!(
+        action_item_text(&ctx, action_item_id).await.as_deref(),
+        Some("Edited"),
+        "the deleted action item is restored with its id"
+    );
+
+    let mut stream = sse.bytes_stream();
+    let mut buffer = String::new();
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "ACTION_ITEM_CREATED").await;
+    assert_eq!(frame.data["action_item_id"], action_item_id);
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "ACTION_UNDONE").await;
+    assert_eq!(frame.data["undone_event_type"], "ACTION_ITEM_DELETED");
+    assert_eq!(frame.data["action_item_id"], action_item_id);
+
+    let undone: Value = undo(&ctx, "undo-actions")
+        .await
+        .json()
+        .await
+        .expect("Undo should answer JSON");
+    assert_eq!(undone["undone_event_type"], "ACTION_ITEM_UPDATED");
+    assert_eq!(undone["remaining"], 0);
+    assert_eq!(
+        action_item_text(&ctx, action_item_id).await.as_deref(),
+        Some("Original")
+    );
+
+    let response = undo(&ctx, "undo-actions").await;
+    assert_eq!(
+        response.status(),
+        reqwest::StatusCode::CONFLICT,
+        "undoing the undo's own changes is not offered"
+    );
+    assert_eq!(response.text().await.unwrap(), "Nothing to undo");
+}
+
+#[tokio::test]
+async fn undo_refuses_to_clobber_later_changes() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "undo-conflict").await;
+    let older_id = add_action_item(&ctx, retro_id, "Older").await;
+    ctx.client
+        .delete(format!("{}/action-items/{}", ctx.base_url, older_id))
+        .send()
+        .await
+        .expect("Failed to delete action item");
+    let action_item_id = add_action_item(&ctx, retro_id, "Mine").await;
+    ctx.client
+        .post(format!("{}/action-items/{}", ctx.base_url, action_item_id))
+        .form(&[("text", "Mine, edited")])
+        .send()
+        .await
+        .expect("Failed to edit action item");
+    // Someone else edits it again (without an actor, like any other client).
+    sqlx::query!(
+        "UPDATE action_items SET text = 'Theirs' WHERE id = $1",
+        action_item_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to edit action item");
+
+    let response = undo(&ctx, "undo-conflict").await;
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    assert_eq!(
+        action_item_text(&ctx, action_item_id).await.as_deref(),
+        Some("Theirs")
+    );
+    let actor = sqlx::query_scalar!(
+        "SELECT actor_id FROM events WHERE action_item_id = $1 AND event_type = 'ACTION_ITEM_UPDATED'
+         ORDER BY id",
+        action_item_id
+    )
+    .fetch_all(&ctx.pool)
+    .await
+    .expect("Failed to load events");
+    assert!(actor[0].is_some(), "the app records who edited");
+    assert_eq!(actor[1], None);
+
+    // The conflicting edit is skipped, so the next undo reaches the older
+    // deletion instead of failing the same way until the window closes.
+    let response = undo(&ctx, "undo-conflict").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let undone: Value = response.json().await.expect("Undo should answer JSON");
+    assert_eq!(undone["undone_event_type"], "ACTION_ITEM_DELETED");
+    assert_eq!(undone["remaining"], 0);
+    assert_eq!(
+        action_item_text(&ctx, older_id).await.as_deref(),
+        Some("Older")
+    );
+    assert_eq!(
+        action_item_text(&ctx, action_item_id).await.as_deref(),
+        Some("Theirs")
+    );
+}
+
+async fn change_item_status(ctx: &TestContext, item_id: i32, action: &str) -> reqwest::Response {
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/items/{}/status?action={}",
+            ctx.base_url, item_id, action
+        ))
+        .send()
+        .await
+        .expect("Failed to change item status");
+    assert_eq!(response.status(), reqwest::StatusCode::OK, "{action}");
+    response
+}
+
+struct ItemTimer {
+    status: String,
+    started: bool,
+    duration_seconds: Option<i32>,
+}
+
+async fn item_timer(ctx: &TestContext, item_id: i32) -> ItemTimer {
+    let row = sqlx::query!(
+        r#"SELECT status::text as "status!", timer_started_at, timer_duration_seconds
+           FROM items WHERE id = $1"#,
+        item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to load item");
+    ItemTimer {
+        status: row.status,
+        started: row.timer_started_at.is_some(),
+        duration_seconds: row.timer_duration_seconds,
+    }
+}
+
+#[tokio::test]
+async fn undo_highlights_completed_and_cancelled_cards_again() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "undo-highlight").await;
+    let (item_id, _) = add_item(&ctx, "Good", retro_id, "Discussed").await;
+    let (other_id, _) = add_item(&ctx, "Bad", retro_id, "Still open").await;
+    sqlx::query!(
+        "UPDATE retrospectives SET timer_default_seconds = 420 WHERE id = $1",
+        retro_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to change timer settings");
+
+    change_item_status(&ctx, item_id, "highlight").await;
+    let response = change_item_status(&ctx, item_id, "complete").await;
+    assert_eq!(response.headers()["x-undo"], "Card completed");
+    let undone: Value = undo(&ctx, "undo-highlight")
+        .await
+        .json()
+        .await
+        .expect("Undo should answer JSON");
+    assert_eq!(undone["undone_event_type"], "ITEM_STATUS_CHANGED");
+    let timer = item_timer(&ctx, item_id).await;
+    assert_eq!(timer.status, "HIGHLIGHTED");
+    assert!(timer.started, "the restored highlight starts a fresh timer");
+    assert_eq!(timer.duration_seconds, Some(420));
+
+    let response = change_item_status(&ctx, item_id, "cancel").await;
+    assert_eq!(response.headers()["x-undo"], "Highlight cancelled");
+    // Someone else highlights another card in the meantime.
+    sqlx::query!(
+        "UPDATE items SET status = 'HIGHLIGHTED'::status WHERE id = $1",
+        other_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to highlight item");
+    let response = undo(&ctx, "undo-highlight").await;
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    assert_eq!(
+        response.text().await.unwrap(),
+        "Another card is highlighted now"
+    );
+    assert_eq!(item_timer(&ctx, item_id).await.status, "CREATED");
+
+    // Once that card is done, the cancelled highlight can be restored; the
+    // timer follows the retro's current settings.
+    sqlx::query!(
+        "UPDATE items SET status = 'COMPLETED'::status WHERE id = $1",
+        other_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to complete item");
+    sqlx::query!(
+        "UPDATE retrospectives SET timer_auto_start = false WHERE id = $1",
+        retro_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to change timer settings");
+    let response = undo(&ctx, "undo-highlight").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let timer = item_timer(&ctx, item_id).await;
+    assert_eq!(timer.status, "HIGHLIGHTED");
+    assert!(
+        !timer.started,
+        "without auto-start, the restored highlight has no timer"
+    );
+}
+
 #[tokio::test]
 async fn mutation_responses_carry_x_event_id() {
     let ctx = setup().await;
@@ -475,31 +963,208 @@ async fn timer_endpoints_update_the_db_and_emit_events() {
     );
 }
 
+async fn post_timer(ctx: &TestContext, item_id: i32, action: &str) -> reqwest::Response {
+    ctx.client
+        .post(format!(
+            "{}/items/{}/timer/{}",
+            ctx.base_url, item_id, action
+        ))
+        .form(&[] as &[(&str, &str)])
+        .send()
+        .await
+        .expect("Failed to post timer action")
+}
+
 #[tokio::test]
-async fn timer_sweep_marks_short_timers_elapsed() {
+async fn timer_settings_apply_and_paused_timers_keep_their_time() {
     let ctx = setup().await;
-    let retro_id = create_retro(&ctx, "timer-sweep-http").await;
-    let (item_id, _) = add_item(&ctx, "Good", retro_id, "Short timer").await;
+    let retro_id = create_retro(&ctx, "timer-settings").await;
+    let (item_id, _) = add_item(&ctx, "Good", retro_id, "Pairing rotation").await;
 
     let response = ctx
         .client
-        .get(format!("{}/retro/timer-sweep-http/events", ctx.base_url))
-        .send()
-        .await
-        .expect("Failed to open SSE stream");
-    let mut stream = response.bytes_stream();
-    let mut buffer = String::new();
-
-    ctx.client
         .post(format!(
-            "{}/items/{}/status?action=highlight",
-            ctx.base_url, item_id
+            "{}/retro/timer-settings/timer-settings",
+            ctx.base_url
         ))
+        .form(&[
+            ("discussion_minutes", "2"),
+            ("extension_minutes", "1"),
+            ("max_minutes", "1"),
+        ])
         .send()
         .await
-        .expect("Failed to highlight item");
-    wait_for_sse_event(&mut stream, &mut buffer, "ITEM_STATUS_CHANGED").await;
-
+        .expect("Failed to change timer settings");
+    assert_eq!(
+        response.status(),
+        reqwest::StatusCode::BAD_REQUEST,
+        "the maximum cannot be shorter than a discussion"
+    );
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/retro/timer-settings/timer-settings",
+            ctx.base_url
+        ))
+        .form(&[
+            ("discussion_minutes", "2"),
+            ("extension_minutes", "1"),
+            ("max_minutes", "3"),
+        ])
+        .send()
+        .await
+        .expect("Failed to change timer settings");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    let board = get_html(&ctx, "/retro/timer-settings").await;
+    assert!(board.contains(r#"data-timer-default-seconds="120""#));
+    assert!(
+        !board.contains("data-timer-auto-start"),
+        "the unchecked box turns off starting the timer on highlight"
+    );
+    let changes = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM audit_log WHERE action = 'RETRO_TIMER_SETTINGS_CHANGED' AND target = $1",
+        "timer-settings"
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to count audit entries");
+    assert_eq!(changes, Some(1));
+
+    sqlx::query!(
+        "UPDATE items SET status = 'HIGHLIGHTED'::status WHERE id = $1",
+        item_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to highlight item");
+
+    // Start and extend use the retro's lengths, up to its maximum.
+    assert_eq!(
+        post_timer(&ctx, item_id, "start").await.status(),
+        reqwest::StatusCode::OK
+    );
+    assert_eq!(
+        post_timer(&ctx, item_id, "extend").await.status(),
+        reqwest::StatusCode::OK
+    );
+    let duration = sqlx::query_scalar!(
+        "SELECT timer_duration_seconds FROM items WHERE id = $1",
+        item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to read timer duration");
+    assert_eq!(duration, Some(180));
+    assert_eq!(
+        post_timer(&ctx, item_id, "extend").await.status(),
+        reqwest::StatusCode::BAD_REQUEST,
+        "the timer is at its maximum"
+    );
+
+    let response = post_timer(&ctx, item_id, "pause").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let event_id: i64 = response
+        .headers()
+        .get("x-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .expect("timer pause response should carry X-Event-Id");
+    assert_eq!(
+        event_id,
+        latest_event_id(&ctx, item_id, "TIMER_PAUSED").await
+    );
+    assert!(response
+        .text()
+        .await
+        .expect("Card HTML")
+        .contains("data-paused"));
+    assert!(
+        post_timer(&ctx, item_id, "pause")
+            .await
+            .headers()
+            .get("x-event-id")
+            .is_none(),
+        "pausing a paused timer changes nothing"
+    );
+
+    // While paused, the deadline may pass without the timer elapsing: the
+    // time left is frozen at the pause.
+    sqlx::query!(
+        "UPDATE items
+         SET timer_started_at = timer_started_at - INTERVAL '10 minutes',
+             timer_paused_at = timer_paused_at - INTERVAL '10 minutes'
+         WHERE id = $1",
+        item_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to move the pause back");
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    let row = sqlx::query!(
+        "SELECT timer_elapsed_at, timer_ends_at FROM items WHERE id = $1",
+        item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to read timer columns");
+    assert!(
+        row.timer_elapsed_at.is_none(),
+        "paused timers do not elapse"
+    );
+    assert!(row.timer_ends_at.expect("deadline") < chrono::Utc::now());
+
+    let response = post_timer(&ctx, item_id, "resume").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(response.headers().get("x-event-id").is_some());
+    let payload = sqlx::query_scalar!(
+        "SELECT payload FROM events WHERE id = $1",
+        latest_event_id(&ctx, item_id, "TIMER_RESUMED").await
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to load event");
+    assert!(payload["paused_at"].is_null());
+    let row = sqlx::query!(
+        "SELECT timer_paused_at, timer_duration_seconds, timer_ends_at FROM items WHERE id = $1",
+        item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to read timer columns");
+    assert!(row.timer_paused_at.is_none());
+    assert_eq!(row.timer_duration_seconds, Some(180));
+    let remaining = row.timer_ends_at.expect("deadline") - chrono::Utc::now();
+    assert!(
+        remaining > chrono::Duration::seconds(170),
+        "the timer resumes with the time it had left, not {remaining}"
+    );
+}
+
+#[tokio::test]
+async fn timer_sweep_marks_short_timers_elapsed() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "timer-sweep-http").await;
+    let (item_id, _) = add_item(&ctx, "Good", retro_id, "Short timer").await;
+
+    let response = ctx
+        .client
+        .get(format!("{}/retro/timer-sweep-http/events", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to open SSE stream");
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+
+    ctx.client
+        .post(format!(
+            "{}/items/{}/status?action=highlight",
+            ctx.base_url, item_id
+        ))
+        .send()
+        .await
+        .expect("Failed to highlight item");
+    wait_for_sse_event(&mut stream, &mut buffer, "ITEM_STATUS_CHANGED").await;
+
     ctx.client
         .post(format!("{}/items/{}/timer/start", ctx.base_url, item_id))
         .form(&[("duration", "2")])
@@ -508,7 +1173,7 @@ async fn timer_sweep_marks_short_timers_elapsed() {
         .expect("Failed to start short timer");
     wait_for_sse_event(&mut stream, &mut buffer, "TIMER_STARTED").await;
 
-    // The 1-second sweep should mark the timer elapsed within a few seconds.
+    // The scheduler should mark the timer elapsed within a few seconds.
     let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(15);
     loop {
         let elapsed: Option<chrono::DateTime<chrono::Utc>> =
@@ -550,42 +1215,1632 @@ async fn timer_sweep_marks_short_timers_elapsed() {
 }
 
 #[tokio::test]
-async fn archive_emits_single_retro_archived_event() {
+async fn session_and_phase_timers_are_swept_and_broadcast() {
     let ctx = setup().await;
-    let retro_id = create_retro(&ctx, "archive-event").await;
-    add_item(&ctx, "Good", retro_id, "To archive").await;
+    let retro_id = create_retro(&ctx, "retro-timers").await;
 
     let response = ctx
         .client
-        .get(format!("{}/retro/archive-event/events", ctx.base_url))
+        .get(format!("{}/retro/retro-timers/events", ctx.base_url))
         .send()
         .await
         .expect("Failed to open SSE stream");
     let mut stream = response.bytes_stream();
     let mut buffer = String::new();
 
-    ctx.client
-        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/retro/retro-timers/timers/phase/start",
+            ctx.base_url
+        ))
+        .form(&[("label", "Voting"), ("minutes", "61")])
         .send()
         .await
-        .expect("Failed to archive retro");
+        .expect("Failed to start phase timer");
+    assert_eq!(
+        response.status(),
+        reqwest::StatusCode::BAD_REQUEST,
+        "a phase lasts at most an hour"
+    );
 
-    let frame = wait_for_sse_event(&mut stream, &mut buffer, "RETRO_ARCHIVED").await;
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/retro/retro-timers/timers/phase/start",
+            ctx.base_url
+        ))
+        .form(&[("label", " Voting "), ("minutes", "5")])
+        .send()
+        .await
+        .expect("Failed to start phase timer");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let event_id: i64 = response
+        .headers()
+        .get("x-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .expect("phase start response should carry X-Event-Id");
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "RETRO_TIMER_STARTED").await;
+    assert_eq!(frame.id, Some(event_id));
     assert_eq!(frame.data["retro_id"].as_i64(), Some(retro_id as i64));
+    assert_eq!(frame.data["kind"].as_str(), Some("PHASE"));
+    assert_eq!(frame.data["label"].as_str(), Some("Voting"));
+    assert_eq!(frame.data["duration_seconds"].as_i64(), Some(300));
 
-    // Archiving again emits nothing (no active items left).
-    ctx.client
-        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+    // The session may run longer than a phase.
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/retro/retro-timers/timers/session/start",
+            ctx.base_url
+        ))
+        .form(&[("minutes", "90")])
         .send()
         .await
-        .expect("Failed to archive empty retro");
-    let nothing = tokio::time::timeout(
-        std::time::Duration::from_secs(2),
-        next_sse_frame(&mut stream, &mut buffer),
+        .expect("Failed to start session timer");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "RETRO_TIMER_STARTED").await;
+    assert_eq!(frame.data["kind"].as_str(), Some("SESSION"));
+    assert_eq!(frame.data["duration_seconds"].as_i64(), Some(5400));
+
+    let board = get_html(&ctx, "/retro/retro-timers").await;
+    assert!(board.contains(r#"data-kind="session""#));
+    assert!(board.contains(r#"data-kind="phase""#));
+    assert!(board.contains("Voting"));
+
+    // Once its deadline passed, the sweep marks the phase elapsed.
+    sqlx::query!(
+        "UPDATE retro_timers SET started_at = NOW() - INTERVAL '301 seconds'
+         WHERE retro_id = $1 AND kind = 'PHASE'",
+        retro_id
     )
-    .await;
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to backdate phase timer");
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "RETRO_TIMER_ELAPSED").await;
+    assert_eq!(frame.data["kind"].as_str(), Some("PHASE"));
+    let timers = get_html(&ctx, "/retro/retro-timers/timers").await;
+    assert!(timers.contains("data-elapsed"));
+
+    // Extending an elapsed phase gives it the extension step from now on.
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/retro/retro-timers/timers/phase/extend",
+            ctx.base_url
+        ))
+        .send()
+        .await
+        .expect("Failed to extend phase timer");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    wait_for_sse_event(&mut stream, &mut buffer, "RETRO_TIMER_EXTENDED").await;
+    let row = sqlx::query!(
+        r#"SELECT ends_at, elapsed_at
+           FROM retro_timers WHERE retro_id = $1 AND kind = 'PHASE'"#,
+        retro_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to read phase timer");
+    assert!(row.elapsed_at.is_none());
+    let remaining = row.ends_at.expect("deadline") - chrono::Utc::now();
     assert!(
-        nothing.is_err(),
-        "archiving an empty retro should not emit another RETRO_ARCHIVED"
+        remaining > chrono::Duration::seconds(110),
+        "the phase runs for the extension step, not {remaining}"
+    );
+
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/retro/retro-timers/timers/phase/stop",
+            ctx.base_url
+        ))
+        .send()
+        .await
+        .expect("Failed to stop phase timer");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "RETRO_TIMER_STOPPED").await;
+    assert_eq!(frame.data["kind"].as_str(), Some("PHASE"));
+    let kinds = sqlx::query_scalar!(
+        "SELECT kind::text FROM retro_timers WHERE retro_id = $1",
+        retro_id
+    )
+    .fetch_all(&ctx.pool)
+    .await
+    .expect("Failed to read timers");
+    assert_eq!(kinds, vec![Some("SESSION".to_string())]);
+}
+
+/// Backend pids of the app connections holding (`granted`) or waiting for the
+/// timer scheduler's advisory lock (keys "timr" and 0).
+async fn scheduler_lock_holders(ctx: &TestContext, granted: bool) -> Vec<i32> {
+    sqlx::query_scalar!(
+        r#"SELECT pid as "pid!" FROM pg_locks
+           WHERE locktype = 'advisory' AND classid = x'74696d72'::integer::oid
+             AND objid = 0 AND objsubid = 2 AND granted = $1"#,
+        granted
+    )
+    .fetch_all(&ctx.pool)
+    .await
+    .expect("Failed to read advisory locks")
+}
+
+/// Insert a session timer running for `seconds` and wait until it elapsed;
+/// returns how long after its deadline it was marked.
+async fn wait_for_short_session_timer(
+    ctx: &TestContext,
+    retro_id: i32,
+    seconds: i32,
+) -> chrono::Duration {
+    sqlx::query!(
+        "INSERT INTO retro_timers (retro_id, kind, duration_seconds) VALUES ($1, 'SESSION', $2)
+         ON CONFLICT (retro_id, kind) DO UPDATE
+         SET started_at = NOW(), duration_seconds = $2, elapsed_at = NULL",
+        retro_id,
+        seconds
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to start session timer");
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(15);
+    loop {
+        let row = sqlx::query!(
+            "SELECT ends_at, elapsed_at FROM retro_timers WHERE retro_id = $1 AND kind = 'SESSION'",
+            retro_id
+        )
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to read session timer");
+        if let Some(elapsed_at) = row.elapsed_at {
+            return elapsed_at - row.ends_at.expect("deadline");
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "timed out waiting for the session timer to elapse"
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+}
+
+#[tokio::test]
+async fn one_instance_marks_timers_elapsed_at_their_deadline() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "timer-scheduler").await;
+    let _second = TestServer::start(&ctx.db.database_url).await;
+
+    // One instance holds the scheduler lock, the other waits for it.
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(15);
+    let leader = loop {
+        let leaders = scheduler_lock_holders(&ctx, true).await;
+        let waiting = scheduler_lock_holders(&ctx, false).await;
+        if leaders.len() == 1 && waiting.len() == 1 {
+            break leaders[0];
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "expected one leader and one waiting instance, got {leaders:?} and {waiting:?}"
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    };
+
+    // The leader wakes at the deadline instead of polling.
+    let late = wait_for_short_session_timer(&ctx, retro_id, 2).await;
+    assert!(
+        late >= chrono::Duration::zero() && late < chrono::Duration::milliseconds(500),
+        "the timer should elapse right at its deadline, not {late} after it"
+    );
+
+    // When the leader's connection goes away, the other instance takes over.
+    let terminated = sqlx::query_scalar!("SELECT pg_terminate_backend($1)", leader)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to terminate the leader's connection");
+    assert_eq!(terminated, Some(true));
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(15);
+    loop {
+        let leaders = scheduler_lock_holders(&ctx, true).await;
+        if leaders.len() == 1 && leaders[0] != leader {
+            break;
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "no instance took over the scheduler lock"
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    wait_for_short_session_timer(&ctx, retro_id, 1).await;
+}
+
+#[tokio::test]
+async fn highlight_timers_warn_a_minute_before_they_elapse() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "timer-warning").await;
+    let (item_id, _) = add_item(&ctx, "Good", retro_id, "Almost out of time").await;
+
+    let response = ctx
+        .client
+        .get(format!("{}/retro/timer-warning/events", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to open SSE stream");
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+
+    // A 61-second timer that started 58 seconds ago: the warning is due
+    // right away, the deadline three seconds later.
+    sqlx::query!(
+        "UPDATE items SET status = 'HIGHLIGHTED'::status,
+             timer_started_at = NOW() - interval '58 seconds', timer_duration_seconds = 61
+         WHERE id = $1",
+        item_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to highlight item");
+
+    let warning = wait_for_sse_event(&mut stream, &mut buffer, "TIMER_WARNING").await;
+    assert_eq!(warning.data["item_id"].as_i64(), Some(item_id as i64));
+    let elapsed = wait_for_sse_event(&mut stream, &mut buffer, "TIMER_ELAPSED").await;
+    assert_eq!(elapsed.data["item_id"].as_i64(), Some(item_id as i64));
+    assert!(
+        elapsed.data["ends_at"].is_string(),
+        "clients tell live alerts from replayed ones by the deadline"
+    );
+    assert_eq!(elapsed.data["ends_at"], warning.data["ends_at"]);
+
+    // Extending beyond the last minute arms the warning again.
+    let response = ctx
+        .client
+        .post(format!("{}/items/{}/timer/extend", ctx.base_url, item_id))
+        .send()
+        .await
+        .expect("Failed to extend elapsed timer");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let warned_at = sqlx::query_scalar!("SELECT timer_warned_at FROM items WHERE id = $1", item_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to read item");
+    assert!(
+        warned_at.is_none(),
+        "the extended timer is warned about again"
     );
 }
+
+async fn edit_item(ctx: &TestContext, item_id: i32, text: &str) {
+    let response = ctx
+        .client
+        .post(format!("{}/items/{}", ctx.base_url, item_id))
+        .form(&[("text", text)])
+        .send()
+        .await
+        .expect("Failed to edit item");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+}
+
+async fn get_html(ctx: &TestContext, path: &str) -> String {
+    let response = ctx
+        .client
+        .get(format!("{}{}", ctx.base_url, path))
+        .send()
+        .await
+        .expect("Failed to load page");
+    assert_eq!(response.status(), reqwest::StatusCode::OK, "GET {path}");
+    response.text().await.expect("Page should be HTML")
+}
+
+#[tokio::test]
+async fn card_edits_are_kept_as_history_and_archived() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "card-history").await;
+    let (item_id, _) = add_item(&ctx, "Good", retro_id, "First draft").await;
+    let card = get_html(&ctx, &format!("/items/{item_id}")).await;
+    assert!(
+        !card.contains("card-history-toggle"),
+        "unedited cards have no history"
+    );
+
+    edit_item(&ctx, item_id, "Second draft").await;
+    edit_item(&ctx, item_id, "Second draft").await; // unchanged: no revision
+    edit_item(&ctx, item_id, "Final text").await;
+
+    let card = get_html(&ctx, &format!("/items/{item_id}")).await;
+    assert!(card.contains("card-history-toggle"));
+    let history = get_html(&ctx, &format!("/items/{item_id}/history")).await;
+    let positions: Vec<usize> = ["First draft", "Second draft", "Final text"]
+        .iter()
+        .map(|text| history.find(text).expect("every version is listed"))
+        .collect();
+    assert!(
+        positions.windows(2).all(|pair| pair[0] < pair[1]),
+        "versions are listed oldest first"
+    );
+    assert_eq!(history.matches("Edited by Demo User").count(), 2);
+
+    ctx.client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive retro");
+    let archive_id = sqlx::query_scalar!("SELECT id FROM archives WHERE retro_id = $1", retro_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Archive should exist");
+    let archive = get_html(&ctx, &format!("/retro/card-history/archives/{archive_id}")).await;
+    assert!(
+        archive.contains(&format!("card-history-{item_id}")),
+        "archived cards keep their history"
+    );
+    let history = get_html(&ctx, &format!("/items/{item_id}/history")).await;
+    assert!(history.contains("First draft"));
+}
+
+async fn set_edit_policy(ctx: &TestContext, slug: &str, policy: &str) {
+    let response = ctx
+        .client
+        .post(format!("{}/retro/{}/edit-policy", ctx.base_url, slug))
+        .form(&[("edit_policy", policy)])
+        .send()
+        .await
+        .expect("Failed to set edit policy");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+}
+
+#[tokio::test]
+async fn edit_policy_decides_who_can_edit_a_card() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "edit-policy").await;
+    let (own_id, _) = add_item(&ctx, "Good", retro_id, "My card").await;
+    let other_user_id = sqlx::query_scalar!(
+        "INSERT INTO users (github_id, username) VALUES ($1, $2) RETURNING id",
+        rand::random::<i32>() as i64,
+        "someone-else"
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to insert user");
+    let other_id = sqlx::query_scalar!(
+        "INSERT INTO items (retro_id, text, category, status, created_by)
+         VALUES ($1, 'Their card', 'GOOD', 'CREATED', $2) RETURNING id",
+        retro_id,
+        other_user_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to insert item");
+
+    // Retros are open by default.
+    assert!(get_html(&ctx, &format!("/items/{other_id}"))
+        .await
+        .contains("card-text-edit"));
+
+    set_edit_policy(&ctx, "edit-policy", "AUTHOR").await;
+    assert!(!get_html(&ctx, &format!("/items/{other_id}"))
+        .await
+        .contains("card-text-edit"));
+    assert!(get_html(&ctx, &format!("/items/{own_id}"))
+        .await
+        .contains("card-text-edit"));
+    let response = ctx
+        .client
+        .get(format!("{}/items/{}/edit", ctx.base_url, other_id))
+        .send()
+        .await
+        .expect("Failed to request edit form");
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    let response = ctx
+        .client
+        .post(format!("{}/items/{}", ctx.base_url, other_id))
+        .form(&[("text", "Hijacked")])
+        .send()
+        .await
+        .expect("Failed to edit item");
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    assert!(response
+        .text()
+        .await
+        .expect("Error page")
+        .contains("Only the author of a card can edit it"));
+    edit_item(&ctx, own_id, "Still mine").await;
+
+    // The demo user is an admin, and admins facilitate every retro.
+    set_edit_policy(&ctx, "edit-policy", "AUTHOR_AND_FACILITATOR").await;
+    assert!(get_html(&ctx, &format!("/items/{other_id}"))
+        .await
+        .contains("card-text-edit"));
+    edit_item(&ctx, other_id, "Fixed typo").await;
+
+    let changes = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM audit_log WHERE action = 'RETRO_EDIT_POLICY_CHANGED' AND target = $1",
+        "edit-policy"
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to count audit entries");
+    assert_eq!(changes, Some(2));
+}
+
+async fn move_item(ctx: &TestContext, item_id: i32, category: &str) -> reqwest::Response {
+    ctx.client
+        .post(format!("{}/items/{}/move", ctx.base_url, item_id))
+        .form(&[("category", category)])
+        .send()
+        .await
+        .expect("Failed to move item")
+}
+
+#[tokio::test]
+async fn moving_a_card_emits_item_moved() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "move-card").await;
+    let (item_id, _) = add_item(&ctx, "Bad", retro_id, "Belongs in Watch").await;
+
+    let response = move_item(&ctx, item_id, "Watch").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let event_id: i64 = response
+        .headers()
+        .get("x-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .expect("move response should carry X-Event-Id");
+    assert_eq!(event_id, latest_event_id(&ctx, item_id, "ITEM_MOVED").await);
+    let payload = sqlx::query_scalar!("SELECT payload FROM events WHERE id = $1", event_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to load event");
+    assert_eq!(payload["old_category"], "BAD");
+    assert_eq!(payload["new_category"], "WATCH");
+    let category = sqlx::query_scalar!(
+        r#"SELECT category::text as "category!" FROM items WHERE id = $1"#,
+        item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to load item");
+    assert_eq!(category, "WATCH");
+
+    let response = move_item(&ctx, item_id, "Watch").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(
+        response.headers().get("x-event-id").is_none(),
+        "moving a card to its own column emits no event"
+    );
+    assert_eq!(
+        move_item(&ctx, item_id, "Sideways").await.status(),
+        reqwest::StatusCode::UNPROCESSABLE_ENTITY
+    );
+}
+
+async fn reorder_item(ctx: &TestContext, item_id: i32, after: Option<i32>) -> reqwest::Response {
+    let form: Vec<(&str, String)> = after
+        .map(|after| vec![("after", after.to_string())])
+        .unwrap_or_default();
+    ctx.client
+        .post(format!("{}/items/{}/reorder", ctx.base_url, item_id))
+        .form(&form)
+        .send()
+        .await
+        .expect("Failed to reorder item")
+}
+
+fn board_order(html: &str, item_ids: &[i32]) -> Vec<i32> {
+    let mut ids = item_ids.to_vec();
+    ids.sort_by_key(|id| {
+        html.find(&format!("data-item-id=\"{id}\""))
+            .expect("card should be on the board")
+    });
+    ids
+}
+
+#[tokio::test]
+async fn reordering_cards_sets_their_order_within_the_column() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "reorder-cards").await;
+    let (first, _) = add_item(&ctx, "Good", retro_id, "First").await;
+    let (second, _) = add_item(&ctx, "Good", retro_id, "Second").await;
+    let (third, _) = add_item(&ctx, "Good", retro_id, "Third").await;
+    let (elsewhere, _) = add_item(&ctx, "Bad", retro_id, "Elsewhere").await;
+    let cards = [first, second, third];
+    assert_eq!(
+        board_order(&get_html(&ctx, "/retro/reorder-cards").await, &cards),
+        [first, second, third],
+        "new cards go to the end of their column"
+    );
+
+    let response = reorder_item(&ctx, third, None).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let event_id: i64 = response
+        .headers()
+        .get("x-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .expect("reorder response should carry X-Event-Id");
+    assert_eq!(
+        event_id,
+        latest_event_id(&ctx, third, "ITEM_REORDERED").await
+    );
+    let payload = sqlx::query_scalar!("SELECT payload FROM events WHERE id = $1", event_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to load event");
+    let position = sqlx::query_scalar!("SELECT position FROM items WHERE id = $1", third)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to load item");
+    assert_eq!(payload["position"], position);
+    assert_eq!(payload["category"], "GOOD");
+
+    let response = reorder_item(&ctx, first, Some(second)).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(
+        board_order(&get_html(&ctx, "/retro/reorder-cards").await, &cards),
+        [third, second, first]
+    );
+
+    let response = reorder_item(&ctx, first, Some(second)).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(
+        response.headers().get("x-event-id").is_none(),
+        "a card already in place emits no event"
+    );
+    assert_eq!(
+        reorder_item(&ctx, first, Some(elsewhere)).await.status(),
+        reqwest::StatusCode::BAD_REQUEST
+    );
+
+    // A moved card goes to the end of its new column.
+    move_item(&ctx, third, "Bad").await;
+    let payload = sqlx::query_scalar!(
+        "SELECT payload FROM events WHERE id = $1",
+        latest_event_id(&ctx, third, "ITEM_MOVED").await
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to load event");
+    assert_eq!(
+        board_order(
+            &get_html(&ctx, "/retro/reorder-cards").await,
+            &[elsewhere, third]
+        ),
+        [elsewhere, third]
+    );
+    assert!(payload["position"].is_string());
+}
+
+/// Wait until a request blocks on the retro's position lock ("posn"), held
+/// by the test.
+async fn wait_for_position_lock(ctx: &TestContext, retro_id: i32) {
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(10);
+    loop {
+        let waiting = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM pg_locks
+               WHERE locktype = 'advisory' AND classid = 1886352238::oid
+                 AND objid = $1::integer::oid AND NOT granted"#,
+            retro_id
+        )
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to read locks");
+        if waiting > 0 {
+            return;
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "the request should wait for the position lock"
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+}
+
+/// Wait until a request blocks on a row the test holds locked.
+async fn wait_for_row_lock(ctx: &TestContext) {
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(10);
+    loop {
+        let waiting = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM pg_stat_activity
+               WHERE datname = current_database() AND wait_event_type = 'Lock'"#
+        )
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to read activity");
+        if waiting > 0 {
+            return;
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "the request should wait for the locked row"
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+}
+
+#[tokio::test]
+async fn moving_a_card_someone_else_just_moved_emits_no_event() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "move-race").await;
+    let (item_id, _) = add_item(&ctx, "Bad", retro_id, "Belongs in Watch").await;
+
+    // Two people move the card to the same column at the same time.
+    let mut tx = ctx.pool.begin().await.expect("Failed to begin");
+    sqlx::query!("SELECT lock_item_positions($1)", retro_id)
+        .execute(&mut *tx)
+        .await
+        .expect("Failed to lock positions");
+    let mine = {
+        let client = ctx.client.clone();
+        let url = format!("{}/items/{}/move", ctx.base_url, item_id);
+        tokio::spawn(async move {
+            client
+                .post(url)
+                .form(&[("category", "Watch")])
+                .send()
+                .await
+                .expect("Failed to move item")
+        })
+    };
+    wait_for_position_lock(&ctx, retro_id).await;
+    sqlx::query!(
+        "UPDATE items SET category = 'WATCH'::category,
+             position = item_position_at_end($1, 'WATCH')
+         WHERE id = $2",
+        retro_id,
+        item_id
+    )
+    .execute(&mut *tx)
+    .await
+    .expect("Failed to move item");
+    tx.commit().await.expect("Failed to commit");
+
+    let response = mine.await.expect("Move task failed");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(
+        response.headers().get("x-event-id").is_none(),
+        "the other move's event is not this request's"
+    );
+}
+
+#[tokio::test]
+async fn moving_a_card_deleted_meanwhile_is_not_found() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "move-delete-race").await;
+    let (item_id, _) = add_item(&ctx, "Bad", retro_id, "Belongs in Watch").await;
+
+    // Someone else deletes the card while the move waits for the lock.
+    let mut tx = ctx.pool.begin().await.expect("Failed to begin");
+    sqlx::query!("SELECT lock_item_positions($1)", retro_id)
+        .execute(&mut *tx)
+        .await
+        .expect("Failed to lock positions");
+    let mine = {
+        let client = ctx.client.clone();
+        let url = format!("{}/items/{}/move", ctx.base_url, item_id);
+        tokio::spawn(async move {
+            client
+                .post(url)
+                .form(&[("category", "Watch")])
+                .send()
+                .await
+                .expect("Failed to move item")
+        })
+    };
+    wait_for_position_lock(&ctx, retro_id).await;
+    sqlx::query!("DELETE FROM items WHERE id = $1", item_id)
+        .execute(&mut *tx)
+        .await
+        .expect("Failed to delete item");
+    tx.commit().await.expect("Failed to commit");
+
+    let response = mine.await.expect("Move task failed");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    assert!(response.headers().get("x-event-id").is_none());
+}
+
+#[tokio::test]
+async fn reordering_a_card_moved_meanwhile_uses_its_new_column() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "reorder-race").await;
+    let (first, _) = add_item(&ctx, "Good", retro_id, "First").await;
+    let (second, _) = add_item(&ctx, "Good", retro_id, "Second").await;
+
+    // Hold the retro's position lock, so the reorder has loaded the card but
+    // waits for the lock while someone else moves the card.
+    let mut tx = ctx.pool.begin().await.expect("Failed to begin");
+    sqlx::query!("SELECT lock_item_positions($1)", retro_id)
+        .execute(&mut *tx)
+        .await
+        .expect("Failed to lock positions");
+    let reorder = {
+        let client = ctx.client.clone();
+        let url = format!("{}/items/{}/reorder", ctx.base_url, second);
+        tokio::spawn(async move {
+            client
+                .post(url)
+                .form(&[("after", first.to_string())])
+                .send()
+                .await
+                .expect("Failed to reorder item")
+        })
+    };
+    wait_for_position_lock(&ctx, retro_id).await;
+    sqlx::query!(
+        "UPDATE items SET category = 'BAD'::category, position = item_position_at_end($1, 'BAD')
+         WHERE id = $2",
+        retro_id,
+        second
+    )
+    .execute(&mut *tx)
+    .await
+    .expect("Failed to move item");
+    tx.commit().await.expect("Failed to commit");
+
+    let response = reorder.await.expect("Reorder task failed");
+    assert_eq!(
+        response.status(),
+        reqwest::StatusCode::BAD_REQUEST,
+        "the card is no longer in the column of the card to place it after"
+    );
+}
+
+async fn react_to_item(ctx: &TestContext, item_id: i32, reaction: &str) -> reqwest::Response {
+    ctx.client
+        .post(format!("{}/items/{}/reactions", ctx.base_url, item_id))
+        .form(&[("reaction", reaction)])
+        .send()
+        .await
+        .expect("Failed to react to item")
+}
+
+#[tokio::test]
+async fn reactions_toggle_per_user_and_broadcast_counts() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "reactions").await;
+    let (item_id, _) = add_item(&ctx, "Good", retro_id, "Shipped on Friday").await;
+
+    let response = react_to_item(&ctx, item_id, "PARTY").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let event_id: i64 = response
+        .headers()
+        .get("x-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .expect("reaction response should carry X-Event-Id");
+    assert_eq!(
+        event_id,
+        latest_event_id(&ctx, item_id, "ITEM_REACTED").await
+    );
+    let html = response.text().await.expect("Card HTML");
+    assert!(html.contains(r#"value="PARTY""#) && html.contains(r#"aria-pressed="true""#));
+    let payload = sqlx::query_scalar!("SELECT payload FROM events WHERE id = $1", event_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to load event");
+    assert_eq!(payload["reaction"], "PARTY");
+    assert_eq!(payload["count"], 1);
+    assert_eq!(payload["counts"], serde_json::json!({ "PARTY": 1 }));
+
+    react_to_item(&ctx, item_id, "LAUGH").await;
+    let response = react_to_item(&ctx, item_id, "PARTY").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let event_id = latest_event_id(&ctx, item_id, "ITEM_UNREACTED").await;
+    let payload = sqlx::query_scalar!("SELECT payload FROM events WHERE id = $1", event_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to load event");
+    assert_eq!(payload["count"], 0);
+    assert_eq!(payload["counts"], serde_json::json!({ "LAUGH": 1 }));
+    let likes = sqlx::query_scalar!("SELECT COUNT(*) FROM likes WHERE item_id = $1", item_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to count likes");
+    assert_eq!(likes, Some(0), "reactions are separate from likes");
+
+    assert_eq!(
+        react_to_item(&ctx, item_id, "SHRUG").await.status(),
+        reqwest::StatusCode::UNPROCESSABLE_ENTITY
+    );
+}
+
+async fn comment_on_item(ctx: &TestContext, item_id: i32, text: &str) -> reqwest::Response {
+    ctx.client
+        .post(format!("{}/items/{}/comments", ctx.base_url, item_id))
+        .form(&[("text", text)])
+        .send()
+        .await
+        .expect("Failed to comment on item")
+}
+
+#[tokio::test]
+async fn deleting_a_comment_deleted_meanwhile_is_not_found() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "comment-race").await;
+    let (item_id, _) = add_item(&ctx, "Watch", retro_id, "Flaky deploys").await;
+    sqlx::query!(
+        "UPDATE items SET status = 'HIGHLIGHTED'::status WHERE id = $1",
+        item_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to highlight item");
+    comment_on_item(&ctx, item_id, "Delete me").await;
+    comment_on_item(&ctx, item_id, "Keep me").await;
+    let comment_id = sqlx::query_scalar!(
+        "SELECT id FROM item_comments WHERE item_id = $1 AND text = 'Delete me'",
+        item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Comment should exist");
+
+    // Someone else deletes the comment while this request waits for its row.
+    let mut tx = ctx.pool.begin().await.expect("Failed to begin");
+    sqlx::query!(
+        "SELECT id FROM item_comments WHERE id = $1 FOR UPDATE",
+        comment_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .expect("Failed to lock comment");
+    let mine = {
+        let client = ctx.client.clone();
+        let url = format!("{}/items/{}/comments/{}", ctx.base_url, item_id, comment_id);
+        tokio::spawn(async move {
+            client
+                .delete(url)
+                .send()
+                .await
+                .expect("Failed to delete comment")
+        })
+    };
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(10);
+    loop {
+        let waiting = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM pg_stat_activity
+               WHERE datname = current_database() AND wait_event_type = 'Lock'
+                 AND query LIKE 'DELETE FROM item_comments%'"#
+        )
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to read activity");
+        if waiting > 0 {
+            break;
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "the deletion should wait for the comment's row"
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+    sqlx::query!("DELETE FROM item_comments WHERE id = $1", comment_id)
+        .execute(&mut *tx)
+        .await
+        .expect("Failed to delete comment");
+    tx.commit().await.expect("Failed to commit");
+
+    let response = mine.await.expect("Delete task failed");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    assert!(response.headers().get("x-event-id").is_none());
+}
+
+#[tokio::test]
+async fn commenting_on_a_card_completed_meanwhile_is_refused() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "comment-complete-race").await;
+    let (item_id, _) = add_item(&ctx, "Watch", retro_id, "Flaky deploys").await;
+    sqlx::query!(
+        "UPDATE items SET status = 'HIGHLIGHTED'::status WHERE id = $1",
+        item_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to highlight item");
+
+    // Someone else completes the card while this request waits for its row.
+    let mut tx = ctx.pool.begin().await.expect("Failed to begin");
+    sqlx::query!("SELECT id FROM items WHERE id = $1 FOR UPDATE", item_id)
+        .fetch_one(&mut *tx)
+        .await
+        .expect("Failed to lock item");
+    let mine = {
+        let client = ctx.client.clone();
+        let url = format!("{}/items/{}/comments", ctx.base_url, item_id);
+        tokio::spawn(async move {
+            client
+                .post(url)
+                .form(&[("text", "Too late")])
+                .send()
+                .await
+                .expect("Failed to comment on item")
+        })
+    };
+    wait_for_row_lock(&ctx).await;
+    sqlx::query!(
+        "UPDATE items SET status = 'COMPLETED'::status WHERE id = $1",
+        item_id
+    )
+    .execute(&mut *tx)
+    .await
+    .expect("Failed to complete item");
+    tx.commit().await.expect("Failed to commit");
+
+    let response = mine.await.expect("Comment task failed");
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let comments = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM item_comments WHERE item_id = $1",
+        item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to count comments");
+    assert_eq!(comments, Some(0));
+}
+
+#[tokio::test]
+async fn comments_on_a_discussed_card_are_synced_and_archived() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "card-comments").await;
+    let (item_id, _) = add_item(&ctx, "Watch", retro_id, "Flaky deploys").await;
+    assert_eq!(
+        comment_on_item(&ctx, item_id, "Too early").await.status(),
+        reqwest::StatusCode::BAD_REQUEST,
+        "comments are taken while the card is highlighted"
+    );
+    sqlx::query!(
+        "UPDATE items SET status = 'HIGHLIGHTED'::status WHERE id = $1",
+        item_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to highlight item");
+
+    let response = comment_on_item(&ctx, item_id, "  Pin the runner image  ").await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let event_id: i64 = response
+        .headers()
+        .get("x-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .expect("comment response should carry X-Event-Id");
+    assert_eq!(
+        event_id,
+        latest_event_id(&ctx, item_id, "ITEM_COMMENT_ADDED").await
+    );
+    assert!(response
+        .text()
+        .await
+        .expect("Card HTML")
+        .contains("Pin the runner image"));
+    let payload = sqlx::query_scalar!("SELECT payload FROM events WHERE id = $1", event_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to load event");
+    assert_eq!(payload["item_id"], item_id);
+    assert_eq!(payload["text"], "Pin the runner image");
+    let comment_id = payload["comment_id"].as_i64().expect("comment id");
+    assert_eq!(
+        comment_on_item(&ctx, item_id, "   ").await.status(),
+        reqwest::StatusCode::BAD_REQUEST
+    );
+
+    comment_on_item(&ctx, item_id, "Ask ops about caching").await;
+    let response = ctx
+        .client
+        .delete(format!(
+            "{}/items/{}/comments/{}",
+            ctx.base_url, item_id, comment_id
+        ))
+        .send()
+        .await
+        .expect("Failed to delete comment");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(!response
+        .text()
+        .await
+        .expect("Card HTML")
+        .contains("Pin the runner image"));
+    assert!(latest_event_id(&ctx, item_id, "ITEM_COMMENT_DELETED").await > event_id);
+
+    ctx.client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive retro");
+    let archive_id = sqlx::query_scalar!("SELECT id FROM archives WHERE retro_id = $1", retro_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Archive should exist");
+    let archive = get_html(&ctx, &format!("/retro/card-comments/archives/{archive_id}")).await;
+    assert!(archive.contains("Ask ops about caching"));
+}
+
+async fn save_notes(ctx: &TestContext, item_id: i32, notes: &str) -> reqwest::Response {
+    ctx.client
+        .post(format!("{}/items/{}/notes", ctx.base_url, item_id))
+        .form(&[("notes", notes)])
+        .send()
+        .await
+        .expect("Failed to save notes")
+}
+
+#[tokio::test]
+async fn discussion_notes_are_saved_while_highlighted_and_archived() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "card-notes").await;
+    let (item_id, _) = add_item(&ctx, "Watch", retro_id, "Release cadence").await;
+    assert_eq!(
+        save_notes(&ctx, item_id, "Too early").await.status(),
+        reqwest::StatusCode::BAD_REQUEST,
+        "notes are taken while the card is highlighted"
+    );
+    sqlx::query!(
+        "UPDATE items SET status = 'HIGHLIGHTED'::status WHERE id = $1",
+        item_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to highlight item");
+
+    let notes = "Ship every other week\nOwner: ops";
+    let response = save_notes(&ctx, item_id, notes).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let event_id: i64 = response
+        .headers()
+        .get("x-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .expect("notes response should carry X-Event-Id");
+    assert_eq!(
+        event_id,
+        latest_event_id(&ctx, item_id, "ITEM_NOTES_UPDATED").await
+    );
+    let saved: serde_json::Value = response.json().await.expect("notes response JSON");
+    assert_eq!(saved["notes_revision"], 1);
+    let payload = sqlx::query_scalar!("SELECT payload FROM events WHERE id = $1", event_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to load event");
+    assert_eq!(payload["item_id"], item_id);
+    assert_eq!(payload["notes"], notes);
+    assert_eq!(payload["notes_revision"], 1);
+
+    let response = save_notes(&ctx, item_id, notes).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(
+        response.headers().get("x-event-id").is_none(),
+        "unchanged notes are not broadcast again"
+    );
+    assert_eq!(
+        save_notes(&ctx, item_id, &"x".repeat(5_001)).await.status(),
+        reqwest::StatusCode::BAD_REQUEST
+    );
+
+    ctx.client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive retro");
+    let archive_id = sqlx::query_scalar!("SELECT id FROM archives WHERE retro_id = $1", retro_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Archive should exist");
+    let archive = get_html(&ctx, &format!("/retro/card-notes/archives/{archive_id}")).await;
+    assert!(archive.contains("Ship every other week"));
+}
+
+async fn action_item_from_card(ctx: &TestContext, item_id: i32) -> reqwest::Response {
+    ctx.client
+        .post(format!("{}/items/{}/action-items", ctx.base_url, item_id))
+        .send()
+        .await
+        .expect("Failed to create action item from card")
+}
+
+async fn action_item_source(ctx: &TestContext, action_item_id: i32) -> Option<i32> {
+    sqlx::query_scalar!(
+        "SELECT source_item_id FROM action_items WHERE id = $1",
+        action_item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to load action item")
+}
+
+#[tokio::test]
+async fn action_items_created_from_cards_link_back_to_them() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "card-actions").await;
+    let (item_id, _) = add_item(&ctx, "Bad", retro_id, "Nobody owns the pager").await;
+
+    let response = action_item_from_card(&ctx, item_id).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let html = response.text().await.expect("Action item HTML");
+    assert!(html.contains("Nobody owns the pager"));
+    assert!(html.contains(&format!("href=\"#card-{item_id}\"")));
+    let action_item_id = sqlx::query_scalar!(
+        "SELECT id FROM action_items WHERE source_item_id = $1",
+        item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Action item should link to the card");
+    let payload = sqlx::query_scalar!(
+        "SELECT payload FROM events WHERE action_item_id = $1 AND event_type = 'ACTION_ITEM_CREATED'",
+        action_item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to load event");
+    assert_eq!(payload["text"], "Nobody owns the pager");
+    assert_eq!(payload["source_item_id"], item_id);
+
+    ctx.client
+        .delete(format!("{}/action-items/{}", ctx.base_url, action_item_id))
+        .send()
+        .await
+        .expect("Failed to delete action item");
+    assert_eq!(
+        undo(&ctx, "card-actions").await.status(),
+        reqwest::StatusCode::OK
+    );
+    assert_eq!(
+        action_item_source(&ctx, action_item_id).await,
+        Some(item_id),
+        "undoing the deletion restores the link"
+    );
+
+    let (other_item_id, _) = add_item(&ctx, "Watch", retro_id, "Wrong column").await;
+    action_item_from_card(&ctx, other_item_id).await;
+    let other_action_item_id = sqlx::query_scalar!(
+        "SELECT id FROM action_items WHERE source_item_id = $1",
+        other_item_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Action item should link to the card");
+    delete_item(&ctx, other_item_id).await;
+    assert_eq!(
+        action_item_source(&ctx, other_action_item_id).await,
+        None,
+        "deleting the card keeps its action item"
+    );
+
+    ctx.client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive retro");
+    assert_eq!(
+        action_item_from_card(&ctx, item_id).await.status(),
+        reqwest::StatusCode::BAD_REQUEST
+    );
+    let archive_id = sqlx::query_scalar!("SELECT id FROM archives WHERE retro_id = $1", retro_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Archive should exist");
+    let archive = get_html(&ctx, &format!("/retro/card-actions/archives/{archive_id}")).await;
+    assert!(archive.contains(&format!("id=\"card-{item_id}\"")));
+    assert!(archive.contains(&format!("href=\"#card-{item_id}\"")));
+}
+
+async fn delete_item(ctx: &TestContext, item_id: i32) -> reqwest::Response {
+    ctx.client
+        .delete(format!("{}/items/{}", ctx.base_url, item_id))
+        .send()
+        .await
+        .expect("Failed to delete item")
+}
+
+#[tokio::test]
+async fn editing_a_card_deleted_meanwhile_is_not_found() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "edit-delete-race").await;
+    let (item_id, _) = add_item(&ctx, "Bad", retro_id, "Oops, typo").await;
+
+    // Someone else deletes the card while the edit waits for its row.
+    let mut tx = ctx.pool.begin().await.expect("Failed to begin");
+    sqlx::query!("SELECT id FROM items WHERE id = $1 FOR UPDATE", item_id)
+        .fetch_one(&mut *tx)
+        .await
+        .expect("Failed to lock item");
+    let mine = {
+        let client = ctx.client.clone();
+        let url = format!("{}/items/{}", ctx.base_url, item_id);
+        tokio::spawn(async move {
+            client
+                .post(url)
+                .form(&[("text", "Oops, fixed")])
+                .send()
+                .await
+                .expect("Failed to edit item")
+        })
+    };
+    wait_for_row_lock(&ctx).await;
+    sqlx::query!("DELETE FROM items WHERE id = $1", item_id)
+        .execute(&mut *tx)
+        .await
+        .expect("Failed to delete item");
+    tx.commit().await.expect("Failed to commit");
+
+    let response = mine.await.expect("Edit task failed");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    assert!(response.headers().get("x-event-id").is_none());
+}
+
+#[tokio::test]
+async fn deleting_a_card_takes_its_likes_and_timer_along() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "delete-card").await;
+    let (item_id, _) = add_item(&ctx, "Bad", retro_id, "Oops, wrong column").await;
+    ctx.client
+        .post(format!("{}/items/{}/like", ctx.base_url, item_id))
+        .send()
+        .await
+        .expect("Failed to like item");
+    sqlx::query!(
+        "UPDATE items SET timer_started_at = NOW(), timer_duration_seconds = 300 WHERE id = $1",
+        item_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to start timer");
+    assert!(get_html(&ctx, &format!("/items/{item_id}"))
+        .await
+        .contains("card-delete"));
+
+    let response = delete_item(&ctx, item_id).await;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let event_id: i64 = response
+        .headers()
+        .get("x-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .expect("delete response should carry X-Event-Id");
+    assert_eq!(
+        event_id,
+        latest_event_id(&ctx, item_id, "ITEM_DELETED").await
+    );
+
+    let likes = sqlx::query_scalar!("SELECT COUNT(*) FROM likes WHERE item_id = $1", item_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to count likes");
+    assert_eq!(likes, Some(0));
+    let later_events = sqlx::query_scalar!(
+        r#"SELECT event_type::text as "event_type!" FROM events WHERE item_id = $1 AND id >= $2"#,
+        item_id,
+        event_id
+    )
+    .fetch_all(&ctx.pool)
+    .await
+    .expect("Failed to load events");
+    assert_eq!(
+        later_events,
+        ["ITEM_DELETED"],
+        "the cascaded like deletion emits no ITEM_UNLIKED"
+    );
+
+    assert_eq!(
+        delete_item(&ctx, item_id).await.status(),
+        reqwest::StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn archived_cards_cannot_be_deleted() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "delete-archived").await;
+    let (item_id, _) = add_item(&ctx, "Good", retro_id, "Archived soon").await;
+    ctx.client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive retro");
+
+    assert_eq!(
+        delete_item(&ctx, item_id).await.status(),
+        reqwest::StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn archive_emits_single_retro_archived_event() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "archive-event").await;
+    add_item(&ctx, "Good", retro_id, "To archive").await;
+
+    let response = ctx
+        .client
+        .get(format!("{}/retro/archive-event/events", ctx.base_url))
+        .send()
+        .await
+        .expect("Failed to open SSE stream");
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+
+    ctx.client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive retro");
+
+    let frame = wait_for_sse_event(&mut stream, &mut buffer, "RETRO_ARCHIVED").await;
+    assert_eq!(frame.data["retro_id"].as_i64(), Some(retro_id as i64));
+
+    // Archiving again emits nothing (no active items left).
+    ctx.client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive empty retro");
+    let nothing = tokio::time::timeout(
+        std::time::Duration::from_secs(2),
+        next_sse_frame(&mut stream, &mut buffer),
+    )
+    .await;
+    assert!(
+        nothing.is_err(),
+        "archiving an empty retro should not emit another RETRO_ARCHIVED"
+    );
+}
+
+async fn pruned_through(ctx: &TestContext, retro_id: i32) -> Option<i64> {
+    sqlx::query_scalar!(
+        "SELECT pruned_through FROM event_retention WHERE retro_id = $1",
+        retro_id
+    )
+    .fetch_optional(&ctx.pool)
+    .await
+    .expect("Failed to read event retention")
+}
+
+/// Events are pruned at startup, so a second server on the same database
+/// applies the retention policy to what the test has set up.
+async fn restart_and_wait_for_pruning(ctx: &TestContext, retro_id: i32) -> TestServer {
+    let server = TestServer::start(&ctx.db.database_url).await;
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(10);
+    while pruned_through(ctx, retro_id).await.is_none() {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "timed out waiting for events to be pruned"
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+    server
+}
+
+async fn open_sse_since(ctx: &TestContext, slug: &str, last_event_id: i64) -> reqwest::Response {
+    ctx.client
+        .get(format!("{}/retro/{}/events", ctx.base_url, slug))
+        .header("Last-Event-ID", last_event_id.to_string())
+        .send()
+        .await
+        .expect("Failed to open SSE stream")
+}
+
+#[tokio::test]
+async fn events_past_retention_are_pruned_and_reset_clients() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "retention-age").await;
+    let (_, old_event) = add_item(&ctx, "Good", retro_id, "Long ago").await;
+    let old_event = old_event.expect("add_item should return X-Event-Id");
+    let (_, recent_event) = add_item(&ctx, "Good", retro_id, "Just now").await;
+    let recent_event = recent_event.expect("add_item should return X-Event-Id");
+    sqlx::query!(
+        "UPDATE events SET created_at = NOW() - interval '31 days' WHERE id = $1",
+        old_event
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to backdate event");
+
+    let _server = restart_and_wait_for_pruning(&ctx, retro_id).await;
+    assert_eq!(pruned_through(&ctx, retro_id).await, Some(old_event));
+    let remaining: Vec<i64> =
+        sqlx::query_scalar!("SELECT id FROM events WHERE retro_id = $1", retro_id)
+            .fetch_all(&ctx.pool)
+            .await
+            .expect("Failed to list events");
+    assert_eq!(remaining, vec![recent_event]);
+
+    // A client that missed the deleted event has to start over ...
+    let mut stream = open_sse_since(&ctx, "retention-age", old_event - 1)
+        .await
+        .bytes_stream();
+    let mut buffer = String::new();
+    let frame = next_sse_frame(&mut stream, &mut buffer)
+        .await
+        .expect("SSE stream should send a frame");
+    assert_eq!(frame.event, "RESET");
+    assert_eq!(frame.id, None, "RESET must not move Last-Event-ID");
+
+    // ... one that saw it catches up by replay as usual.
+    let mut stream = open_sse_since(&ctx, "retention-age", old_event)
+        .await
+        .bytes_stream();
+    let mut buffer = String::new();
+    let frame = next_sse_frame(&mut stream, &mut buffer)
+        .await
+        .expect("SSE stream should send a frame");
+    assert_eq!(frame.event, "ITEM_CREATED");
+    assert_eq!(frame.id, Some(recent_event));
+}
+
+#[tokio::test]
+async fn events_before_the_latest_archive_are_pruned() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "retention-archive").await;
+    let (_, archived_card_event) = add_item(&ctx, "Good", retro_id, "Archived").await;
+    let archived_card_event = archived_card_event.expect("add_item should return X-Event-Id");
+    ctx.client
+        .post(format!("{}/retro/{}/archive", ctx.base_url, retro_id))
+        .send()
+        .await
+        .expect("Failed to archive retro");
+    let _server = restart_and_wait_for_pruning(&ctx, retro_id).await;
+    assert_eq!(
+        pruned_through(&ctx, retro_id).await,
+        Some(archived_card_event)
+    );
+    let remaining: Vec<String> = sqlx::query_scalar!(
+        r#"SELECT event_type::text as "event_type!" FROM events WHERE retro_id = $1"#,
+        retro_id
+    )
+    .fetch_all(&ctx.pool)
+    .await
+    .expect("Failed to list events");
+    assert_eq!(
+        remaining,
+        vec!["RETRO_ARCHIVED"],
+        "the archive event itself is kept"
+    );
+}
+
+type Socket =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+async fn open_socket(ctx: &TestContext, slug: &str) -> Socket {
+    let url = format!(
+        "{}/retro/{}/ws",
+        ctx.base_url.replacen("http", "ws", 1),
+        slug
+    );
+    let (socket, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .expect("Failed to open WebSocket");
+    socket
+}
+
+/// Read messages until one with the expected event name appears (skipping
+/// signals and unrelated events).
+async fn wait_for_socket_message(socket: &mut Socket, expected_event: &str) -> Value {
+    use tokio_tungstenite::tungstenite::Message;
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(10);
+    loop {
+        let message = tokio::time::timeout_at(deadline, socket.next())
+            .await
+            .unwrap_or_else(|_| panic!("timed out waiting for {expected_event}"))
+            .expect("WebSocket closed before expected message")
+            .expect("WebSocket error");
+        if let Message::Text(text) = message {
+            let message: Value = serde_json::from_str(&text).expect("messages should be JSON");
+            if message["event"] == expected_event {
+                return message;
+            }
+        }
+    }
+}
+
+async fn send_socket_request(socket: &mut Socket, request: Value) {
+    use futures_util::SinkExt;
+    socket
+        .send(tokio_tungstenite::tungstenite::Message::Text(
+            request.to_string().into(),
+        ))
+        .await
+        .expect("Failed to send WebSocket message");
+}
+
+#[tokio::test]
+async fn websocket_streams_events_and_acknowledges_mutations() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "socket").await;
+    let mut socket = open_socket(&ctx, "socket").await;
+
+    send_socket_request(
+        &mut socket,
+        serde_json::json!({
+            "request_id": "add-1",
+            "type": "add_card",
+            "category": "Watch",
+            "text": "Over the socket",
+        }),
+    )
+    .await;
+    let ack = wait_for_socket_message(&mut socket, "ACK").await;
+    assert_eq!(ack["request_id"], "add-1");
+    let item_id = ack["item_id"]
+        .as_i64()
+        .expect("ACK should carry the item id");
+    let event_id = ack["event_id"]
+        .as_i64()
+        .expect("ACK should carry the event id");
+    let event = wait_for_socket_message(&mut socket, "ITEM_CREATED").await;
+    assert_eq!(
+        event["id"].as_i64(),
+        Some(event_id),
+        "same id as acknowledged"
+    );
+    assert_eq!(event["data"]["item_id"].as_i64(), Some(item_id));
+    assert_eq!(event["data"]["text"], "Over the socket");
+
+    send_socket_request(
+        &mut socket,
+        serde_json::json!({ "request_id": "like-1", "type": "like", "item_id": item_id }),
+    )
+    .await;
+    let ack = wait_for_socket_message(&mut socket, "ACK").await;
+    assert_eq!(ack["request_id"], "like-1");
+    assert_eq!(ack["likes_count"], 1);
+    let event = wait_for_socket_message(&mut socket, "ITEM_LIKED").await;
+    assert_eq!(event["id"], ack["event_id"]);
+
+    send_socket_request(
+        &mut socket,
+        serde_json::json!({
+            "request_id": "add-2",
+            "type": "add_card",
+            "category": "Good",
+            "text": "   ",
+        }),
+    )
+    .await;
+    let error = wait_for_socket_message(&mut socket, "ERROR").await;
+    assert_eq!(error["request_id"], "add-2");
+    assert_eq!(error["status"], 400);
+
+    // Mutations over HTTP reach the socket, too.
+    add_item(&ctx, "Bad", retro_id, "Over HTTP").await;
+    let event = wait_for_socket_message(&mut socket, "ITEM_CREATED").await;
+    assert_eq!(event["data"]["text"], "Over HTTP");
+}
+
+#[tokio::test]
+async fn websocket_replays_events_since_last_event_id() {
+    let ctx = setup().await;
+    let retro_id = create_retro(&ctx, "socket-replay").await;
+    let (_, first) = add_item(&ctx, "Good", retro_id, "Seen").await;
+    add_item(&ctx, "Good", retro_id, "Missed").await;
+
+    let url = format!(
+        "{}/retro/socket-replay/ws?last_event_id={}",
+        ctx.base_url.replacen("http", "ws", 1),
+        first.expect("add_item should return X-Event-Id")
+    );
+    let (mut socket, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .expect("Failed to open WebSocket");
+    let event = wait_for_socket_message(&mut socket, "ITEM_CREATED").await;
+    assert_eq!(event["data"]["text"], "Missed");
+}
+
+#[tokio::test]
+async fn websocket_handshakes_from_foreign_origins_are_rejected() {
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    let ctx = setup().await;
+    create_retro(&ctx, "socket-origin").await;
+
+    let mut request = format!(
+        "{}/retro/socket-origin/ws",
+        ctx.base_url.replacen("http", "ws", 1)
+    )
+    .into_client_request()
+    .expect("valid WebSocket URL");
+    request
+        .headers_mut()
+        .insert("origin", "https://evil.example".parse().unwrap());
+    match tokio_tungstenite::connect_async(request).await {
+        Err(tokio_tungstenite::tungstenite::Error::Http(response)) => {
+            assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+        }
+        other => panic!("expected the handshake to be rejected, got {other:?}"),
+    }
+}
@@ -423,6 +423,41 @@ async fn users_display_name_virtual_column() {
     .await;
 }
 
+#[tokio::test]
+async fn users_display_name_prefers_nickname() {
+    with_fresh_migrated_database("nickname", |pool| async move {
+        sqlx::query!(
+            "INSERT INTO users (github_id, username, full_name, nickname) VALUES ($1, $2, $3, $4)",
+            789_i64,
+            "jdoe",
+            "John Doe",
+            "Johnny"
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to insert user with nickname");
+
+        let display_name: Option<String> =
+            sqlx::query_scalar!("SELECT display_name FROM users WHERE username = $1", "jdoe")
+                .fetch_one(&pool)
+                .await
+                .expect("Failed to query display_name");
+        assert_eq!(display_name.as_deref(), Some("Johnny"));
+
+        let too_long_initials = sqlx::query!(
+            "UPDATE users SET initials = 'ABCDE' WHERE username = $1",
+            "jdoe"
+        )
+        .execute(&pool)
+        .await;
+        assert!(
+            too_long_initials.is_err(),
+            "initials are limited to four characters"
+        );
+    })
+    .await;
+}
+
 // ---------------------------------------------------------------------------
 // SSE event log (migration 021)
 // ---------------------------------------------------------------------------
@@ -978,3 +1013,96 @@ async fn elapsed_sweep_update_is_idempotent() {
     })
     .await;
 }
+
+// ---------------------------------------------------------------------------
+// Presence (migration 029)
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn presence_joins_and_leaves_notify_but_heartbeats_do_not() {
+    with_fresh_migrated_database("presence", |pool| async move {
+        let user_id = insert_test_user(&pool, 1109, "present").await;
+        let retro_id = insert_test_retro(&pool, user_id, "presence-notify").await;
+
+        let mut listener = sqlx::postgres::PgListener::connect_with(&pool)
+            .await
+            .expect("Failed to connect PgListener");
+        listener
+            .listen("rostfacto_presence")
+            .await
+            .expect("Failed to LISTEN");
+
+        sqlx::query!(
+            "INSERT INTO presence (retro_id, user_id, instance_id, connections)
+             VALUES ($1, $2, 'instance', 1)",
+            retro_id,
+            user_id
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to join");
+        let notification = tokio::time::timeout(std::time::Duration::from_secs(3), listener.recv())
+            .await
+            .expect("timed out waiting for join notification")
+            .expect("failed to receive join notification");
+        assert_eq!(notification.payload(), retro_id.to_string());
+
+        sqlx::query!("UPDATE presence SET heartbeat_at = NOW()")
+            .execute(&pool)
+            .await
+            .expect("Failed to heartbeat");
+        let heartbeat =
+            tokio::time::timeout(std::time::Duration::from_millis(500), listener.recv()).await;
+        assert!(heartbeat.is_err(), "heartbeats should not notify");
+
+        sqlx::query!("DELETE FROM presence")
+            .execute(&pool)
+            .await
+            .expect("Failed to leave");
+        let notification = tokio::time::timeout(std::time::Duration::from_secs(3), listener.recv())
+            .await
+            .expect("timed out waiting for leave notification")
+            .expect("failed to receive leave notification");
+        assert_eq!(notification.payload(), retro_id.to_string());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn item_positions_append_and_fit_between_neighbours() {
+    with_fresh_migrated_database("item_positions", |pool| async move {
+        let user_id = insert_test_user(&pool, 1901, "orderer").await;
+        let retro_id = insert_test_retro(&pool, user_id, "positions").await;
+        let first = insert_test_item(&pool, retro_id, user_id, "First").await;
+        let second = insert_test_item(&pool, retro_id, user_id, "Second").await;
+        let positions = sqlx::query_scalar!(
+            "SELECT position FROM items WHERE id = ANY($1) ORDER BY position",
+            &[first, second][..]
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("Failed to load positions");
+        assert_eq!(positions.len(), 2);
+
+        // Splitting the same gap over and over never runs out of keys.
+        let (mut low, mut high) = (positions[0].clone(), positions[1].clone());
+        for round in 0..200 {
+            let key = sqlx::query_scalar!(
+                r#"SELECT item_position_between($1, $2) as "key!""#,
+                low,
+                high
+            )
+            .fetch_one(&pool)
+            .await
+            .expect("Failed to compute position");
+            assert!(low < key && key < high, "{low} < {key} < {high}");
+            assert!(!key.ends_with('0'), "keys never end in the zero digit");
+            if round % 2 == 0 {
+                low = key;
+            } else {
+                high = key;
+            }
+        }
+    })
+    .await;
+}
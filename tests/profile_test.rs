@@ -0,0 +1,364 @@
+mod test_helpers;
+
+use reqwest::Client;
+use sqlx::PgPool;
+use test_helpers::*;
+
+struct TestContext {
+    _db: TestDb,
+    _server: TestServer,
+    client: Client,
+    pool: PgPool,
+    base_url: String,
+}
+
+async fn setup() -> TestContext {
+    let db = TestDb::new().await;
+    let server = TestServer::start(&db.database_url).await;
+    let base_url = server.base_url();
+    let pool = PgPool::connect(&db.database_url)
+        .await
+        .expect("Failed to connect to test DB");
+    TestContext {
+        _db: db,
+        _server: server,
+        client: Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build HTTP client"),
+        pool,
+        base_url,
+    }
+}
+
+async fn demo_user_id(ctx: &TestContext) -> i32 {
+    sqlx::query_scalar!("SELECT id FROM users WHERE github_id = 0")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Demo user should exist")
+}
+
+/// Insert a session and return its (token, public id).
+async fn insert_session(ctx: &TestContext, user_id: i32, user_agent: &str) -> (String, String) {
+    let session = sqlx::query!(
+        "INSERT INTO sessions (user_id, expires_at, is_admin, user_agent, ip_address)
+         VALUES ($1, NOW() + interval '1 day', false, $2, '192.0.2.1')
+         RETURNING id, public_id",
+        user_id,
+        user_agent
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to insert session");
+    (session.id, session.public_id)
+}
+
+async fn session_exists(ctx: &TestContext, public_id: &str) -> bool {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM sessions WHERE public_id = $1) as "exists!""#,
+        public_id
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to check session")
+}
+
+#[tokio::test]
+async fn profile_lists_only_own_sessions() {
+    let ctx = setup().await;
+    let user_id = demo_user_id(&ctx).await;
+    let (token, _) = insert_session(&ctx, user_id, "Firefox on Linux").await;
+    insert_session(&ctx, user_id, "Safari on iPhone").await;
+    let other_user = sqlx::query_scalar!(
+        "INSERT INTO users (github_id, username) VALUES (4242, 'someone-else') RETURNING id"
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to insert user");
+    insert_session(&ctx, other_user, "Other browser").await;
+
+    let html = ctx
+        .client
+        .get(format!("{}/profile", ctx.base_url))
+        .header("Cookie", format!("rostfacto_session={token}"))
+        .send()
+        .await
+        .expect("Failed to load profile")
+        .text()
+        .await
+        .expect("Profile should be HTML");
+    assert!(html.contains("Firefox on Linux"));
+    assert!(html.contains("Safari on iPhone"));
+    assert!(html.contains("192.0.2.1"));
+    assert!(
+        !html.contains("Other browser"),
+        "sessions of other users must not be listed"
+    );
+    assert_eq!(
+        html.matches("This session").count(),
+        1,
+        "the session of the request should be marked"
+    );
+}
+
+#[tokio::test]
+async fn revoking_an_own_session_keeps_the_current_one() {
+    let ctx = setup().await;
+    let user_id = demo_user_id(&ctx).await;
+    let (token, current) = insert_session(&ctx, user_id, "Current").await;
+    let (_, stale) = insert_session(&ctx, user_id, "Stale").await;
+
+    let response = ctx
+        .client
+        .post(format!("{}/profile/sessions/{stale}/revoke", ctx.base_url))
+        .header("Cookie", format!("rostfacto_session={token}"))
+        .send()
+        .await
+        .expect("Failed to revoke session");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    assert_eq!(response.headers()["location"], "/profile");
+    assert!(!session_exists(&ctx, &stale).await);
+    assert!(session_exists(&ctx, &current).await);
+
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/profile/sessions/{current}/revoke",
+            ctx.base_url
+        ))
+        .header("Cookie", format!("rostfacto_session={token}"))
+        .send()
+        .await
+        .expect("Failed to revoke session");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    assert_eq!(
+        response.headers()["location"],
+        "/",
+        "revoking the current session signs out"
+    );
+    let cookie = response.headers()["set-cookie"].to_str().unwrap();
+    assert!(cookie.starts_with("rostfacto_session=;"));
+    assert!(!session_exists(&ctx, &current).await);
+}
+
+#[tokio::test]
+async fn sessions_of_other_users_cannot_be_revoked() {
+    let ctx = setup().await;
+    let other_user = sqlx::query_scalar!(
+        "INSERT INTO users (github_id, username) VALUES (4243, 'victim') RETURNING id"
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to insert user");
+    let (_, public_id) = insert_session(&ctx, other_user, "Victim browser").await;
+
+    let response = ctx
+        .client
+        .post(format!(
+            "{}/profile/sessions/{public_id}/revoke",
+            ctx.base_url
+        ))
+        .send()
+        .await
+        .expect("Failed to revoke session");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    assert!(session_exists(&ctx, &public_id).await);
+}
+
+#[tokio::test]
+async fn sign_out_everywhere_revokes_all_own_sessions() {
+    let ctx = setup().await;
+    let user_id = demo_user_id(&ctx).await;
+    let (token, first) = insert_session(&ctx, user_id, "Laptop").await;
+    let (_, second) = insert_session(&ctx, user_id, "Phone").await;
+
+    let response = ctx
+        .client
+        .post(format!("{}/auth/logout/everywhere", ctx.base_url))
+        .header("Cookie", format!("rostfacto_session={token}"))
+        .send()
+        .await
+        .expect("Failed to sign out everywhere");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    assert!(response.headers()["set-cookie"]
+        .to_str()
+        .unwrap()
+        .starts_with("rostfacto_session=;"));
+    assert!(!session_exists(&ctx, &first).await);
+    assert!(!session_exists(&ctx, &second).await);
+
+    let details = sqlx::query_scalar!(
+        "SELECT details FROM audit_log WHERE action = 'USER_SIGNED_OUT' ORDER BY id DESC LIMIT 1"
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Sign-out should be audited");
+    assert_eq!(details["everywhere"], true);
+    assert_eq!(details["sessions"], 2);
+}
+
+async fn add_card(ctx: &TestContext, retro_id: i32, text: &str) -> String {
+    let response = ctx
+        .client
+        .post(format!("{}/items/Good/{}", ctx.base_url, retro_id))
+        .form(&[("text", text)])
+        .send()
+        .await
+        .expect("Failed to add item");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    response.text().await.expect("Card should be HTML")
+}
+
+async fn update_profile(ctx: &TestContext, form: &[(&str, &str)]) -> reqwest::Response {
+    ctx.client
+        .post(format!("{}/profile", ctx.base_url))
+        .form(form)
+        .send()
+        .await
+        .expect("Failed to update profile")
+}
+
+#[tokio::test]
+async fn profile_changes_how_cards_show_the_author() {
+    let ctx = setup().await;
+    let user_id = demo_user_id(&ctx).await;
+    sqlx::query!(
+        "UPDATE users SET avatar_url = 'https://avatars.githubusercontent.com/u/1' WHERE id = $1",
+        user_id
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to set avatar");
+    let response = ctx
+        .client
+        .post(format!("{}/retros", ctx.base_url))
+        .form(&[("title", "Profile Test"), ("slug", "profile-test")])
+        .send()
+        .await
+        .expect("Failed to create retro");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    let retro_id = sqlx::query_scalar!("SELECT id FROM retrospectives WHERE slug = 'profile-test'")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Created retro should exist");
+
+    let response = update_profile(
+        &ctx,
+        &[
+            ("nickname", " Captain Demo "),
+            ("initials", "cap"),
+            ("show_avatar", "on"),
+        ],
+    )
+    .await;
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    assert_eq!(response.headers()["location"], "/profile");
+
+    let card = add_card(&ctx, retro_id, "With custom initials").await;
+    assert!(card.contains("title=\"Captain Demo\""));
+    assert!(card.contains("[CAP]"));
+    assert!(card.contains("src=\"https://avatars.githubusercontent.com/u/1\""));
+
+    let response = update_profile(&ctx, &[("nickname", "Nick Name"), ("initials", "")]).await;
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+
+    let card = add_card(&ctx, retro_id, "With computed initials").await;
+    assert!(
+        card.contains("[NN]"),
+        "initials are computed from the nickname"
+    );
+    assert!(
+        !card.contains("avatars.githubusercontent.com"),
+        "a hidden avatar is not shown"
+    );
+
+    let details = sqlx::query_scalar!(
+        "SELECT details FROM audit_log WHERE action = 'PROFILE_UPDATED' ORDER BY id DESC LIMIT 1"
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Profile update should be audited");
+    assert_eq!(details["nickname"], "Nick Name");
+    assert_eq!(details["initials"], serde_json::Value::Null);
+    assert_eq!(details["show_avatar"], false);
+}
+
+#[tokio::test]
+async fn cards_clashing_with_other_initials_reload_the_board() {
+    let ctx = setup().await;
+    let response = ctx
+        .client
+        .post(format!("{}/retros", ctx.base_url))
+        .form(&[("title", "Initials Test"), ("slug", "initials-test")])
+        .send()
+        .await
+        .expect("Failed to create retro");
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    let retro_id =
+        sqlx::query_scalar!("SELECT id FROM retrospectives WHERE slug = 'initials-test'")
+            .fetch_one(&ctx.pool)
+            .await
+            .expect("Created retro should exist");
+    let other_user = sqlx::query_scalar!(
+        "INSERT INTO users (github_id, username, full_name)
+         VALUES (4343, 'suhl', 'Sam Uhl') RETURNING id"
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Failed to insert user");
+    sqlx::query!(
+        "INSERT INTO items (retro_id, text, category, status, created_by)
+         VALUES ($1, 'Theirs', 'GOOD'::category, 'CREATED'::status, $2)",
+        retro_id,
+        other_user
+    )
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to insert item");
+
+    // Custom initials taken as-is make Sam's computed ones longer.
+    let response = update_profile(&ctx, &[("nickname", ""), ("initials", "SU")]).await;
+    assert_eq!(response.status(), reqwest::StatusCode::SEE_OTHER);
+    let response = ctx
+        .client
+        .post(format!("{}/items/Good/{}", ctx.base_url, retro_id))
+        .form(&[("text", "Mine")])
+        .send()
+        .await
+        .expect("Failed to add item");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get("hx-refresh")
+            .and_then(|value| value.to_str().ok()),
+        Some("true"),
+        "the other author's cards show new initials"
+    );
+
+    // Further cards change nothing.
+    let response = ctx
+        .client
+        .post(format!("{}/items/Good/{}", ctx.base_url, retro_id))
+        .form(&[("text", "Mine, too")])
+        .send()
+        .await
+        .expect("Failed to add item");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(response.headers().get("hx-refresh").is_none());
+}
+
+#[tokio::test]
+async fn invalid_initials_are_rejected() {
+    let ctx = setup().await;
+    let user_id = demo_user_id(&ctx).await;
+
+    let response = update_profile(&ctx, &[("nickname", ""), ("initials", "S.U.")]).await;
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    let initials = sqlx::query_scalar!("SELECT initials FROM users WHERE id = $1", user_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to load user");
+    assert_eq!(initials, None);
+}